@@ -27,7 +27,7 @@ pub use self::{
 	consensus::ConsensusDataProvider,
 	error::Error,
 	finalize_block::{finalize_block, FinalizeBlockParams},
-	rpc::{CreatedBlock, EngineCommand},
+	rpc::{CreatedBlock, EngineCommand, InvariantCheck},
 	seal_block::{seal_block, SealBlockParams, MAX_PROPOSAL_DURATION},
 };
 use sc_transaction_pool_api::TransactionPool;
@@ -454,7 +454,8 @@ mod tests {
 					needs_justification: false,
 					bad_justification: false,
 					is_new_best: true,
-				}
+				},
+				invariant_violations: Vec::new(),
 			}
 		);
 		// assert that there's a new block in the db.
@@ -544,7 +545,8 @@ mod tests {
 					needs_justification: false,
 					bad_justification: false,
 					is_new_best: true,
-				}
+				},
+				invariant_violations: Vec::new(),
 			}
 		);
 		// assert that there's a new block in the db.
@@ -620,7 +622,8 @@ mod tests {
 					needs_justification: false,
 					bad_justification: false,
 					is_new_best: true,
-				}
+				},
+				invariant_violations: Vec::new(),
 			}
 		);
 		// assert that there's a new block in the db.
@@ -706,7 +709,8 @@ mod tests {
 					needs_justification: false,
 					bad_justification: false,
 					is_new_best: true
-				}
+				},
+				invariant_violations: Vec::new(),
 			}
 		);
 
@@ -23,6 +23,12 @@ use std::sync::Arc;
 /// Sender passed to the authorship task to report errors or successes.
 pub type Sender<T> = Option<oneshot::Sender<std::result::Result<T, Error>>>;
 
+/// [Swanky Node specific]
+/// Check of the contract invariants in a block created by `engine_createBlock`, resolving to the
+/// violations found.
+pub type InvariantCheck<Hash> =
+	Arc<dyn Fn(Hash) -> future::BoxFuture<'static, Vec<serde_json::Value>> + Send + Sync>;
+
 /// Message sent to the background authorship task, usually by RPC.
 pub enum EngineCommand<Hash> {
 	/// Tells the engine to propose a new block
@@ -94,6 +100,7 @@ pub struct ManualSeal<Block: BlockT, Client, Backend> {
 	client: Arc<Client>,
 	backend: Arc<Backend>,
 	import_block_channel: mpsc::Sender<EngineCommand<Block::Hash>>,
+	invariant_check: Option<InvariantCheck<Block::Hash>>,
 }
 
 /// return type of `engine_createBlock`
@@ -103,6 +110,10 @@ pub struct CreatedBlock<Hash> {
 	pub hash: Hash,
 	/// some extra details about the import operation
 	pub aux: ImportedAux,
+	/// [Swanky Node specific]
+	/// violations of the contract invariants found in the created block.
+	#[serde(rename = "invariantViolations", default)]
+	pub invariant_violations: Vec<serde_json::Value>,
 }
 
 impl<Block: BlockT, Client, Backend> ManualSeal<Block, Client, Backend> {
@@ -112,7 +123,14 @@ impl<Block: BlockT, Client, Backend> ManualSeal<Block, Client, Backend> {
 		backend: Arc<Backend>,
 		import_block_channel: mpsc::Sender<EngineCommand<Block::Hash>>,
 	) -> Self {
-		Self { client, backend, import_block_channel }
+		Self { client, backend, import_block_channel, invariant_check: None }
+	}
+
+	/// [Swanky Node specific]
+	/// Checks the contract invariants in the blocks created by `engine_createBlock`.
+	pub fn with_invariant_check(mut self, invariant_check: InvariantCheck<Block::Hash>) -> Self {
+		self.invariant_check = Some(invariant_check);
+		self
 	}
 }
 
@@ -144,7 +162,12 @@ where
 		sink.send(command).await?;
 
 		match receiver.await {
-			Ok(Ok(rx)) => Ok(rx),
+			Ok(Ok(mut created)) => {
+				if let Some(check) = &self.invariant_check {
+					created.invariant_violations = check(created.hash).await;
+				}
+				Ok(created)
+			},
 			Ok(Err(e)) => Err(e.into()),
 			Err(e) => Err(JsonRpseeError::to_call_error(e)),
 		}
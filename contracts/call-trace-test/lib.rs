@@ -0,0 +1,50 @@
+//! Minimal contract calling other contracts, for tracing calls from runtime tests.
+//!
+//! Selectors are fixed so tests can encode calls without the contract metadata.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod call_trace_test {
+	use ink::{
+		env::call::{build_call, ExecutionInput, Selector},
+		prelude::vec::Vec,
+	};
+
+	#[ink(storage)]
+	pub struct CallTraceTest {}
+
+	#[ink(event)]
+	pub struct Relayed {
+		remaining: u32,
+	}
+
+	impl CallTraceTest {
+		#[ink(constructor, payable, selector = 0)]
+		pub fn new() -> Self {
+			Self {}
+		}
+
+		/// Emits `Relayed` and calls `relay` of the first of `callees` with the others.
+		#[ink(message, selector = 1)]
+		pub fn relay(&mut self, callees: Vec<AccountId>) {
+			self.env().emit_event(Relayed { remaining: callees.len() as u32 });
+			if let Some((callee, rest)) = callees.split_first() {
+				build_call::<Environment>()
+					.call(*callee)
+					.gas_limit(0)
+					.exec_input(
+						ExecutionInput::new(Selector::new(1u32.to_be_bytes()))
+							.push_arg(rest.to_vec()),
+					)
+					.returns::<()>()
+					.invoke();
+			}
+		}
+
+		/// Always reverts.
+		#[ink(message, selector = 2)]
+		pub fn fail(&mut self) -> Result<(), u8> {
+			Err(1)
+		}
+	}
+}
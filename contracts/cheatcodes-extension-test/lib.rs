@@ -0,0 +1,54 @@
+//! Minimal contract exercising the cheatcodes chain extension from runtime tests.
+//!
+//! Selectors are fixed so tests can encode calls without the contract metadata.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract(env = cheatcodes_extension::CheatcodesEnvironment)]
+mod cheatcodes_extension_test {
+	use cheatcodes_extension::CheatcodesError;
+	use ink::prelude::vec::Vec;
+
+	#[ink(storage)]
+	pub struct CheatcodesExtensionTest {}
+
+	impl CheatcodesExtensionTest {
+		#[ink(constructor, payable, selector = 0)]
+		pub fn new() -> Self {
+			Self {}
+		}
+
+		#[ink(message, selector = 1)]
+		pub fn set_free_balance(
+			&mut self,
+			who: AccountId,
+			free: Balance,
+		) -> Result<(), CheatcodesError> {
+			self.env().extension().set_free_balance(who, free)
+		}
+
+		#[ink(message, selector = 2)]
+		pub fn next_timestamp(&self) -> Option<Timestamp> {
+			self.env().extension().next_timestamp()
+		}
+
+		#[ink(message, selector = 3)]
+		pub fn set_next_timestamp(&mut self, timestamp: Timestamp) -> Result<(), CheatcodesError> {
+			self.env().extension().set_next_timestamp(timestamp)
+		}
+
+		#[ink(message, selector = 4)]
+		pub fn emit_marker(&mut self, data: Vec<u8>) -> Result<(), CheatcodesError> {
+			self.env().extension().emit_marker(data)
+		}
+
+		#[ink(message, selector = 5)]
+		pub fn is_dev_chain(&self) -> bool {
+			self.env().extension().is_dev_chain()
+		}
+
+		#[ink(message, selector = 6)]
+		pub fn block_timestamp(&self) -> Timestamp {
+			self.env().block_timestamp()
+		}
+	}
+}
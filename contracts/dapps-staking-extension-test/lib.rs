@@ -0,0 +1,96 @@
+//! Minimal contract exercising the dApps staking chain extension from runtime tests.
+//!
+//! Selectors are fixed so tests can encode calls without the contract metadata.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::env::{DefaultEnvironment, Environment};
+
+type AccountId = <DefaultEnvironment as Environment>::AccountId;
+type Balance = <DefaultEnvironment as Environment>::Balance;
+
+/// Non-zero status code returned by the chain extension.
+#[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DappsStakingError(pub u32);
+
+impl ink::env::chain_extension::FromStatusCode for DappsStakingError {
+	fn from_status_code(status_code: u32) -> Result<(), Self> {
+		match status_code {
+			0 => Ok(()),
+			code => Err(Self(code)),
+		}
+	}
+}
+
+/// dApps staking chain extension, registered under extension ID 0.
+#[ink::chain_extension]
+pub trait DappsStakingExtension {
+	type ErrorCode = DappsStakingError;
+
+	#[ink(extension = 1, handle_status = false)]
+	fn read_current_era() -> u32;
+
+	#[ink(extension = 5, handle_status = false)]
+	fn read_staked_amount(staker: AccountId) -> Balance;
+
+	#[ink(extension = 8)]
+	fn bond_and_stake(contract: AccountId, value: Balance) -> Result<(), DappsStakingError>;
+
+	#[ink(extension = 9)]
+	fn unbond_and_unstake(contract: AccountId, value: Balance) -> Result<(), DappsStakingError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum CustomEnvironment {}
+
+impl Environment for CustomEnvironment {
+	const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+	type AccountId = AccountId;
+	type Balance = Balance;
+	type Hash = <DefaultEnvironment as Environment>::Hash;
+	type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+	type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+
+	type ChainExtension = DappsStakingExtension;
+}
+
+#[ink::contract(env = crate::CustomEnvironment)]
+mod dapps_staking_extension_test {
+	use super::DappsStakingError;
+
+	#[ink(storage)]
+	pub struct DappsStakingExtensionTest {}
+
+	impl DappsStakingExtensionTest {
+		#[ink(constructor, payable, selector = 0)]
+		pub fn new() -> Self {
+			Self {}
+		}
+
+		#[ink(message, selector = 1)]
+		pub fn read_current_era(&self) -> u32 {
+			self.env().extension().read_current_era()
+		}
+
+		#[ink(message, selector = 2)]
+		pub fn read_staked_amount(&self, staker: AccountId) -> Balance {
+			self.env().extension().read_staked_amount(staker)
+		}
+
+		/// Stakes `value` of the contract's own balance on itself.
+		#[ink(message, selector = 3)]
+		pub fn bond_and_stake(&mut self, value: Balance) -> Result<(), DappsStakingError> {
+			let contract = self.env().account_id();
+			self.env().extension().bond_and_stake(contract, value)
+		}
+
+		/// Unstakes `value` of the contract's own stake on itself.
+		#[ink(message, selector = 4)]
+		pub fn unbond_and_unstake(&mut self, value: Balance) -> Result<(), DappsStakingError> {
+			let contract = self.env().account_id();
+			self.env().extension().unbond_and_unstake(contract, value)
+		}
+	}
+}
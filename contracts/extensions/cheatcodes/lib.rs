@@ -0,0 +1,79 @@
+//! ink! bindings for the cheatcodes chain extension of swanky-node (extension ID 101).
+//!
+//! The extension is only present in runtimes built with the `dev-cheatcodes` feature, and
+//! refuses everything but [`CheatcodesExtension::is_dev_chain`] unless enabled in genesis.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink::{
+	env::{DefaultEnvironment, Environment},
+	prelude::vec::Vec,
+};
+
+type AccountId = <DefaultEnvironment as Environment>::AccountId;
+type Balance = <DefaultEnvironment as Environment>::Balance;
+type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+
+/// Status codes returned by the chain extension.
+#[derive(scale::Encode, scale::Decode, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum CheatcodesError {
+	NotDevChain = 1,
+	TimestampInPast = 2,
+	MarkerTooLong = 3,
+	UnknownError = 99,
+}
+
+impl ink::env::chain_extension::FromStatusCode for CheatcodesError {
+	fn from_status_code(status_code: u32) -> Result<(), Self> {
+		match status_code {
+			0 => Ok(()),
+			1 => Err(Self::NotDevChain),
+			2 => Err(Self::TimestampInPast),
+			3 => Err(Self::MarkerTooLong),
+			_ => Err(Self::UnknownError),
+		}
+	}
+}
+
+/// Cheatcodes chain extension. The `extension` values are `(101 << 16) | func_id`.
+#[ink::chain_extension]
+pub trait CheatcodesExtension {
+	type ErrorCode = CheatcodesError;
+
+	/// Sets the free balance of `who`.
+	#[ink(extension = 0x650000)]
+	fn set_free_balance(who: AccountId, free: Balance) -> Result<(), CheatcodesError>;
+
+	/// Timestamp set with [`Self::set_next_timestamp`] which isn't applied yet.
+	#[ink(extension = 0x650001, handle_status = false)]
+	fn next_timestamp() -> Option<Timestamp>;
+
+	/// Makes `block_timestamp()` return `timestamp` from the next block on, advancing from there.
+	#[ink(extension = 0x650002)]
+	fn set_next_timestamp(timestamp: Timestamp) -> Result<(), CheatcodesError>;
+
+	/// Deposits a `Cheatcodes::Marker` event carrying `data`.
+	#[ink(extension = 0x650003)]
+	fn emit_marker(data: Vec<u8>) -> Result<(), CheatcodesError>;
+
+	/// Whether cheatcodes are enabled on this chain.
+	#[ink(extension = 0x650004, handle_status = false)]
+	fn is_dev_chain() -> bool;
+}
+
+/// Default environment with [`CheatcodesExtension`] as chain extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum CheatcodesEnvironment {}
+
+impl Environment for CheatcodesEnvironment {
+	const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+	type AccountId = AccountId;
+	type Balance = Balance;
+	type Hash = <DefaultEnvironment as Environment>::Hash;
+	type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+	type Timestamp = Timestamp;
+
+	type ChainExtension = CheatcodesExtension;
+}
@@ -0,0 +1,157 @@
+//! ink! bindings for the `pallet-uniques` chain extension of swanky-node (extension ID 100).
+//!
+//! Calls are dispatched with the calling contract as origin. Either use [`UniquesEnvironment`]
+//! directly, or add [`UniquesExtension`] to the `ChainExtension` of your own environment.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink::{
+	env::{DefaultEnvironment, Environment},
+	prelude::vec::Vec,
+};
+
+type AccountId = <DefaultEnvironment as Environment>::AccountId;
+
+/// Collection ID type of the swanky runtime.
+pub type CollectionId = u32;
+/// Item ID type of the swanky runtime.
+pub type ItemId = u32;
+
+/// Status codes returned by the chain extension.
+#[derive(scale::Encode, scale::Decode, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum UniquesError {
+	NoPermission = 1,
+	UnknownCollection = 2,
+	AlreadyExists = 3,
+	WrongOwner = 4,
+	BadWitness = 5,
+	InUse = 6,
+	Frozen = 7,
+	WrongDelegate = 8,
+	NoDelegate = 9,
+	Unapproved = 10,
+	Unaccepted = 11,
+	Locked = 12,
+	MaxSupplyReached = 13,
+	UnknownItem = 14,
+	TooLong = 15,
+	InsufficientBalance = 16,
+	UnknownError = 99,
+}
+
+impl ink::env::chain_extension::FromStatusCode for UniquesError {
+	fn from_status_code(status_code: u32) -> Result<(), Self> {
+		match status_code {
+			0 => Ok(()),
+			1 => Err(Self::NoPermission),
+			2 => Err(Self::UnknownCollection),
+			3 => Err(Self::AlreadyExists),
+			4 => Err(Self::WrongOwner),
+			5 => Err(Self::BadWitness),
+			6 => Err(Self::InUse),
+			7 => Err(Self::Frozen),
+			8 => Err(Self::WrongDelegate),
+			9 => Err(Self::NoDelegate),
+			10 => Err(Self::Unapproved),
+			11 => Err(Self::Unaccepted),
+			12 => Err(Self::Locked),
+			13 => Err(Self::MaxSupplyReached),
+			14 => Err(Self::UnknownItem),
+			15 => Err(Self::TooLong),
+			16 => Err(Self::InsufficientBalance),
+			_ => Err(Self::UnknownError),
+		}
+	}
+}
+
+/// `pallet-uniques` chain extension. The `extension` values are `(100 << 16) | func_id`.
+#[ink::chain_extension]
+pub trait UniquesExtension {
+	type ErrorCode = UniquesError;
+
+	/// Creates `collection` owned by the contract, with `admin` as admin.
+	#[ink(extension = 0x640000)]
+	fn create(collection: CollectionId, admin: AccountId) -> Result<(), UniquesError>;
+
+	/// Mints `item` of `collection` to `owner`. The contract must be the collection issuer.
+	#[ink(extension = 0x640001)]
+	fn mint(collection: CollectionId, item: ItemId, owner: AccountId) -> Result<(), UniquesError>;
+
+	/// Burns `item`, failing if `check_owner` is given and doesn't own it.
+	#[ink(extension = 0x640002)]
+	fn burn(
+		collection: CollectionId,
+		item: ItemId,
+		check_owner: Option<AccountId>,
+	) -> Result<(), UniquesError>;
+
+	/// Transfers `item` to `dest`. The contract must own it, be the admin or be approved.
+	#[ink(extension = 0x640003)]
+	fn transfer(
+		collection: CollectionId,
+		item: ItemId,
+		dest: AccountId,
+	) -> Result<(), UniquesError>;
+
+	/// Approves `delegate` to transfer `item`.
+	#[ink(extension = 0x640004)]
+	fn approve_transfer(
+		collection: CollectionId,
+		item: ItemId,
+		delegate: AccountId,
+	) -> Result<(), UniquesError>;
+
+	/// Cancels the approval of `item`, failing if `maybe_check_delegate` isn't the delegate.
+	#[ink(extension = 0x640005)]
+	fn cancel_approval(
+		collection: CollectionId,
+		item: ItemId,
+		maybe_check_delegate: Option<AccountId>,
+	) -> Result<(), UniquesError>;
+
+	/// Sets an attribute of `maybe_item`, or of the collection if `None`.
+	#[ink(extension = 0x640006)]
+	fn set_attribute(
+		collection: CollectionId,
+		maybe_item: Option<ItemId>,
+		key: Vec<u8>,
+		value: Vec<u8>,
+	) -> Result<(), UniquesError>;
+
+	/// Clears an attribute of `maybe_item`, or of the collection if `None`.
+	#[ink(extension = 0x640007)]
+	fn clear_attribute(
+		collection: CollectionId,
+		maybe_item: Option<ItemId>,
+		key: Vec<u8>,
+	) -> Result<(), UniquesError>;
+
+	#[ink(extension = 0x640008, handle_status = false)]
+	fn owner(collection: CollectionId, item: ItemId) -> Option<AccountId>;
+
+	#[ink(extension = 0x640009, handle_status = false)]
+	fn collection_owner(collection: CollectionId) -> Option<AccountId>;
+
+	#[ink(extension = 0x64000a, handle_status = false)]
+	fn attribute(collection: CollectionId, item: ItemId, key: Vec<u8>) -> Option<Vec<u8>>;
+
+	#[ink(extension = 0x64000b, handle_status = false)]
+	fn collection_attribute(collection: CollectionId, key: Vec<u8>) -> Option<Vec<u8>>;
+}
+
+/// Default environment with [`UniquesExtension`] as chain extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum UniquesEnvironment {}
+
+impl Environment for UniquesEnvironment {
+	const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+	type AccountId = AccountId;
+	type Balance = <DefaultEnvironment as Environment>::Balance;
+	type Hash = <DefaultEnvironment as Environment>::Hash;
+	type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+	type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+
+	type ChainExtension = UniquesExtension;
+}
@@ -0,0 +1,29 @@
+//! Minimal contract panicking after a known sequence of calls, for testing `swanky-node fuzz`.
+//!
+//! `divide` panics once `set` has set the divisor to zero.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod fuzz_test {
+	#[ink(storage)]
+	pub struct FuzzTest {
+		divisor: u32,
+	}
+
+	impl FuzzTest {
+		#[ink(constructor, selector = 0)]
+		pub fn new() -> Self {
+			Self { divisor: 1 }
+		}
+
+		#[ink(message, selector = 1)]
+		pub fn set(&mut self, divisor: u32) {
+			self.divisor = divisor;
+		}
+
+		#[ink(message, selector = 2)]
+		pub fn divide(&self, value: u32) -> u32 {
+			value / self.divisor
+		}
+	}
+}
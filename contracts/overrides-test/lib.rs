@@ -0,0 +1,41 @@
+//! Minimal contract reading its storage and the block context, for dry-running calls with
+//! overrides from runtime tests.
+//!
+//! Selectors are fixed so tests can encode calls without the contract metadata.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod overrides_test {
+	/// Stored under the root key `0x00000000`.
+	#[ink(storage)]
+	pub struct OverridesTest {
+		value: u32,
+	}
+
+	impl OverridesTest {
+		#[ink(constructor, payable, selector = 0)]
+		pub fn new() -> Self {
+			Self { value: 1 }
+		}
+
+		#[ink(message, selector = 1)]
+		pub fn value(&self) -> u32 {
+			self.value
+		}
+
+		#[ink(message, selector = 2)]
+		pub fn block_number(&self) -> BlockNumber {
+			self.env().block_number()
+		}
+
+		#[ink(message, selector = 3)]
+		pub fn block_timestamp(&self) -> Timestamp {
+			self.env().block_timestamp()
+		}
+
+		#[ink(message, selector = 4)]
+		pub fn balance(&self) -> Balance {
+			self.env().balance()
+		}
+	}
+}
@@ -0,0 +1,120 @@
+//! Minimal contract exercising the uniques chain extension from runtime tests.
+//!
+//! Selectors are fixed so tests can encode calls without the contract metadata.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract(env = uniques_extension::UniquesEnvironment)]
+mod uniques_extension_test {
+	use ink::prelude::vec::Vec;
+	use uniques_extension::{CollectionId, ItemId, UniquesError};
+
+	#[ink(storage)]
+	pub struct UniquesExtensionTest {}
+
+	impl UniquesExtensionTest {
+		#[ink(constructor, payable, selector = 0)]
+		pub fn new() -> Self {
+			Self {}
+		}
+
+		#[ink(message, selector = 1)]
+		pub fn create(&mut self, collection: CollectionId) -> Result<(), UniquesError> {
+			let admin = self.env().account_id();
+			self.env().extension().create(collection, admin)
+		}
+
+		#[ink(message, selector = 2)]
+		pub fn mint(
+			&mut self,
+			collection: CollectionId,
+			item: ItemId,
+			owner: AccountId,
+		) -> Result<(), UniquesError> {
+			self.env().extension().mint(collection, item, owner)
+		}
+
+		#[ink(message, selector = 3)]
+		pub fn burn(&mut self, collection: CollectionId, item: ItemId) -> Result<(), UniquesError> {
+			self.env().extension().burn(collection, item, None)
+		}
+
+		#[ink(message, selector = 4)]
+		pub fn transfer(
+			&mut self,
+			collection: CollectionId,
+			item: ItemId,
+			dest: AccountId,
+		) -> Result<(), UniquesError> {
+			self.env().extension().transfer(collection, item, dest)
+		}
+
+		#[ink(message, selector = 5)]
+		pub fn approve_transfer(
+			&mut self,
+			collection: CollectionId,
+			item: ItemId,
+			delegate: AccountId,
+		) -> Result<(), UniquesError> {
+			self.env().extension().approve_transfer(collection, item, delegate)
+		}
+
+		#[ink(message, selector = 6)]
+		pub fn cancel_approval(
+			&mut self,
+			collection: CollectionId,
+			item: ItemId,
+		) -> Result<(), UniquesError> {
+			self.env().extension().cancel_approval(collection, item, None)
+		}
+
+		#[ink(message, selector = 7)]
+		pub fn set_attribute(
+			&mut self,
+			collection: CollectionId,
+			maybe_item: Option<ItemId>,
+			key: Vec<u8>,
+			value: Vec<u8>,
+		) -> Result<(), UniquesError> {
+			self.env().extension().set_attribute(collection, maybe_item, key, value)
+		}
+
+		#[ink(message, selector = 8)]
+		pub fn clear_attribute(
+			&mut self,
+			collection: CollectionId,
+			maybe_item: Option<ItemId>,
+			key: Vec<u8>,
+		) -> Result<(), UniquesError> {
+			self.env().extension().clear_attribute(collection, maybe_item, key)
+		}
+
+		#[ink(message, selector = 9)]
+		pub fn owner(&self, collection: CollectionId, item: ItemId) -> Option<AccountId> {
+			self.env().extension().owner(collection, item)
+		}
+
+		#[ink(message, selector = 10)]
+		pub fn collection_owner(&self, collection: CollectionId) -> Option<AccountId> {
+			self.env().extension().collection_owner(collection)
+		}
+
+		#[ink(message, selector = 11)]
+		pub fn attribute(
+			&self,
+			collection: CollectionId,
+			item: ItemId,
+			key: Vec<u8>,
+		) -> Option<Vec<u8>> {
+			self.env().extension().attribute(collection, item, key)
+		}
+
+		#[ink(message, selector = 12)]
+		pub fn collection_attribute(
+			&self,
+			collection: CollectionId,
+			key: Vec<u8>,
+		) -> Option<Vec<u8>> {
+			self.env().extension().collection_attribute(collection, key)
+		}
+	}
+}
@@ -0,0 +1,193 @@
+//! Chain extension wrapper tracing every call of a contract to a chain extension.
+//!
+//! Traces are logged to the `runtime::chain_extension` target and collected by [`collect`], which
+//! the runtime uses to append them to the debug message of dry-run calls and instantiations.
+//!
+//! The wrapped extension takes ownership of the call's `Environment`, and with it of the input
+//! and output buffers and the gas meter, so a trace holds what the call returns: the status code,
+//! the output of a call ending the contract execution, or the error trapping the contract.
+//! Extensions handling the call through [`TracedEnvironment`] also report the input length, the
+//! weight charged and the output written to the contract's buffer. The extensions of this
+//! repository do, the assets and dApps staking extensions of Astar don't, and their traces leave
+//! these out.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+use core::fmt;
+use frame_support::weights::Weight;
+use pallet_contracts::{
+	chain_extension::{
+		BufInBufOutState, ChainExtension, Environment, Ext, InitState, RetVal, ReturnFlags,
+	},
+	DebugInfo,
+};
+use sp_core::hexdisplay::HexDisplay;
+use sp_runtime::DispatchError;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+const LOG_TARGET: &str = "runtime::chain_extension";
+
+environmental::environmental!(traces: Vec<ChainExtensionTrace>);
+environmental::environmental!(details: Details);
+
+/// A call of a contract to a chain extension.
+///
+/// Only what the call returns is known unless the extension reports the rest, see the
+/// [crate documentation](crate).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ChainExtensionTrace {
+	pub ext_id: u16,
+	pub func_id: u16,
+	pub details: Details,
+	pub outcome: Outcome,
+}
+
+/// What the extension handling a call reported about it through [`TracedEnvironment`].
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Details {
+	/// Length of the input buffer.
+	pub input_len: Option<u32>,
+	/// Weight charged for the call.
+	pub weight_charged: Option<Weight>,
+	/// Bytes written to the output buffer.
+	pub output: Option<Vec<u8>>,
+}
+
+/// How a chain extension call ended.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Outcome {
+	/// Returned the status code to the contract.
+	Converging(u32),
+	/// Ended the contract execution with the given output.
+	Diverging { flags: ReturnFlags, data: Vec<u8> },
+	/// Trapped the contract.
+	Error(DispatchError),
+}
+
+impl fmt::Display for ChainExtensionTrace {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "chain extension {} function {}: ", self.ext_id, self.func_id)?;
+		if let Some(len) = self.details.input_len {
+			write!(f, "input of {} bytes, ", len)?;
+		}
+		if let Some(weight) = self.details.weight_charged {
+			write!(f, "charged {}/{}, ", weight.ref_time(), weight.proof_size())?;
+		}
+		if let Some(output) = &self.details.output {
+			write!(f, "output 0x{}, ", HexDisplay::from(output))?;
+		}
+		match &self.outcome {
+			Outcome::Converging(code) => write!(f, "returned {}", code),
+			Outcome::Diverging { flags, data } =>
+				write!(f, "diverged with {:?}, output 0x{}", flags, HexDisplay::from(data)),
+			Outcome::Error(error) => write!(f, "failed with {:?}", error),
+		}
+	}
+}
+
+/// Runs `f`, returning its result along with the chain extension calls it traced.
+pub fn collect<R>(f: impl FnOnce() -> R) -> (R, Vec<ChainExtensionTrace>) {
+	let mut collected = Vec::new();
+	let result = traces::using(&mut collected, f);
+	(result, collected)
+}
+
+/// Appends `traces` to a contract's debug message, one line each, if `debug` requests debug output.
+pub fn append_to_debug_message(
+	debug: DebugInfo,
+	debug_message: &mut Vec<u8>,
+	traces: &[ChainExtensionTrace],
+) {
+	if matches!(debug, DebugInfo::Skip) {
+		return
+	}
+	for trace in traces {
+		debug_message.extend_from_slice(format!("{}\n", trace).as_bytes());
+	}
+}
+
+/// Lets chain extensions report the input length, weight charged and output of the call they
+/// handle in its trace.
+pub trait TracedEnvironment {
+	/// Runs `f` handling the call, recording the length of its input and the weight charged by `f`.
+	fn traced<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R;
+
+	/// Writes `buffer` to the output buffer like `Environment::write`, recording it as the output.
+	fn write_traced(
+		&mut self,
+		buffer: &[u8],
+		allow_skip: bool,
+		weight_per_byte: Option<Weight>,
+	) -> Result<(), DispatchError>;
+}
+
+impl<'a, 'b, E: Ext> TracedEnvironment for Environment<'a, 'b, E, BufInBufOutState> {
+	fn traced<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+		let gas_left = self.ext().gas_meter().gas_left();
+		let result = f(self);
+		let weight_charged = gas_left.saturating_sub(self.ext().gas_meter().gas_left());
+		let input_len = self.in_len();
+		details::with(|details| {
+			details.input_len = Some(input_len);
+			details.weight_charged = Some(weight_charged);
+		});
+		result
+	}
+
+	fn write_traced(
+		&mut self,
+		buffer: &[u8],
+		allow_skip: bool,
+		weight_per_byte: Option<Weight>,
+	) -> Result<(), DispatchError> {
+		self.write(buffer, allow_skip, weight_per_byte)?;
+		details::with(|details| details.output = Some(buffer.to_vec()));
+		Ok(())
+	}
+}
+
+/// Chain extension tracing the calls it passes on to `E`.
+pub struct TracingChainExtension<T, E> {
+	inner: E,
+	_phantom: PhantomData<T>,
+}
+
+impl<T, E: Default> Default for TracingChainExtension<T, E> {
+	fn default() -> Self {
+		TracingChainExtension { inner: E::default(), _phantom: PhantomData }
+	}
+}
+
+impl<T, E> ChainExtension<T> for TracingChainExtension<T, E>
+where
+	T: pallet_contracts::Config,
+	E: ChainExtension<T>,
+{
+	fn call<X: Ext<T = T>>(
+		&mut self,
+		env: Environment<X, InitState>,
+	) -> Result<RetVal, DispatchError> {
+		let (ext_id, func_id) = (env.ext_id(), env.func_id());
+		let mut details = Details::default();
+		let result = details::using(&mut details, || self.inner.call(env));
+
+		let outcome = match &result {
+			Ok(RetVal::Converging(code)) => Outcome::Converging(*code),
+			Ok(RetVal::Diverging { flags, data }) =>
+				Outcome::Diverging { flags: *flags, data: data.clone() },
+			Err(error) => Outcome::Error(*error),
+		};
+		let trace = ChainExtensionTrace { ext_id, func_id, details, outcome };
+		log::debug!(target: LOG_TARGET, "{}", trace);
+		traces::with(|traces| traces.push(trace));
+
+		result
+	}
+
+	fn enabled() -> bool {
+		E::enabled()
+	}
+}
@@ -0,0 +1,308 @@
+//! Chain extension exposing `pallet_uniques` to ink! contracts.
+//!
+//! State changing functions are dispatched with the contract's own account as the signed origin,
+//! so a contract can only manage collections and items it owns or has been approved for.
+//! Every function returns a [`UniquesError`] status code, queries additionally write their
+//! SCALE encoded result to the output buffer.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::tokens::nonfungibles::Inspect, BoundedVec};
+use frame_system::RawOrigin;
+use pallet_chain_extension_tracing::TracedEnvironment;
+use pallet_contracts::chain_extension::{
+	BufInBufOutState, ChainExtension, Environment, Ext, InitState, RetVal, SysConfig,
+};
+use pallet_uniques::WeightInfo;
+use sp_runtime::{
+	traits::{Get, StaticLookup},
+	DispatchError, DispatchResult, ModuleError,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Function IDs of the uniques chain extension.
+enum UniquesFunc {
+	Create,
+	Mint,
+	Burn,
+	Transfer,
+	ApproveTransfer,
+	CancelApproval,
+	SetAttribute,
+	ClearAttribute,
+	Owner,
+	CollectionOwner,
+	Attribute,
+	CollectionAttribute,
+}
+
+impl TryFrom<u16> for UniquesFunc {
+	type Error = DispatchError;
+
+	fn try_from(value: u16) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(UniquesFunc::Create),
+			1 => Ok(UniquesFunc::Mint),
+			2 => Ok(UniquesFunc::Burn),
+			3 => Ok(UniquesFunc::Transfer),
+			4 => Ok(UniquesFunc::ApproveTransfer),
+			5 => Ok(UniquesFunc::CancelApproval),
+			6 => Ok(UniquesFunc::SetAttribute),
+			7 => Ok(UniquesFunc::ClearAttribute),
+			8 => Ok(UniquesFunc::Owner),
+			9 => Ok(UniquesFunc::CollectionOwner),
+			10 => Ok(UniquesFunc::Attribute),
+			11 => Ok(UniquesFunc::CollectionAttribute),
+			_ => Err(DispatchError::Other("UniquesExtension: unimplemented func_id")),
+		}
+	}
+}
+
+/// Status code returned to the contract.
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Debug)]
+pub enum UniquesError {
+	Success = 0,
+	NoPermission = 1,
+	UnknownCollection = 2,
+	AlreadyExists = 3,
+	WrongOwner = 4,
+	BadWitness = 5,
+	InUse = 6,
+	Frozen = 7,
+	WrongDelegate = 8,
+	NoDelegate = 9,
+	Unapproved = 10,
+	Unaccepted = 11,
+	Locked = 12,
+	MaxSupplyReached = 13,
+	UnknownItem = 14,
+	/// The attribute key or value is longer than the runtime allows.
+	TooLong = 15,
+	/// The contract can't pay the deposit.
+	InsufficientBalance = 16,
+	UnknownError = 99,
+}
+
+impl From<DispatchError> for UniquesError {
+	fn from(input: DispatchError) -> Self {
+		let error_text = match input {
+			DispatchError::Module(ModuleError { message, .. }) => message,
+			_ => None,
+		};
+		match error_text {
+			Some("NoPermission") => UniquesError::NoPermission,
+			Some("UnknownCollection") => UniquesError::UnknownCollection,
+			Some("AlreadyExists") => UniquesError::AlreadyExists,
+			Some("WrongOwner") => UniquesError::WrongOwner,
+			Some("BadWitness") => UniquesError::BadWitness,
+			Some("InUse") => UniquesError::InUse,
+			Some("Frozen") => UniquesError::Frozen,
+			Some("WrongDelegate") => UniquesError::WrongDelegate,
+			Some("NoDelegate") => UniquesError::NoDelegate,
+			Some("Unapproved") => UniquesError::Unapproved,
+			Some("Unaccepted") => UniquesError::Unaccepted,
+			Some("Locked") => UniquesError::Locked,
+			Some("MaxSupplyReached") => UniquesError::MaxSupplyReached,
+			Some("UnknownItem") => UniquesError::UnknownItem,
+			Some("InsufficientBalance") => UniquesError::InsufficientBalance,
+			_ => UniquesError::UnknownError,
+		}
+	}
+}
+
+type Uniques<T> = pallet_uniques::Pallet<T>;
+type AccountIdOf<T> = <T as SysConfig>::AccountId;
+type CollectionIdOf<T> = <T as pallet_uniques::Config>::CollectionId;
+type ItemIdOf<T> = <T as pallet_uniques::Config>::ItemId;
+type KeyOf<T> = BoundedVec<u8, <T as pallet_uniques::Config>::KeyLimit>;
+type ValueOf<T> = BoundedVec<u8, <T as pallet_uniques::Config>::ValueLimit>;
+
+/// Uniques chain extension.
+pub struct UniquesExtension<T>(PhantomData<T>);
+
+impl<T> Default for UniquesExtension<T> {
+	fn default() -> Self {
+		UniquesExtension(PhantomData)
+	}
+}
+
+impl<T> ChainExtension<T> for UniquesExtension<T>
+where
+	T: pallet_uniques::Config + pallet_contracts::Config,
+{
+	fn call<E: Ext<T = T>>(
+		&mut self,
+		env: Environment<E, InitState>,
+	) -> Result<RetVal, DispatchError> {
+		let func_id = env.func_id().try_into()?;
+		let mut env = env.buf_in_buf_out();
+		env.traced(|env| Self::dispatch(func_id, env))
+	}
+}
+
+impl<T> UniquesExtension<T>
+where
+	T: pallet_uniques::Config + pallet_contracts::Config,
+{
+	fn dispatch<E: Ext<T = T>>(
+		func_id: UniquesFunc,
+		env: &mut Environment<E, BufInBufOutState>,
+	) -> Result<RetVal, DispatchError> {
+		let result = match func_id {
+			UniquesFunc::Create => {
+				let (collection, admin): (CollectionIdOf<T>, AccountIdOf<T>) = env.read_as()?;
+				env.charge_weight(<T as pallet_uniques::Config>::WeightInfo::create())?;
+				Uniques::<T>::create(Self::origin(env), collection, T::Lookup::unlookup(admin))
+			},
+			UniquesFunc::Mint => {
+				let (collection, item, owner): (CollectionIdOf<T>, ItemIdOf<T>, AccountIdOf<T>) =
+					env.read_as()?;
+				env.charge_weight(<T as pallet_uniques::Config>::WeightInfo::mint())?;
+				Uniques::<T>::mint(Self::origin(env), collection, item, T::Lookup::unlookup(owner))
+			},
+			UniquesFunc::Burn => {
+				let (collection, item, check_owner): (
+					CollectionIdOf<T>,
+					ItemIdOf<T>,
+					Option<AccountIdOf<T>>,
+				) = env.read_as()?;
+				env.charge_weight(<T as pallet_uniques::Config>::WeightInfo::burn())?;
+				Uniques::<T>::burn(
+					Self::origin(env),
+					collection,
+					item,
+					check_owner.map(T::Lookup::unlookup),
+				)
+			},
+			UniquesFunc::Transfer => {
+				let (collection, item, dest): (CollectionIdOf<T>, ItemIdOf<T>, AccountIdOf<T>) =
+					env.read_as()?;
+				env.charge_weight(<T as pallet_uniques::Config>::WeightInfo::transfer())?;
+				Uniques::<T>::transfer(
+					Self::origin(env),
+					collection,
+					item,
+					T::Lookup::unlookup(dest),
+				)
+			},
+			UniquesFunc::ApproveTransfer => {
+				let (collection, item, delegate): (CollectionIdOf<T>, ItemIdOf<T>, AccountIdOf<T>) =
+					env.read_as()?;
+				env.charge_weight(<T as pallet_uniques::Config>::WeightInfo::approve_transfer())?;
+				Uniques::<T>::approve_transfer(
+					Self::origin(env),
+					collection,
+					item,
+					T::Lookup::unlookup(delegate),
+				)
+			},
+			UniquesFunc::CancelApproval => {
+				let (collection, item, maybe_check_delegate): (
+					CollectionIdOf<T>,
+					ItemIdOf<T>,
+					Option<AccountIdOf<T>>,
+				) = env.read_as()?;
+				env.charge_weight(<T as pallet_uniques::Config>::WeightInfo::cancel_approval())?;
+				Uniques::<T>::cancel_approval(
+					Self::origin(env),
+					collection,
+					item,
+					maybe_check_delegate.map(T::Lookup::unlookup),
+				)
+			},
+			UniquesFunc::SetAttribute => {
+				env.charge_weight(<T as pallet_uniques::Config>::WeightInfo::set_attribute())?;
+				let (collection, maybe_item, key, value): (
+					CollectionIdOf<T>,
+					Option<ItemIdOf<T>>,
+					Vec<u8>,
+					Vec<u8>,
+				) = Self::read_bounded(env)?;
+				let (Ok(key), Ok(value)) =
+					(KeyOf::<T>::try_from(key), ValueOf::<T>::try_from(value))
+				else {
+					return Ok(RetVal::Converging(UniquesError::TooLong as u32))
+				};
+				Uniques::<T>::set_attribute(Self::origin(env), collection, maybe_item, key, value)
+			},
+			UniquesFunc::ClearAttribute => {
+				env.charge_weight(<T as pallet_uniques::Config>::WeightInfo::clear_attribute())?;
+				let (collection, maybe_item, key): (
+					CollectionIdOf<T>,
+					Option<ItemIdOf<T>>,
+					Vec<u8>,
+				) = Self::read_bounded(env)?;
+				let Ok(key) = KeyOf::<T>::try_from(key) else {
+					return Ok(RetVal::Converging(UniquesError::TooLong as u32))
+				};
+				Uniques::<T>::clear_attribute(Self::origin(env), collection, maybe_item, key)
+			},
+			UniquesFunc::Owner => {
+				let (collection, item): (CollectionIdOf<T>, ItemIdOf<T>) = env.read_as()?;
+				env.charge_weight(T::DbWeight::get().reads(1))?;
+				let owner = <Uniques<T> as Inspect<AccountIdOf<T>>>::owner(&collection, &item);
+				env.write_traced(&owner.encode(), false, None)?;
+				Ok(())
+			},
+			UniquesFunc::CollectionOwner => {
+				let collection: CollectionIdOf<T> = env.read_as()?;
+				env.charge_weight(T::DbWeight::get().reads(1))?;
+				let owner = <Uniques<T> as Inspect<AccountIdOf<T>>>::collection_owner(&collection);
+				env.write_traced(&owner.encode(), false, None)?;
+				Ok(())
+			},
+			UniquesFunc::Attribute => {
+				env.charge_weight(T::DbWeight::get().reads(1))?;
+				let (collection, item, key): (CollectionIdOf<T>, ItemIdOf<T>, Vec<u8>) =
+					Self::read_bounded(env)?;
+				let value =
+					<Uniques<T> as Inspect<AccountIdOf<T>>>::attribute(&collection, &item, &key);
+				env.write_traced(&value.encode(), false, None)?;
+				Ok(())
+			},
+			UniquesFunc::CollectionAttribute => {
+				env.charge_weight(T::DbWeight::get().reads(1))?;
+				let (collection, key): (CollectionIdOf<T>, Vec<u8>) = Self::read_bounded(env)?;
+				let value = <Uniques<T> as Inspect<AccountIdOf<T>>>::collection_attribute(
+					&collection,
+					&key,
+				);
+				env.write_traced(&value.encode(), false, None)?;
+				Ok(())
+			},
+		};
+
+		Ok(RetVal::Converging(Self::status(result) as u32))
+	}
+
+	/// The contract itself is the origin of every dispatched call.
+	fn origin<E: Ext<T = T>>(env: &mut Environment<E, BufInBufOutState>) -> T::RuntimeOrigin {
+		RawOrigin::Signed(env.ext().address().clone()).into()
+	}
+
+	/// Reads input containing attribute keys or values, which have no `MaxEncodedLen`.
+	///
+	/// The input is limited to what the longest valid key and value need, anything longer is
+	/// rejected before decoding.
+	fn read_bounded<E: Ext<T = T>, I: Decode>(
+		env: &mut Environment<E, BufInBufOutState>,
+	) -> Result<I, DispatchError> {
+		let max_len = CollectionIdOf::<T>::max_encoded_len() +
+			Option::<ItemIdOf<T>>::max_encoded_len() +
+			KeyOf::<T>::max_encoded_len() +
+			ValueOf::<T>::max_encoded_len();
+		let len = env.in_len();
+		if len as usize > max_len {
+			return Err(DispatchError::Other("UniquesExtension: input too long"))
+		}
+		env.read_as_unbounded(len)
+	}
+
+	fn status(result: DispatchResult) -> UniquesError {
+		match result {
+			Ok(()) => UniquesError::Success,
+			Err(e) => e.into(),
+		}
+	}
+}
@@ -0,0 +1,127 @@
+//! Chain extension exposing the cheatcodes to ink! contracts.
+//!
+//! Every function returns a [`CheatcodesError`] status code, queries additionally write their
+//! SCALE encoded result to the output buffer.
+
+use crate::{BalanceOf, Config, Error, MomentOf, Pallet};
+use codec::{Decode, Encode};
+use frame_support::traits::Get;
+use pallet_chain_extension_tracing::TracedEnvironment;
+use pallet_contracts::chain_extension::{
+	BufInBufOutState, ChainExtension, Environment, Ext, InitState, RetVal, SysConfig,
+};
+use sp_runtime::{DispatchError, DispatchResult};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Function IDs of the cheatcodes chain extension.
+enum CheatcodesFunc {
+	SetFreeBalance,
+	NextTimestamp,
+	SetNextTimestamp,
+	EmitMarker,
+	IsDevChain,
+}
+
+impl TryFrom<u16> for CheatcodesFunc {
+	type Error = DispatchError;
+
+	fn try_from(value: u16) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(CheatcodesFunc::SetFreeBalance),
+			1 => Ok(CheatcodesFunc::NextTimestamp),
+			2 => Ok(CheatcodesFunc::SetNextTimestamp),
+			3 => Ok(CheatcodesFunc::EmitMarker),
+			4 => Ok(CheatcodesFunc::IsDevChain),
+			_ => Err(DispatchError::Other("CheatcodesExtension: unimplemented func_id")),
+		}
+	}
+}
+
+/// Status code returned to the contract.
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Debug)]
+pub enum CheatcodesError {
+	Success = 0,
+	NotDevChain = 1,
+	TimestampInPast = 2,
+	MarkerTooLong = 3,
+	UnknownError = 99,
+}
+
+/// Cheatcodes chain extension.
+pub struct CheatcodesExtension<T>(PhantomData<T>);
+
+impl<T> Default for CheatcodesExtension<T> {
+	fn default() -> Self {
+		CheatcodesExtension(PhantomData)
+	}
+}
+
+impl<T> ChainExtension<T> for CheatcodesExtension<T>
+where
+	T: Config + pallet_contracts::Config,
+{
+	fn call<E: Ext<T = T>>(
+		&mut self,
+		env: Environment<E, InitState>,
+	) -> Result<RetVal, DispatchError> {
+		let func_id = env.func_id().try_into()?;
+		let mut env = env.buf_in_buf_out();
+		env.traced(|env| Self::dispatch(func_id, env))
+	}
+}
+
+impl<T> CheatcodesExtension<T>
+where
+	T: Config + pallet_contracts::Config,
+{
+	fn dispatch<E: Ext<T = T>>(
+		func_id: CheatcodesFunc,
+		env: &mut Environment<E, BufInBufOutState>,
+	) -> Result<RetVal, DispatchError> {
+		let result = match func_id {
+			CheatcodesFunc::SetFreeBalance => {
+				let (who, free): (<T as SysConfig>::AccountId, BalanceOf<T>) = env.read_as()?;
+				env.charge_weight(T::DbWeight::get().reads_writes(2, 2))?;
+				Pallet::<T>::set_free_balance(who, free)
+			},
+			CheatcodesFunc::NextTimestamp => {
+				env.charge_weight(T::DbWeight::get().reads(1))?;
+				env.write_traced(&Pallet::<T>::next_timestamp().encode(), false, None)?;
+				Ok(())
+			},
+			CheatcodesFunc::SetNextTimestamp => {
+				let timestamp: MomentOf<T> = env.read_as()?;
+				env.charge_weight(T::DbWeight::get().reads_writes(3, 1))?;
+				Pallet::<T>::set_next_timestamp(timestamp)
+			},
+			CheatcodesFunc::EmitMarker => {
+				let len = env.in_len();
+				// Compact length prefix plus data.
+				if len > T::MaxMarkerLen::get().saturating_add(5) {
+					return Ok(RetVal::Converging(CheatcodesError::MarkerTooLong as u32))
+				}
+				env.charge_weight(T::DbWeight::get().reads(1))?;
+				let data: Vec<u8> = env.read_as_unbounded(len)?;
+				let contract = env.ext().address().clone();
+				Pallet::<T>::emit_marker(contract, data)
+			},
+			CheatcodesFunc::IsDevChain => {
+				env.charge_weight(T::DbWeight::get().reads(1))?;
+				env.write_traced(&Pallet::<T>::is_dev_chain().encode(), false, None)?;
+				Ok(())
+			},
+		};
+
+		Ok(RetVal::Converging(Self::status(result) as u32))
+	}
+
+	fn status(result: DispatchResult) -> CheatcodesError {
+		match result {
+			Ok(()) => CheatcodesError::Success,
+			Err(e) if e == Error::<T>::NotDevChain.into() => CheatcodesError::NotDevChain,
+			Err(e) if e == Error::<T>::TimestampInPast.into() => CheatcodesError::TimestampInPast,
+			Err(e) if e == Error::<T>::MarkerTooLong.into() => CheatcodesError::MarkerTooLong,
+			Err(_) => CheatcodesError::UnknownError,
+		}
+	}
+}
@@ -0,0 +1,160 @@
+//! # Cheatcodes Pallet
+//!
+//! [Swanky Node specific]
+//! Lets ink! test contracts manipulate the chain environment from inside a call: set an account's
+//! free balance, override the timestamp contracts see from the next block on, emit marker events
+//! and check whether they run on a dev chain.
+//!
+//! The pallet itself has no dispatchables, the cheatcodes are only reachable through the chain
+//! extension compiled in with the `chain-extension` feature. Everything but the dev chain check is
+//! refused unless [`DevChain`] is set in genesis.
+//!
+//! Block timestamps follow the wall clock, which the timestamp inherent can't go back on. An
+//! overridden timestamp is therefore kept as an offset to `pallet_timestamp`, which contracts
+//! observe through [`ContractsTime`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{
+	ensure,
+	traits::{Currency, Get, Time},
+};
+use sp_runtime::{traits::Saturating, DispatchResult};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+pub use pallet::*;
+
+#[cfg(feature = "chain-extension")]
+pub mod chain_extension;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+type MomentOf<T> = <T as pallet_timestamp::Config>::Moment;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_timestamp::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Currency whose free balances can be set.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Maximum length of the data of a marker event.
+		#[pallet::constant]
+		type MaxMarkerLen: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Whether cheatcodes are enabled on this chain.
+	#[pallet::storage]
+	#[pallet::getter(fn is_dev_chain)]
+	pub type DevChain<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Timestamp contracts will see in the next block, if overridden.
+	#[pallet::storage]
+	#[pallet::getter(fn next_timestamp)]
+	pub type NextTimestamp<T: Config> = StorageValue<_, MomentOf<T>, OptionQuery>;
+
+	/// Offset added to the block timestamp for contracts.
+	#[pallet::storage]
+	#[pallet::getter(fn timestamp_offset)]
+	pub type TimestampOffset<T: Config> = StorageValue<_, MomentOf<T>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(Default)]
+	pub struct GenesisConfig {
+		/// Enables cheatcodes.
+		pub dev_chain: bool,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			DevChain::<T>::put(self.dev_chain);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Free balance of an account was set.
+		FreeBalanceSet { who: T::AccountId, free: BalanceOf<T> },
+		/// Timestamp contracts will see in the next block was set.
+		NextTimestampSet { timestamp: MomentOf<T> },
+		/// A contract emitted a marker.
+		Marker { contract: T::AccountId, data: Vec<u8> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Cheatcodes are not enabled on this chain.
+		NotDevChain,
+		/// The timestamp isn't later than the one contracts currently see.
+		TimestampInPast,
+		/// Marker data is longer than `MaxMarkerLen`.
+		MarkerTooLong,
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Sets the free balance of `who`, creating or reaping the account as needed.
+	pub fn set_free_balance(who: T::AccountId, free: BalanceOf<T>) -> DispatchResult {
+		Self::ensure_dev_chain()?;
+		let _ = T::Currency::make_free_balance_be(&who, free);
+		Self::deposit_event(Event::FreeBalanceSet { who, free });
+		Ok(())
+	}
+
+	/// Makes contracts see `timestamp` in the next block and time advance from there.
+	pub fn set_next_timestamp(timestamp: MomentOf<T>) -> DispatchResult {
+		Self::ensure_dev_chain()?;
+		ensure!(timestamp > ContractsTime::<T>::now(), Error::<T>::TimestampInPast);
+		NextTimestamp::<T>::put(timestamp);
+		Self::deposit_event(Event::NextTimestampSet { timestamp });
+		Ok(())
+	}
+
+	/// Deposits a [`Event::Marker`] on behalf of `contract`.
+	pub fn emit_marker(contract: T::AccountId, data: Vec<u8>) -> DispatchResult {
+		Self::ensure_dev_chain()?;
+		ensure!(data.len() <= T::MaxMarkerLen::get() as usize, Error::<T>::MarkerTooLong);
+		Self::deposit_event(Event::Marker { contract, data });
+		Ok(())
+	}
+
+	fn ensure_dev_chain() -> DispatchResult {
+		ensure!(Self::is_dev_chain(), Error::<T>::NotDevChain);
+		Ok(())
+	}
+}
+
+/// Applies a pending timestamp override once the block timestamp is known.
+impl<T: Config> pallet_timestamp::OnTimestampSet<MomentOf<T>> for Pallet<T> {
+	fn on_timestamp_set(now: MomentOf<T>) {
+		if let Some(timestamp) = NextTimestamp::<T>::take() {
+			TimestampOffset::<T>::put(timestamp.saturating_sub(now));
+		}
+	}
+}
+
+/// Block timestamp including the overridden offset. Meant as `pallet_contracts::Config::Time`.
+pub struct ContractsTime<T>(PhantomData<T>);
+
+impl<T: Config> Time for ContractsTime<T> {
+	type Moment = MomentOf<T>;
+
+	fn now() -> Self::Moment {
+		pallet_timestamp::Pallet::<T>::now().saturating_add(TimestampOffset::<T>::get())
+	}
+}
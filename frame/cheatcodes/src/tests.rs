@@ -0,0 +1,69 @@
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use mock::*;
+
+fn set_timestamp(now: u64) {
+	assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+	Timestamp::on_finalize(System::block_number());
+	System::set_block_number(System::block_number() + 1);
+}
+
+#[test]
+fn set_free_balance_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Cheatcodes::set_free_balance(1, 500));
+		assert_eq!(Balances::free_balance(1), 500);
+
+		System::assert_last_event(Event::<Test>::FreeBalanceSet { who: 1, free: 500 }.into());
+	});
+}
+
+#[test]
+fn cheatcodes_require_dev_chain() {
+	ExtBuilder::default().dev_chain(false).build().execute_with(|| {
+		assert!(!Cheatcodes::is_dev_chain());
+		assert_noop!(Cheatcodes::set_free_balance(1, 500), Error::<Test>::NotDevChain);
+		assert_noop!(Cheatcodes::set_next_timestamp(1_000), Error::<Test>::NotDevChain);
+		assert_noop!(Cheatcodes::emit_marker(1, vec![1]), Error::<Test>::NotDevChain);
+	});
+}
+
+#[test]
+fn next_timestamp_is_applied_in_next_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_timestamp(100);
+		assert_ok!(Cheatcodes::set_next_timestamp(10_000));
+		assert_eq!(Cheatcodes::next_timestamp(), Some(10_000));
+		// Unchanged within the current block.
+		assert_eq!(ContractsTime::<Test>::now(), 100);
+
+		set_timestamp(106);
+		assert_eq!(Cheatcodes::next_timestamp(), None);
+		assert_eq!(ContractsTime::<Test>::now(), 10_000);
+		// The block timestamp itself keeps following the inherent.
+		assert_eq!(Timestamp::now(), 106);
+
+		set_timestamp(112);
+		assert_eq!(ContractsTime::<Test>::now(), 10_006);
+	});
+}
+
+#[test]
+fn next_timestamp_must_be_in_future() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_timestamp(100);
+		assert_noop!(Cheatcodes::set_next_timestamp(100), Error::<Test>::TimestampInPast);
+		assert_ok!(Cheatcodes::set_next_timestamp(101));
+	});
+}
+
+#[test]
+fn emit_marker_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Cheatcodes::emit_marker(7, b"marker".to_vec()));
+		let event = Event::<Test>::Marker { contract: 7, data: b"marker".to_vec() };
+		System::assert_last_event(event.into());
+
+		assert_noop!(Cheatcodes::emit_marker(7, vec![0; 9]), Error::<Test>::MarkerTooLong);
+	});
+}
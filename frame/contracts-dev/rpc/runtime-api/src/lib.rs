@@ -0,0 +1,100 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::{traits::Block as BlockT, DispatchError};
+use sp_std::vec::Vec;
+use sp_weights::Weight;
+
+pub use types::{
+	AccountOverride, CallKind, CallOutcome, CallResult, CallSpec, CallSummary, CodeDetails,
+	ContractDetails, ContractEvent, Estimate, EstimateRequest, Overrides, StepOutcome,
+	StorageDeposit,
+};
+
+mod types;
+
+sp_api::decl_runtime_apis! {
+	pub trait ContractsDevApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Applies `extrinsics` of a block, replaying every contract call and instantiation as a
+		/// dry-run with debug output enabled first. Returns the debug messages by extrinsic index.
+		///
+		/// Must be called on top of `Core::initialize_block` for the block.
+		fn debug_messages(extrinsics: Vec<<Block as BlockT>::Extrinsic>) -> Vec<(u32, Vec<u8>)>;
+
+		/// Applies `extrinsics` of a block, dry-running every contract call and instantiation
+		/// first. Returns their summaries by extrinsic index.
+		///
+		/// Must be called on top of `Core::initialize_block` for the block.
+		fn block_calls(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+		) -> Vec<(u32, CallSummary<AccountId, Balance, <Block as BlockT>::Hash>)>;
+
+		/// Hash of the code of the contract at `address`.
+		fn code_hash(address: AccountId) -> Option<<Block as BlockT>::Hash>;
+
+		/// Searches the smallest gas and storage deposit limits with which a contract call,
+		/// instantiation or code upload succeeds, by dry-running it.
+		fn estimate(
+			request: EstimateRequest<AccountId, Balance, <Block as BlockT>::Hash>,
+		) -> Estimate<AccountId, Balance, <Block as BlockT>::Hash>;
+
+		/// Dry-runs a contract call on top of `overrides`, which are discarded afterwards. Fails
+		/// if the overrides can't be applied.
+		fn call_with_overrides(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			input_data: Vec<u8>,
+			overrides: Overrides<AccountId, Balance, <Block as BlockT>::Hash>,
+		) -> Result<CallOutcome<Balance>, DispatchError>;
+
+		/// Runs `steps` one after another on the same state, which is discarded afterwards. Fails
+		/// with the index of the first step whose runtime call can't be decoded.
+		fn simulate_bundle(
+			steps: Vec<CallSpec<AccountId, Balance, <Block as BlockT>::Hash>>,
+		) -> Result<Vec<StepOutcome<AccountId, Balance, <Block as BlockT>::Hash>>, u32>;
+
+		/// Contracts deployed on the chain.
+		fn contracts() -> Vec<ContractDetails<AccountId, Balance, <Block as BlockT>::Hash>>;
+
+		/// Contract deployed at `address`.
+		fn contract_info(
+			address: AccountId,
+		) -> Option<ContractDetails<AccountId, Balance, <Block as BlockT>::Hash>>;
+
+		/// Code uploaded to the chain.
+		fn codes() -> Vec<CodeDetails<AccountId, Balance, <Block as BlockT>::Hash>>;
+
+		/// Address the configured `AddressGenerator` assigns to a contract instantiated by
+		/// `deployer` from the code `code_hash` with `input_data` and `salt`.
+		fn predict_address(
+			deployer: AccountId,
+			code_hash: <Block as BlockT>::Hash,
+			input_data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> AccountId;
+
+		/// Unsigned extrinsic uploading `code` as `owner` and switching `contracts` to it, keeping
+		/// their storage.
+		fn upgrade_code_extrinsic(
+			owner: AccountId,
+			code: Vec<u8>,
+			contracts: Vec<AccountId>,
+		) -> <Block as BlockT>::Extrinsic;
+
+		/// Unsigned extrinsic removing the contract at `contract`, sending its balance and storage
+		/// deposits to `beneficiary`.
+		fn kill_contract_extrinsic(
+			contract: AccountId,
+			beneficiary: AccountId,
+		) -> <Block as BlockT>::Extrinsic;
+
+		/// Unsigned extrinsic clearing the storage of the contract at `contract`.
+		fn reset_contract_storage_extrinsic(contract: AccountId) -> <Block as BlockT>::Extrinsic;
+	}
+}
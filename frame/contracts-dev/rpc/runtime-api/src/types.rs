@@ -0,0 +1,241 @@
+use codec::{Decode, Encode};
+use pallet_contracts_primitives::{
+	Code, ExecReturnValue, StorageDeposit as ContractsStorageDeposit,
+};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_std::vec::Vec;
+use sp_weights::Weight;
+
+/// How a contract was entered.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum CallKind {
+	Call,
+	Instantiate,
+}
+
+/// How a contract call ended.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum CallResult {
+	/// Returned the given output.
+	Returned(Vec<u8>),
+	/// Reverted with the given output.
+	Reverted(Vec<u8>),
+	/// Trapped with the given error.
+	Failed(DispatchError),
+	/// Completed without output, e.g. a runtime call or a code upload.
+	Completed,
+}
+
+impl From<Result<ExecReturnValue, DispatchError>> for CallResult {
+	fn from(result: Result<ExecReturnValue, DispatchError>) -> Self {
+		match result {
+			Ok(output) if output.did_revert() => CallResult::Reverted(output.data),
+			Ok(output) => CallResult::Returned(output.data),
+			Err(error) => CallResult::Failed(error),
+		}
+	}
+}
+
+/// Storage deposit charged or refunded by a call.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum StorageDeposit<Balance> {
+	Refund(Balance),
+	Charge(Balance),
+}
+
+impl<Balance> From<ContractsStorageDeposit<Balance>> for StorageDeposit<Balance> {
+	fn from(deposit: ContractsStorageDeposit<Balance>) -> Self {
+		match deposit {
+			ContractsStorageDeposit::Refund(amount) => StorageDeposit::Refund(amount),
+			ContractsStorageDeposit::Charge(amount) => StorageDeposit::Charge(amount),
+		}
+	}
+}
+
+/// The outermost call of a contract call or instantiation, as the frames of nested calls aren't
+/// exposed by the `contracts` pallet. The events emitted by all contracts during the call are
+/// included.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CallSummary<AccountId, Balance, Hash> {
+	pub kind: CallKind,
+	pub caller: AccountId,
+	pub callee: AccountId,
+	/// Hash of the code executed by the callee, unless it was removed.
+	pub code_hash: Option<Hash>,
+	pub selector: Option<[u8; 4]>,
+	pub input: Vec<u8>,
+	pub value: Balance,
+	pub gas_consumed: Weight,
+	pub storage_deposit: StorageDeposit<Balance>,
+	pub result: CallResult,
+	/// Events emitted by the callee and the contracts it called, in order.
+	pub events: Vec<ContractEvent<AccountId, Hash>>,
+}
+
+/// Contract call, instantiation or code upload to estimate the limits of.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum EstimateRequest<AccountId, Balance, Hash> {
+	Call {
+		origin: AccountId,
+		dest: AccountId,
+		value: Balance,
+		input_data: Vec<u8>,
+	},
+	Instantiate {
+		origin: AccountId,
+		value: Balance,
+		code: Code<Hash>,
+		data: Vec<u8>,
+		salt: Vec<u8>,
+	},
+	/// Uploads are estimated with deterministic execution enforced.
+	Upload {
+		origin: AccountId,
+		code: Vec<u8>,
+	},
+}
+
+/// Smallest limits with which a contract call, instantiation or code upload succeeds, along with
+/// the outcome of a dry-run with these limits.
+///
+/// Limits of calls failing with any limits are those they require to get to the failure.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Estimate<AccountId, Balance, Hash> {
+	/// Zero for uploads, which are charged the weight of the extrinsic instead.
+	pub gas_limit: Weight,
+	pub storage_deposit_limit: Balance,
+	pub gas_consumed: Weight,
+	pub storage_deposit: StorageDeposit<Balance>,
+	pub result: CallResult,
+	/// Address of the contract instantiated.
+	pub account_id: Option<AccountId>,
+	/// Hash of the code instantiated or uploaded.
+	pub code_hash: Option<Hash>,
+	pub debug_message: Vec<u8>,
+}
+
+/// State and block context a contract call is dry-run on top of.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Overrides<AccountId, Balance, Hash> {
+	pub accounts: Vec<AccountOverride<AccountId, Balance, Hash>>,
+	pub block_number: Option<u32>,
+	/// Timestamp contracts see, in milliseconds.
+	pub timestamp: Option<u64>,
+}
+
+/// Overrides of the state of an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AccountOverride<AccountId, Balance, Hash> {
+	pub address: AccountId,
+	pub free_balance: Option<Balance>,
+	/// Hash of the code the contract at `address` runs, which must be uploaded.
+	pub code_hash: Option<Hash>,
+	/// Values of the storage keys of the contract at `address`, as passed by the contract to
+	/// `get_storage`. Keys with no value are removed.
+	pub storage: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+}
+
+/// Outcome of a contract call dry-run.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CallOutcome<Balance> {
+	pub gas_consumed: Weight,
+	pub gas_required: Weight,
+	pub storage_deposit: StorageDeposit<Balance>,
+	pub result: CallResult,
+	pub debug_message: Vec<u8>,
+}
+
+/// Step of a bundle of calls simulated one after another on the same state.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum CallSpec<AccountId, Balance, Hash> {
+	Call {
+		origin: AccountId,
+		dest: AccountId,
+		value: Balance,
+		gas_limit: Option<Weight>,
+		storage_deposit_limit: Option<Balance>,
+		input_data: Vec<u8>,
+	},
+	Instantiate {
+		origin: AccountId,
+		value: Balance,
+		gas_limit: Option<Weight>,
+		storage_deposit_limit: Option<Balance>,
+		code: Code<Hash>,
+		data: Vec<u8>,
+		salt: Vec<u8>,
+	},
+	/// SCALE encoded runtime call, dispatched with a signed origin.
+	Dispatch {
+		origin: AccountId,
+		call: Vec<u8>,
+	},
+}
+
+/// Event emitted by a contract.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ContractEvent<AccountId, Hash> {
+	pub contract: AccountId,
+	/// Hash of the code of the contract after the call, unless it was terminated.
+	pub code_hash: Option<Hash>,
+	pub data: Vec<u8>,
+}
+
+/// Outcome of a step of a bundle of calls.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct StepOutcome<AccountId, Balance, Hash> {
+	pub result: CallResult,
+	/// Actual weight of runtime calls.
+	pub gas_consumed: Weight,
+	/// Storage deposit of contract calls and instantiations.
+	pub storage_deposit: Option<StorageDeposit<Balance>>,
+	/// Address of the contract instantiated.
+	pub account_id: Option<AccountId>,
+	/// SCALE encoded runtime events deposited by the step.
+	pub events: Vec<Vec<u8>>,
+	pub contract_events: Vec<ContractEvent<AccountId, Hash>>,
+	pub debug_message: Vec<u8>,
+}
+
+/// A contract deployed on the chain.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ContractDetails<AccountId, Balance, Hash> {
+	pub address: AccountId,
+	pub code_hash: Hash,
+	/// Storage deposit held for the storage of the contract.
+	pub storage_deposit: Balance,
+	pub storage_items: u32,
+	/// Size of the storage of the contract, in bytes.
+	pub storage_bytes: u32,
+	/// Free balance of the contract account.
+	pub balance: Balance,
+}
+
+/// Code uploaded to the chain.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CodeDetails<AccountId, Balance, Hash> {
+	pub code_hash: Hash,
+	/// Size of the code as uploaded, in bytes.
+	pub size: u32,
+	/// Whether the code may only be executed deterministically. Code which may not is only
+	/// executed by dry-runs.
+	pub deterministic: bool,
+	/// Number of contracts running the code.
+	pub refcount: u64,
+	pub owner: AccountId,
+	/// Storage deposit held from the owner for the code.
+	pub deposit: Balance,
+}
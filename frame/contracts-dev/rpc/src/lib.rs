@@ -0,0 +1,750 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use futures::StreamExt;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_api::{ApiError, Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::{Block as BlockT, Hash, Header as HeaderT, Zero};
+use sp_weights::Weight;
+
+pub use metadata::{
+	ContractMetadata, DecodedCall, DecodedCallSummary, DecodedEvent, DecodedMessage, MessageInfo,
+	MetadataRegistry, NamedValue,
+};
+use pallet_contracts_dev_rpc_runtime_api::{
+	AccountOverride as RuntimeAccountOverride, CallSpec as RuntimeCallSpec,
+	EstimateRequest as RuntimeEstimateRequest, Overrides as RuntimeOverrides,
+};
+pub use pallet_contracts_dev_rpc_runtime_api::{
+	CallKind, CallOutcome, CallResult, CallSummary, CodeDetails, ContractDetails, ContractEvent,
+	ContractsDevApi as ContractsDevRuntimeApi, Estimate, StepOutcome, StorageDeposit,
+};
+use pallet_contracts_primitives::Code;
+
+mod metadata;
+
+/// Log target of contract debug messages printed by [`log_debug_messages`].
+const LOG_TARGET: &str = "contracts::debug";
+
+/// Number of blocks searched for an extrinsic.
+const SEARCH_DEPTH: u32 = 1024;
+
+/// Debug message of a contract call or instantiation executed by an extrinsic.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtrinsicDebugMessage<Hash> {
+	pub block_hash: Hash,
+	pub extrinsic_index: u32,
+	pub extrinsic_hash: Hash,
+	pub debug_message: String,
+}
+
+/// A contract call to dry-run.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct CallRequest<AccountId, Balance> {
+	pub origin: AccountId,
+	pub dest: AccountId,
+	pub value: Balance,
+	pub gas_limit: Option<Weight>,
+	pub storage_deposit_limit: Option<Balance>,
+	pub input_data: Bytes,
+}
+
+/// Code of a contract to instantiate: new code to upload or the hash of existing code.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContractCode<Hash> {
+	Upload(Bytes),
+	Existing(Hash),
+}
+
+impl<Hash> From<ContractCode<Hash>> for Code<Hash> {
+	fn from(code: ContractCode<Hash>) -> Self {
+		match code {
+			ContractCode::Upload(code) => Code::Upload(code.0),
+			ContractCode::Existing(hash) => Code::Existing(hash),
+		}
+	}
+}
+
+/// A contract call, instantiation or code upload to estimate the limits of.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub enum EstimateRequest<AccountId, Balance, Hash> {
+	#[serde(rename_all = "camelCase")]
+	Call { origin: AccountId, dest: AccountId, value: Balance, input_data: Bytes },
+	#[serde(rename_all = "camelCase")]
+	Instantiate {
+		origin: AccountId,
+		value: Balance,
+		code: ContractCode<Hash>,
+		data: Bytes,
+		salt: Bytes,
+	},
+	#[serde(rename_all = "camelCase")]
+	Upload { origin: AccountId, code: Bytes },
+}
+
+impl<AccountId, Balance, Hash> From<EstimateRequest<AccountId, Balance, Hash>>
+	for RuntimeEstimateRequest<AccountId, Balance, Hash>
+{
+	fn from(request: EstimateRequest<AccountId, Balance, Hash>) -> Self {
+		match request {
+			EstimateRequest::Call { origin, dest, value, input_data } =>
+				RuntimeEstimateRequest::Call { origin, dest, value, input_data: input_data.0 },
+			EstimateRequest::Instantiate { origin, value, code, data, salt } =>
+				RuntimeEstimateRequest::Instantiate {
+					origin,
+					value,
+					code: code.into(),
+					data: data.0,
+					salt: salt.0,
+				},
+			EstimateRequest::Upload { origin, code } =>
+				RuntimeEstimateRequest::Upload { origin, code: code.0 },
+		}
+	}
+}
+
+/// Smallest limits with which a contract call, instantiation or code upload succeeds, along with
+/// the outcome of a dry-run with these limits.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateResult<AccountId, Balance, Hash> {
+	pub gas_limit: Weight,
+	pub storage_deposit_limit: Balance,
+	pub gas_consumed: Weight,
+	pub storage_deposit: StorageDeposit<Balance>,
+	pub result: CallResult,
+	pub account_id: Option<AccountId>,
+	pub code_hash: Option<Hash>,
+	pub debug_message: String,
+}
+
+impl<AccountId, Balance, Hash> From<Estimate<AccountId, Balance, Hash>>
+	for EstimateResult<AccountId, Balance, Hash>
+{
+	fn from(estimate: Estimate<AccountId, Balance, Hash>) -> Self {
+		EstimateResult {
+			gas_limit: estimate.gas_limit,
+			storage_deposit_limit: estimate.storage_deposit_limit,
+			gas_consumed: estimate.gas_consumed,
+			storage_deposit: estimate.storage_deposit,
+			result: estimate.result,
+			account_id: estimate.account_id,
+			code_hash: estimate.code_hash,
+			debug_message: String::from_utf8_lossy(&estimate.debug_message).into_owned(),
+		}
+	}
+}
+
+/// State and block context a contract call is dry-run on top of.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Overrides<AccountId, Balance, Hash> {
+	#[serde(default)]
+	pub accounts: Vec<AccountOverride<AccountId, Balance, Hash>>,
+	pub block_number: Option<u32>,
+	/// Timestamp contracts see, in milliseconds.
+	pub timestamp: Option<u64>,
+}
+
+/// Overrides of the state of an account.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct AccountOverride<AccountId, Balance, Hash> {
+	pub address: AccountId,
+	pub free_balance: Option<Balance>,
+	pub code_hash: Option<Hash>,
+	/// Contract storage keys and their values, removed if `null`.
+	#[serde(default)]
+	pub storage: Vec<(Bytes, Option<Bytes>)>,
+}
+
+impl<AccountId, Balance, Hash> From<Overrides<AccountId, Balance, Hash>>
+	for RuntimeOverrides<AccountId, Balance, Hash>
+{
+	fn from(overrides: Overrides<AccountId, Balance, Hash>) -> Self {
+		let accounts = overrides
+			.accounts
+			.into_iter()
+			.map(|account| RuntimeAccountOverride {
+				address: account.address,
+				free_balance: account.free_balance,
+				code_hash: account.code_hash,
+				storage: account
+					.storage
+					.into_iter()
+					.map(|(key, value)| (key.0, value.map(|value| value.0)))
+					.collect(),
+			})
+			.collect();
+		RuntimeOverrides {
+			accounts,
+			block_number: overrides.block_number,
+			timestamp: overrides.timestamp,
+		}
+	}
+}
+
+/// Outcome of a contract call dry-run.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallOutcomeResult<Balance> {
+	pub gas_consumed: Weight,
+	pub gas_required: Weight,
+	pub storage_deposit: StorageDeposit<Balance>,
+	pub result: CallResult,
+	pub debug_message: String,
+}
+
+impl<Balance> From<CallOutcome<Balance>> for CallOutcomeResult<Balance> {
+	fn from(outcome: CallOutcome<Balance>) -> Self {
+		CallOutcomeResult {
+			gas_consumed: outcome.gas_consumed,
+			gas_required: outcome.gas_required,
+			storage_deposit: outcome.storage_deposit,
+			result: outcome.result,
+			debug_message: String::from_utf8_lossy(&outcome.debug_message).into_owned(),
+		}
+	}
+}
+
+/// Step of a bundle of calls simulated one after another on the same state.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub enum CallSpec<AccountId, Balance, Hash> {
+	Call(CallRequest<AccountId, Balance>),
+	#[serde(rename_all = "camelCase")]
+	Instantiate {
+		origin: AccountId,
+		value: Balance,
+		gas_limit: Option<Weight>,
+		storage_deposit_limit: Option<Balance>,
+		code: ContractCode<Hash>,
+		data: Bytes,
+		salt: Bytes,
+	},
+	/// SCALE encoded runtime call, dispatched with a signed origin.
+	Dispatch { origin: AccountId, call: Bytes },
+}
+
+impl<AccountId, Balance, Hash> From<CallSpec<AccountId, Balance, Hash>>
+	for RuntimeCallSpec<AccountId, Balance, Hash>
+{
+	fn from(spec: CallSpec<AccountId, Balance, Hash>) -> Self {
+		match spec {
+			CallSpec::Call(request) => RuntimeCallSpec::Call {
+				origin: request.origin,
+				dest: request.dest,
+				value: request.value,
+				gas_limit: request.gas_limit,
+				storage_deposit_limit: request.storage_deposit_limit,
+				input_data: request.input_data.0,
+			},
+			CallSpec::Instantiate {
+				origin,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				code,
+				data,
+				salt,
+			} => RuntimeCallSpec::Instantiate {
+				origin,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				code: code.into(),
+				data: data.0,
+				salt: salt.0,
+			},
+			CallSpec::Dispatch { origin, call } =>
+				RuntimeCallSpec::Dispatch { origin, call: call.0 },
+		}
+	}
+}
+
+/// Event emitted by a contract, decoded with the registered metadata of its code.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractEventResult<AccountId> {
+	pub contract: AccountId,
+	pub data: Bytes,
+	pub decoded: Option<DecodedEvent>,
+}
+
+/// Outcome of a step of a bundle of calls.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepResult<AccountId, Balance> {
+	pub result: CallResult,
+	pub gas_consumed: Weight,
+	pub storage_deposit: Option<StorageDeposit<Balance>>,
+	pub account_id: Option<AccountId>,
+	/// SCALE encoded runtime events.
+	pub events: Vec<Bytes>,
+	pub contract_events: Vec<ContractEventResult<AccountId>>,
+	pub debug_message: String,
+}
+
+impl<AccountId, Balance> StepResult<AccountId, Balance> {
+	/// Decodes the events emitted by contracts in `outcome` with the metadata in `registry`.
+	pub fn new<Hash>(
+		outcome: StepOutcome<AccountId, Balance, Hash>,
+		registry: &MetadataRegistry<Hash>,
+	) -> Self
+	where
+		Hash: Eq + std::hash::Hash + Clone + DeserializeOwned,
+	{
+		let contract_events = outcome
+			.contract_events
+			.into_iter()
+			.map(|event| ContractEventResult {
+				decoded: event
+					.code_hash
+					.and_then(|code_hash| registry.get(&code_hash))
+					.and_then(|metadata| metadata.decode_event(&event.data).ok()),
+				contract: event.contract,
+				data: event.data.into(),
+			})
+			.collect();
+		StepResult {
+			result: outcome.result,
+			gas_consumed: outcome.gas_consumed,
+			storage_deposit: outcome.storage_deposit,
+			account_id: outcome.account_id,
+			events: outcome.events.into_iter().map(Into::into).collect(),
+			contract_events,
+			debug_message: String::from_utf8_lossy(&outcome.debug_message).into_owned(),
+		}
+	}
+}
+
+/// RPC trait that provides methods for debugging contracts.
+#[rpc(server)]
+pub trait ContractsDevApi<BlockHash, AccountId, Balance> {
+	/// Debug message of the contract call or instantiation made by the extrinsic
+	/// `extrinsic_hash`, searched in the 1024 blocks up to `at`.
+	#[method(name = "contracts_getDebugMessages")]
+	fn get_debug_messages(
+		&self,
+		extrinsic_hash: BlockHash,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ExtrinsicDebugMessage<BlockHash>>>;
+
+	/// Searches the smallest gas and storage deposit limits with which a contract call,
+	/// instantiation or code upload succeeds at block `at`, by dry-running it.
+	#[method(name = "contracts_estimate")]
+	fn estimate(
+		&self,
+		request: EstimateRequest<AccountId, Balance, BlockHash>,
+		at: Option<BlockHash>,
+	) -> RpcResult<EstimateResult<AccountId, Balance, BlockHash>>;
+
+	/// Dry-runs a contract call at block `at` on top of `overrides` of account balances, contract
+	/// code and storage, and of the block number and timestamp, which are discarded afterwards.
+	#[method(name = "contracts_callWithOverrides")]
+	fn call_with_overrides(
+		&self,
+		call_request: CallRequest<AccountId, Balance>,
+		overrides: Overrides<AccountId, Balance, BlockHash>,
+		at: Option<BlockHash>,
+	) -> RpcResult<CallOutcomeResult<Balance>>;
+
+	/// Runs contract calls, instantiations and runtime calls one after another on the state of
+	/// block `at`, which is discarded afterwards.
+	#[method(name = "contracts_simulateBundle")]
+	fn simulate_bundle(
+		&self,
+		steps: Vec<CallSpec<AccountId, Balance, BlockHash>>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<StepResult<AccountId, Balance>>>;
+
+	/// Address of the contract `deployer` would instantiate from the code `code_hash` with
+	/// `input_data` and `salt`, as assigned by the runtime at block `at`.
+	#[method(name = "contracts_predictAddress")]
+	fn predict_address(
+		&self,
+		deployer: AccountId,
+		code_hash: BlockHash,
+		input_data: Bytes,
+		salt: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<AccountId>;
+
+	/// Registers ink! metadata, the contents of a `.contract` bundle or metadata JSON file, for
+	/// decoding the data of contracts running its code. Returns the code hash.
+	#[method(name = "dev_registerContractMetadata")]
+	fn register_contract_metadata(&self, metadata: serde_json::Value) -> RpcResult<BlockHash>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The contract metadata could not be parsed.
+	InvalidMetadata,
+	/// The state overrides could not be applied.
+	InvalidOverrides,
+	/// A runtime call of a bundle could not be decoded.
+	InvalidBundle,
+	/// The storage of a contract could not be decoded.
+	InvalidStorage,
+	/// There is no contract at the address.
+	ContractNotFound,
+	/// The extrinsic wasn't accepted by the transaction pool.
+	PoolError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidMetadata => 2,
+			Error::InvalidOverrides => 3,
+			Error::InvalidBundle => 4,
+			Error::InvalidStorage => 5,
+			Error::ContractNotFound => 6,
+			Error::PoolError => 7,
+		}
+	}
+}
+
+/// Replays block `hash` on top of its parent's state, passing the runtime API, the parent hash
+/// and the extrinsics of the block to `replay`.
+fn replay_block<Client, Block, R>(
+	client: &Client,
+	hash: Block::Hash,
+	replay: impl FnOnce(&Client::Api, Block::Hash, Vec<Block::Extrinsic>) -> Result<R, ApiError>,
+) -> Result<R, String>
+where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+	Client::Api: Core<Block>,
+{
+	let header = client
+		.header(hash)
+		.map_err(|e| e.to_string())?
+		.ok_or_else(|| format!("Unknown block {:?}", hash))?;
+	let extrinsics = client
+		.block_body(hash)
+		.map_err(|e| e.to_string())?
+		.ok_or_else(|| format!("Unknown block body {:?}", hash))?;
+
+	let parent_hash = *header.parent_hash();
+	let runtime_api = client.runtime_api();
+	runtime_api.initialize_block(parent_hash, &header).map_err(|e| e.to_string())?;
+	replay(&runtime_api, parent_hash, extrinsics).map_err(|e| e.to_string())
+}
+
+/// Block and index of the extrinsic `extrinsic_hash`, searched in the blocks up to `at`.
+fn find_extrinsic<Client, Block>(
+	client: &Client,
+	extrinsic_hash: Block::Hash,
+	at: Block::Hash,
+) -> Result<Option<(Block::Hash, u32)>, String>
+where
+	Block: BlockT,
+	Client: HeaderBackend<Block> + BlockBackend<Block>,
+{
+	let mut hash = at;
+	for _ in 0..SEARCH_DEPTH {
+		let header = match client.header(hash).map_err(|e| e.to_string())? {
+			Some(header) => header,
+			None => break,
+		};
+		let extrinsics = client.block_body(hash).map_err(|e| e.to_string())?.unwrap_or_default();
+		let index = extrinsics.iter().position(|xt| {
+			<<Block::Header as HeaderT>::Hashing as Hash>::hash_of(xt) == extrinsic_hash
+		});
+		if let Some(index) = index {
+			return Ok(Some((hash, index as u32)))
+		}
+		if header.number().is_zero() {
+			break
+		}
+		hash = *header.parent_hash();
+	}
+	Ok(None)
+}
+
+/// Debug messages of the contract calls and instantiations in block `hash`, by replaying it.
+pub fn block_debug_messages<Client, Block, AccountId, Balance>(
+	client: &Client,
+	hash: Block::Hash,
+) -> Result<Vec<ExtrinsicDebugMessage<Block::Hash>>, String>
+where
+	Block: BlockT,
+	AccountId: Codec,
+	Balance: Codec,
+	Client: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+	Client::Api: ContractsDevRuntimeApi<Block, AccountId, Balance>,
+{
+	replay_block(client, hash, |runtime_api, parent_hash, extrinsics| {
+		let messages = runtime_api.debug_messages(parent_hash, extrinsics.clone())?;
+		Ok(messages
+			.into_iter()
+			.map(|(index, message)| ExtrinsicDebugMessage {
+				block_hash: hash,
+				extrinsic_index: index,
+				extrinsic_hash: <<Block::Header as HeaderT>::Hashing as Hash>::hash_of(
+					&extrinsics[index as usize],
+				),
+				debug_message: String::from_utf8_lossy(&message).into_owned(),
+			})
+			.collect())
+	})
+}
+
+/// Summaries of the contract calls and instantiations in block `hash`, by extrinsic index, by
+/// replaying it.
+pub fn block_calls<Client, Block, AccountId, Balance>(
+	client: &Client,
+	hash: Block::Hash,
+) -> Result<Vec<(u32, CallSummary<AccountId, Balance, Block::Hash>)>, String>
+where
+	Block: BlockT,
+	AccountId: Codec,
+	Balance: Codec,
+	Client: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+	Client::Api: ContractsDevRuntimeApi<Block, AccountId, Balance>,
+{
+	replay_block(client, hash, |runtime_api, parent_hash, extrinsics| {
+		runtime_api.block_calls(parent_hash, extrinsics)
+	})
+}
+
+/// Prints the debug messages of the contract calls and instantiations in every imported block,
+/// followed by their calls and events decoded with the metadata in `registry`.
+pub async fn log_debug_messages<Client, Block, AccountId, Balance>(
+	client: Arc<Client>,
+	registry: MetadataRegistry<Block::Hash>,
+) where
+	Block: BlockT,
+	AccountId: Codec + std::fmt::Display,
+	Balance: Codec,
+	Client: ProvideRuntimeApi<Block>
+		+ HeaderBackend<Block>
+		+ BlockBackend<Block>
+		+ BlockchainEvents<Block>,
+	Client::Api: ContractsDevRuntimeApi<Block, AccountId, Balance>,
+{
+	let mut imported = client.import_notification_stream();
+	while let Some(notification) = imported.next().await {
+		match block_debug_messages::<_, _, AccountId, Balance>(&*client, notification.hash) {
+			Ok(messages) =>
+				for message in messages.into_iter().filter(|m| !m.debug_message.is_empty()) {
+					log::info!(
+						target: LOG_TARGET,
+						"#{} extrinsic {} ({:?}):\n{}",
+						notification.header.number(),
+						message.extrinsic_index,
+						message.extrinsic_hash,
+						message.debug_message.trim_end(),
+					);
+				},
+			Err(e) => log::warn!(
+				target: LOG_TARGET,
+				"Unable to replay block {:?}: {}",
+				notification.hash,
+				e
+			),
+		}
+		if registry.is_empty() {
+			continue
+		}
+
+		match block_calls::<_, _, AccountId, Balance>(&*client, notification.hash) {
+			Ok(calls) =>
+				for (index, call) in calls {
+					let call = DecodedCallSummary::new(call, &registry);
+					let mut lines = String::new();
+					if call.write_lines(&mut lines).is_ok() {
+						log::info!(
+							target: LOG_TARGET,
+							"#{} extrinsic {} call:\n{}",
+							notification.header.number(),
+							index,
+							lines.trim_end(),
+						);
+					}
+				},
+			Err(e) => log::warn!(
+				target: LOG_TARGET,
+				"Unable to replay the contract calls of block {:?}: {}",
+				notification.hash,
+				e
+			),
+		}
+	}
+}
+
+/// Provides RPC methods for debugging contracts.
+pub struct ContractsDev<C, Block: BlockT> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	/// Metadata of contracts registered by clients.
+	registry: MetadataRegistry<Block::Hash>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block: BlockT> ContractsDev<C, Block> {
+	/// Creates a new instance of the ContractsDev Rpc helper.
+	pub fn new(client: Arc<C>, registry: MetadataRegistry<Block::Hash>) -> Self {
+		Self { client, registry, _marker: Default::default() }
+	}
+}
+
+fn runtime_error(message: &str, e: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(Error::RuntimeError.into(), message, Some(e.to_string())))
+		.into()
+}
+
+impl<Client, Block, AccountId, Balance>
+	ContractsDevApiServer<<Block as BlockT>::Hash, AccountId, Balance>
+	for ContractsDev<Client, Block>
+where
+	Block: BlockT,
+	AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Client: Send
+		+ Sync
+		+ 'static
+		+ ProvideRuntimeApi<Block>
+		+ HeaderBackend<Block>
+		+ BlockBackend<Block>,
+	Client::Api: ContractsDevRuntimeApi<Block, AccountId, Balance>,
+{
+	fn get_debug_messages(
+		&self,
+		extrinsic_hash: <Block as BlockT>::Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<ExtrinsicDebugMessage<<Block as BlockT>::Hash>>> {
+		let error = "Unable to get debug messages.";
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let Some((hash, _)) = find_extrinsic(&*self.client, extrinsic_hash, at)
+			.map_err(|e| runtime_error(error, e))?
+		else {
+			return Ok(None)
+		};
+
+		let messages = block_debug_messages::<_, _, AccountId, Balance>(&*self.client, hash)
+			.map_err(|e| runtime_error(error, e))?;
+		Ok(messages.into_iter().find(|m| m.extrinsic_hash == extrinsic_hash))
+	}
+
+	fn estimate(
+		&self,
+		request: EstimateRequest<AccountId, Balance, <Block as BlockT>::Hash>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<EstimateResult<AccountId, Balance, <Block as BlockT>::Hash>> {
+		let runtime_api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		runtime_api
+			.estimate(at_hash, request.into())
+			.map(Into::into)
+			.map_err(|e| runtime_error("Unable to estimate limits.", e))
+	}
+
+	fn call_with_overrides(
+		&self,
+		call_request: CallRequest<AccountId, Balance>,
+		overrides: Overrides<AccountId, Balance, <Block as BlockT>::Hash>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<CallOutcomeResult<Balance>> {
+		let runtime_api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let CallRequest { origin, dest, value, gas_limit, storage_deposit_limit, input_data } =
+			call_request;
+		runtime_api
+			.call_with_overrides(
+				at_hash,
+				origin,
+				dest,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				input_data.0,
+				overrides.into(),
+			)
+			.map_err(|e| runtime_error("Unable to call contract.", e))?
+			.map(Into::into)
+			.map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::InvalidOverrides.into(),
+					"Unable to apply overrides.",
+					Some(format!("{:?}", e)),
+				))
+				.into()
+			})
+	}
+
+	fn simulate_bundle(
+		&self,
+		steps: Vec<CallSpec<AccountId, Balance, <Block as BlockT>::Hash>>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<StepResult<AccountId, Balance>>> {
+		let runtime_api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let steps = steps.into_iter().map(Into::into).collect();
+		let outcomes = runtime_api
+			.simulate_bundle(at_hash, steps)
+			.map_err(|e| runtime_error("Unable to simulate bundle.", e))?
+			.map_err(|index| {
+				CallError::Custom(ErrorObject::owned(
+					Error::InvalidBundle.into(),
+					"Unable to decode runtime call.",
+					Some(format!("step {}", index)),
+				))
+			})?;
+		Ok(outcomes.into_iter().map(|outcome| StepResult::new(outcome, &self.registry)).collect())
+	}
+
+	fn predict_address(
+		&self,
+		deployer: AccountId,
+		code_hash: <Block as BlockT>::Hash,
+		input_data: Bytes,
+		salt: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<AccountId> {
+		let runtime_api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		runtime_api
+			.predict_address(at_hash, deployer, code_hash, input_data.0, salt.0)
+			.map_err(|e| runtime_error("Unable to predict address.", e))
+	}
+
+	fn register_contract_metadata(
+		&self,
+		metadata: serde_json::Value,
+	) -> RpcResult<<Block as BlockT>::Hash> {
+		self.registry.register(metadata).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::InvalidMetadata.into(),
+				"Unable to register contract metadata.",
+				Some(e),
+			))
+			.into()
+		})
+	}
+}
@@ -0,0 +1,823 @@
+//! Registry of ink! contract metadata, decoding the SCALE encoded data of contracts into names and
+//! values.
+//!
+//! Metadata is registered by the hash of the code it describes, as found in the `source.hash` of
+//! the metadata generated by `cargo contract` for ink! 4 contracts.
+
+use std::{
+	collections::{BTreeMap, HashMap},
+	fmt,
+	sync::{Arc, RwLock},
+};
+
+use codec::{Compact, Decode};
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use sp_core::{hexdisplay::HexDisplay, Bytes};
+
+use crate::{CallKind, CallResult, CallSummary};
+
+/// Metadata of the contracts known to the node, by code hash.
+#[derive(Clone)]
+pub struct MetadataRegistry<Hash> {
+	contracts: Arc<RwLock<HashMap<Hash, Arc<ContractMetadata>>>>,
+}
+
+impl<Hash> Default for MetadataRegistry<Hash> {
+	fn default() -> Self {
+		Self { contracts: Default::default() }
+	}
+}
+
+impl<Hash: Eq + std::hash::Hash + Clone + DeserializeOwned> MetadataRegistry<Hash> {
+	/// Registers the contents of a `.contract` bundle or metadata JSON file, replacing metadata
+	/// previously registered for the same code. Returns the code hash.
+	pub fn register(&self, metadata: Value) -> Result<Hash, String> {
+		let project: InkProject<Hash> =
+			serde_json::from_value(metadata).map_err(|e| format!("Invalid metadata: {}", e))?;
+		let code_hash = project.source.hash.clone();
+		self.contracts.write().expect("lock is not poisoned; qed").insert(
+			code_hash.clone(),
+			Arc::new(ContractMetadata {
+				name: project.contract.name,
+				spec: project.spec,
+				storage: project.storage.map(|storage| storage.root),
+				registry: project.registry,
+			}),
+		);
+		Ok(code_hash)
+	}
+
+	/// Metadata registered for the code `code_hash`.
+	pub fn get(&self, code_hash: &Hash) -> Option<Arc<ContractMetadata>> {
+		self.contracts
+			.read()
+			.expect("lock is not poisoned; qed")
+			.get(code_hash)
+			.cloned()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.contracts.read().expect("lock is not poisoned; qed").is_empty()
+	}
+}
+
+/// The parts of the ink! 4 metadata format needed for decoding.
+#[derive(Deserialize)]
+struct InkProject<Hash> {
+	source: Source<Hash>,
+	contract: Contract,
+	spec: Spec,
+	storage: Option<StorageSpec>,
+	#[serde(flatten)]
+	registry: PortableRegistry,
+}
+
+#[derive(Deserialize)]
+struct Source<Hash> {
+	hash: Hash,
+}
+
+#[derive(Deserialize)]
+struct Contract {
+	name: String,
+}
+
+#[derive(Deserialize)]
+struct Spec {
+	constructors: Vec<MessageSpec>,
+	messages: Vec<MessageSpec>,
+	events: Vec<EventSpec>,
+	lang_error: Option<TypeSpec>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MessageSpec {
+	label: String,
+	selector: Bytes,
+	args: Vec<ArgSpec>,
+	return_type: Option<TypeSpec>,
+}
+
+#[derive(Deserialize)]
+struct EventSpec {
+	label: String,
+	args: Vec<ArgSpec>,
+}
+
+#[derive(Deserialize)]
+struct ArgSpec {
+	label: String,
+	#[serde(rename = "type")]
+	ty: TypeSpec,
+}
+
+#[derive(Deserialize)]
+struct TypeSpec {
+	#[serde(rename = "type")]
+	id: u32,
+}
+
+#[derive(Deserialize)]
+struct StorageSpec {
+	root: RootLayout,
+}
+
+/// Layout of the storage of an ink! 4 contract, which has no hash layouts.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Layout {
+	Leaf(LeafLayout),
+	Root(RootLayout),
+	Array(ArrayLayout),
+	Struct(StructLayout),
+	Enum(EnumLayout),
+}
+
+/// A value encoded in the cell of the enclosing root.
+#[derive(Deserialize)]
+struct LeafLayout {
+	ty: u32,
+}
+
+/// A cell of its own, at the storage key `root_key`, or the cells of a `Mapping`.
+#[derive(Deserialize)]
+struct RootLayout {
+	root_key: Bytes,
+	layout: Box<Layout>,
+	/// Type of the `Lazy` or `Mapping`, since ink! 4.3.
+	ty: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct ArrayLayout {
+	len: u32,
+	layout: Box<Layout>,
+}
+
+#[derive(Deserialize)]
+struct StructLayout {
+	name: String,
+	fields: Vec<FieldLayout>,
+}
+
+#[derive(Deserialize)]
+struct FieldLayout {
+	name: String,
+	layout: Layout,
+}
+
+#[derive(Deserialize)]
+struct EnumLayout {
+	name: String,
+	variants: BTreeMap<u8, StructLayout>,
+}
+
+/// Metadata of a contract's code.
+pub struct ContractMetadata {
+	name: String,
+	spec: Spec,
+	storage: Option<RootLayout>,
+	registry: PortableRegistry,
+}
+
+/// A named value decoded from contract data.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamedValue {
+	pub name: String,
+	pub value: Value,
+}
+
+/// A message or constructor of a contract.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MessageInfo {
+	pub label: String,
+	pub selector: Vec<u8>,
+	/// Types of the arguments, in the type registry of the metadata.
+	pub args: Vec<u32>,
+}
+
+/// A message or constructor call decoded from its input.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedMessage {
+	pub label: String,
+	pub args: Vec<NamedValue>,
+}
+
+/// An event decoded from the data emitted by a contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedEvent {
+	pub label: String,
+	pub fields: Vec<NamedValue>,
+}
+
+impl fmt::Display for DecodedMessage {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}(", self.label)?;
+		for (i, arg) in self.args.iter().enumerate() {
+			let separator = if i == 0 { "" } else { ", " };
+			write!(f, "{}{}: {}", separator, arg.name, arg.value)?;
+		}
+		write!(f, ")")
+	}
+}
+
+impl fmt::Display for DecodedEvent {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.label)?;
+		for (i, field) in self.fields.iter().enumerate() {
+			let separator = if i == 0 { " { " } else { ", " };
+			write!(f, "{}{}: {}", separator, field.name, field.value)?;
+		}
+		if !self.fields.is_empty() {
+			write!(f, " }}")?;
+		}
+		Ok(())
+	}
+}
+
+impl ContractMetadata {
+	/// Name of the contract.
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Messages of the contract, or its constructors if `constructor` is set.
+	pub fn messages(&self, constructor: bool) -> Vec<MessageInfo> {
+		let specs = if constructor { &self.spec.constructors } else { &self.spec.messages };
+		specs
+			.iter()
+			.map(|spec| MessageInfo {
+				label: spec.label.clone(),
+				selector: spec.selector.0.clone(),
+				args: spec.args.iter().map(|arg| arg.ty.id).collect(),
+			})
+			.collect()
+	}
+
+	/// Registry of the types used by the contract.
+	pub fn types(&self) -> &PortableRegistry {
+		&self.registry
+	}
+
+	fn find(&self, constructor: bool, selector: &[u8]) -> Option<&MessageSpec> {
+		let specs = if constructor { &self.spec.constructors } else { &self.spec.messages };
+		specs.iter().find(|spec| spec.selector.0 == selector)
+	}
+
+	/// Decodes the input of a message, or of a constructor if `constructor` is set.
+	pub fn decode_input(&self, constructor: bool, input: &[u8]) -> Result<DecodedMessage, String> {
+		let (selector, mut args) = input.split_at(4.min(input.len()));
+		let spec = self
+			.find(constructor, selector)
+			.ok_or_else(|| format!("Unknown selector 0x{}", HexDisplay::from(&selector)))?;
+		Ok(DecodedMessage {
+			label: spec.label.clone(),
+			args: self.decode_args(&spec.args, &mut args)?,
+		})
+	}
+
+	/// Decodes the output of the message or constructor with the given `selector`.
+	///
+	/// The output of calls with unknown selectors is decoded as `LangError`, which ink! returns
+	/// when it fails to dispatch a call.
+	pub fn decode_output(
+		&self,
+		constructor: bool,
+		selector: &[u8],
+		output: &[u8],
+	) -> Result<Value, String> {
+		let mut output = output;
+		match self.find(constructor, selector) {
+			Some(MessageSpec { return_type: Some(ty), .. }) => {
+				decode_value(&self.registry, ty.id, &mut output)
+			},
+			Some(MessageSpec { return_type: None, .. }) => Ok(Value::Null),
+			None => match (&self.spec.lang_error, output.split_first()) {
+				// `Err(LangError)` of the `MessageResult` returned by ink!.
+				(Some(lang_error), Some((1, mut error))) => {
+					Ok(named("Err", decode_value(&self.registry, lang_error.id, &mut error)?))
+				},
+				_ => Err(format!("Unknown selector 0x{}", HexDisplay::from(&selector))),
+			},
+		}
+	}
+
+	/// Decodes the data of an event emitted by the contract.
+	pub fn decode_event(&self, data: &[u8]) -> Result<DecodedEvent, String> {
+		let (index, mut fields) = data.split_first().ok_or("Empty event data")?;
+		let spec = self
+			.spec
+			.events
+			.get(*index as usize)
+			.ok_or_else(|| format!("Unknown event {}", index))?;
+		Ok(DecodedEvent {
+			label: spec.label.clone(),
+			fields: self.decode_args(&spec.args, &mut fields)?,
+		})
+	}
+
+	/// Decodes the storage of the contract, reading the cell at a storage key with `read`.
+	///
+	/// Each `Mapping` is decoded into its entries for the SCALE encoded keys given in `keys` by
+	/// the path of the mapping, e.g. `balances` or `inner.allowances`, as mappings can't be
+	/// iterated. Cells which aren't set are decoded as `null`.
+	pub fn decode_storage(
+		&self,
+		read: impl Fn(&[u8]) -> Result<Option<Vec<u8>>, String>,
+		keys: &HashMap<String, Vec<Bytes>>,
+	) -> Result<Value, String> {
+		let root = self.storage.as_ref().ok_or("No storage layout in the metadata")?;
+		StorageDecoder { registry: &self.registry, read, keys }.decode_root(root, "")
+	}
+
+	fn decode_call<AccountId, Balance, Hash>(
+		&self,
+		call: &CallSummary<AccountId, Balance, Hash>,
+	) -> DecodedCall {
+		let constructor = matches!(call.kind, CallKind::Instantiate);
+		let output = match &call.result {
+			CallResult::Returned(output) | CallResult::Reverted(output) => call
+				.selector
+				.and_then(|selector| self.decode_output(constructor, &selector, output).ok()),
+			CallResult::Failed(_) | CallResult::Completed => None,
+		};
+		DecodedCall {
+			contract: self.name.clone(),
+			message: self.decode_input(constructor, &call.input).ok(),
+			output,
+		}
+	}
+
+	fn decode_args(&self, args: &[ArgSpec], input: &mut &[u8]) -> Result<Vec<NamedValue>, String> {
+		args.iter()
+			.map(|arg| {
+				let value = decode_value(&self.registry, arg.ty.id, input)?;
+				Ok(NamedValue { name: arg.label.clone(), value })
+			})
+			.collect()
+	}
+}
+
+/// Names and values of a [`CallSummary`], decoded with the metadata of the callee's code.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedCall {
+	/// Name of the contract.
+	pub contract: String,
+	/// Message or constructor called.
+	pub message: Option<DecodedMessage>,
+	/// Output of the call.
+	pub output: Option<Value>,
+}
+
+/// A [`CallSummary`] along with the names and values decoded from the registered metadata.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedCallSummary<AccountId, Balance, Hash> {
+	#[serde(flatten)]
+	pub call: CallSummary<AccountId, Balance, Hash>,
+	/// Decoded call, if metadata is registered for the callee's code.
+	pub decoded: Option<DecodedCall>,
+	/// Events of the call decoded with the metadata of their emitter's code, `None` for those
+	/// which can't be decoded.
+	pub decoded_events: Vec<Option<DecodedEvent>>,
+}
+
+impl<AccountId, Balance, Hash> DecodedCallSummary<AccountId, Balance, Hash>
+where
+	Hash: Eq + std::hash::Hash + Clone + DeserializeOwned,
+{
+	/// Decodes `call` with the metadata in `registry`.
+	pub fn new(
+		call: CallSummary<AccountId, Balance, Hash>,
+		registry: &MetadataRegistry<Hash>,
+	) -> Self {
+		let decoded = call
+			.code_hash
+			.as_ref()
+			.and_then(|code_hash| registry.get(code_hash))
+			.map(|metadata| metadata.decode_call(&call));
+		let decoded_events = call
+			.events
+			.iter()
+			.map(|event| {
+				let metadata = registry.get(event.code_hash.as_ref()?)?;
+				metadata.decode_event(&event.data).ok()
+			})
+			.collect();
+		DecodedCallSummary { call, decoded, decoded_events }
+	}
+}
+
+impl<AccountId: fmt::Display, Balance, Hash> DecodedCallSummary<AccountId, Balance, Hash> {
+	/// Writes the decoded call and the events emitted during it, one line each.
+	pub fn write_lines(&self, f: &mut impl fmt::Write) -> fmt::Result {
+		write!(f, "{} -> {}", self.call.caller, self.call.callee)?;
+		if let Some(decoded) = &self.decoded {
+			write!(f, " {}", decoded.contract)?;
+			if let Some(message) = &decoded.message {
+				write!(f, "::{}", message)?;
+			}
+			if let Some(output) = &decoded.output {
+				write!(f, " => {}", output)?;
+			}
+		}
+		for (event, decoded) in self.call.events.iter().zip(&self.decoded_events) {
+			if let Some(decoded) = decoded {
+				write!(f, "\n  {} event {}", event.contract, decoded)?;
+			}
+		}
+		writeln!(f)
+	}
+}
+
+/// Walks the storage layout of a contract, decoding the cells it reads.
+struct StorageDecoder<'a, R> {
+	registry: &'a PortableRegistry,
+	read: R,
+	keys: &'a HashMap<String, Vec<Bytes>>,
+}
+
+impl<'a, R: Fn(&[u8]) -> Result<Option<Vec<u8>>, String>> StorageDecoder<'a, R> {
+	fn decode_root(&self, root: &RootLayout, path: &str) -> Result<Value, String> {
+		let Some(key_type) = root.ty.and_then(|id| self.mapping_key_type(id)) else {
+			return self.decode_cell(&root.root_key, &root.layout, path)
+		};
+		self.keys
+			.get(path)
+			.map(Vec::as_slice)
+			.unwrap_or_default()
+			.iter()
+			.map(|key| {
+				let storage_key = [&root.root_key.0[..], &key.0[..]].concat();
+				let decoded_key = decode_value(self.registry, key_type, &mut &key.0[..])
+					.map_err(|e| format!("Invalid key of {}: {}", path, e))?;
+				let value = self.decode_cell(&storage_key, &root.layout, path)?;
+				Ok([("key".to_owned(), decoded_key), ("value".to_owned(), value)]
+					.into_iter()
+					.collect())
+			})
+			.collect::<Result<_, String>>()
+			.map(Value::Array)
+	}
+
+	/// Type of the keys of the `Mapping` of type `id`, `None` if it isn't a mapping.
+	fn mapping_key_type(&self, id: u32) -> Option<u32> {
+		let ty = self.registry.resolve(id)?;
+		if ty.path.segments.last().map(String::as_str) != Some("Mapping") {
+			return None
+		}
+		ty.type_params.iter().find(|param| param.name == "K")?.ty.map(|ty| ty.id)
+	}
+
+	fn decode_cell(&self, key: &[u8], layout: &Layout, path: &str) -> Result<Value, String> {
+		let Some(data) = (self.read)(key)? else { return Ok(Value::Null) };
+		let mut input = &data[..];
+		self.decode_layout(layout, path, &mut input)
+	}
+
+	fn decode_layout(
+		&self,
+		layout: &Layout,
+		path: &str,
+		input: &mut &[u8],
+	) -> Result<Value, String> {
+		match layout {
+			Layout::Leaf(leaf) => decode_value(self.registry, leaf.ty, input)
+				.map_err(|e| format!("Unable to decode {}: {}", path, e)),
+			Layout::Root(root) => self.decode_root(root, path),
+			Layout::Array(array) => (0..array.len)
+				.map(|i| self.decode_layout(&array.layout, &format!("{}[{}]", path, i), input))
+				.collect::<Result<_, _>>()
+				.map(Value::Array),
+			Layout::Struct(layout) => self.decode_struct(layout, path, input),
+			Layout::Enum(layout) => {
+				let index: u8 = decode(input)?;
+				let variant = layout
+					.variants
+					.get(&index)
+					.ok_or_else(|| format!("Unknown variant {} of {}", index, layout.name))?;
+				Ok(named(&variant.name, self.decode_struct(variant, path, input)?))
+			},
+		}
+	}
+
+	fn decode_struct(
+		&self,
+		layout: &StructLayout,
+		path: &str,
+		input: &mut &[u8],
+	) -> Result<Value, String> {
+		layout
+			.fields
+			.iter()
+			.map(|field| {
+				let path = match path {
+					"" => field.name.clone(),
+					path => format!("{}.{}", path, field.name),
+				};
+				Ok((field.name.clone(), self.decode_layout(&field.layout, &path, input)?))
+			})
+			.collect::<Result<_, String>>()
+			.map(Value::Object)
+	}
+}
+
+fn named(name: &str, value: Value) -> Value {
+	Value::Object([(name.to_owned(), value)].into_iter().collect())
+}
+
+fn decode<T: Decode>(input: &mut &[u8]) -> Result<T, String> {
+	T::decode(input).map_err(|e| e.to_string())
+}
+
+/// Decodes a value of the type `id` from the front of `input`.
+///
+/// Structs with named fields and enum variants become objects, byte arrays and sequences
+/// hex strings, and integers wider than 64 bits decimal strings.
+fn decode_value(registry: &PortableRegistry, id: u32, input: &mut &[u8]) -> Result<Value, String> {
+	let ty = registry.resolve(id).ok_or_else(|| format!("Unknown type {}", id))?;
+	match &ty.type_def {
+		TypeDef::Composite(composite) => decode_fields(registry, &composite.fields, input),
+		TypeDef::Variant(variants) => {
+			let index: u8 = decode(input)?;
+			let variant = variants
+				.variants
+				.iter()
+				.find(|variant| variant.index == index)
+				.ok_or_else(|| format!("Unknown variant {} of type {}", index, id))?;
+			if variant.fields.is_empty() {
+				Ok(Value::String(variant.name.clone()))
+			} else {
+				Ok(named(&variant.name, decode_fields(registry, &variant.fields, input)?))
+			}
+		},
+		TypeDef::Sequence(sequence) => {
+			let len = decode::<Compact<u32>>(input)?.0;
+			decode_items(registry, sequence.type_param.id, len, input)
+		},
+		TypeDef::Array(array) => decode_items(registry, array.type_param.id, array.len, input),
+		TypeDef::Tuple(tuple) if tuple.fields.is_empty() => Ok(Value::Null),
+		TypeDef::Tuple(tuple) => tuple
+			.fields
+			.iter()
+			.map(|field| decode_value(registry, field.id, input))
+			.collect::<Result<_, _>>()
+			.map(Value::Array),
+		TypeDef::Primitive(primitive) => decode_primitive(primitive, input),
+		TypeDef::Compact(_) => {
+			let value = decode::<Compact<u128>>(input)?.0;
+			Ok(u64::try_from(value).map_or_else(|_| value.to_string().into(), Value::from))
+		},
+		TypeDef::BitSequence(_) => Err("Bit sequences are not supported".into()),
+	}
+}
+
+fn decode_fields(
+	registry: &PortableRegistry,
+	fields: &[Field<PortableForm>],
+	input: &mut &[u8],
+) -> Result<Value, String> {
+	match fields {
+		[] => Ok(Value::Null),
+		[field] if field.name.is_none() => decode_value(registry, field.ty.id, input),
+		fields if fields.iter().all(|field| field.name.is_some()) => fields
+			.iter()
+			.map(|field| {
+				let name = field.name.clone().unwrap_or_default();
+				Ok((name, decode_value(registry, field.ty.id, input)?))
+			})
+			.collect::<Result<_, String>>()
+			.map(Value::Object),
+		fields => fields
+			.iter()
+			.map(|field| decode_value(registry, field.ty.id, input))
+			.collect::<Result<_, _>>()
+			.map(Value::Array),
+	}
+}
+
+fn decode_items(
+	registry: &PortableRegistry,
+	id: u32,
+	len: u32,
+	input: &mut &[u8],
+) -> Result<Value, String> {
+	let is_byte = registry
+		.resolve(id)
+		.map_or(false, |ty| matches!(ty.type_def, TypeDef::Primitive(TypeDefPrimitive::U8)));
+	if is_byte {
+		let len = len as usize;
+		if input.len() < len {
+			return Err("Not enough data to decode bytes".into());
+		}
+		let (bytes, rest) = input.split_at(len);
+		*input = rest;
+		return Ok(format!("0x{}", HexDisplay::from(&bytes)).into());
+	}
+	(0..len)
+		.map(|_| decode_value(registry, id, input))
+		.collect::<Result<_, _>>()
+		.map(Value::Array)
+}
+
+fn decode_primitive(primitive: &TypeDefPrimitive, input: &mut &[u8]) -> Result<Value, String> {
+	Ok(match primitive {
+		TypeDefPrimitive::Bool => decode::<bool>(input)?.into(),
+		TypeDefPrimitive::Char => char::from_u32(decode(input)?).ok_or("Invalid char")?.into(),
+		TypeDefPrimitive::Str => decode::<String>(input)?.into(),
+		TypeDefPrimitive::U8 => decode::<u8>(input)?.into(),
+		TypeDefPrimitive::U16 => decode::<u16>(input)?.into(),
+		TypeDefPrimitive::U32 => decode::<u32>(input)?.into(),
+		TypeDefPrimitive::U64 => decode::<u64>(input)?.into(),
+		TypeDefPrimitive::U128 => decode::<u128>(input)?.to_string().into(),
+		TypeDefPrimitive::I8 => decode::<i8>(input)?.into(),
+		TypeDefPrimitive::I16 => decode::<i16>(input)?.into(),
+		TypeDefPrimitive::I32 => decode::<i32>(input)?.into(),
+		TypeDefPrimitive::I64 => decode::<i64>(input)?.into(),
+		TypeDefPrimitive::I128 => decode::<i128>(input)?.to_string().into(),
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => {
+			format!("0x{}", HexDisplay::from(&decode::<[u8; 32]>(input)?)).into()
+		},
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use serde_json::json;
+	use sp_core::H256;
+
+	fn registry() -> (MetadataRegistry<H256>, Arc<ContractMetadata>) {
+		let metadata = json!({
+			"source": { "hash": H256::repeat_byte(1) },
+			"contract": { "name": "flipper" },
+			"spec": {
+				"constructors": [{
+					"label": "new",
+					"selector": "0x9bae9d5e",
+					"args": [{ "label": "init_value", "type": { "type": 0 } }],
+					"returnType": { "type": 1 },
+				}],
+				"messages": [
+					{
+						"label": "flip",
+						"selector": "0x633aa551",
+						"args": [],
+						"returnType": { "type": 1 },
+					},
+					{
+						"label": "transfer",
+						"selector": "0x00000001",
+						"args": [
+							{ "label": "to", "type": { "type": 3 } },
+							{ "label": "amount", "type": { "type": 5 } },
+						],
+						"returnType": null,
+					},
+				],
+				"events": [{
+					"label": "Flipped",
+					"args": [{ "label": "value", "indexed": false, "type": { "type": 0 } }],
+				}],
+				"lang_error": { "type": 2 },
+			},
+			"storage": { "root": {
+				"root_key": "0x00000000",
+				"layout": { "struct": { "name": "Flipper", "fields": [
+					{ "name": "value", "layout": { "leaf": { "key": "0x00000000", "ty": 0 } } },
+					{ "name": "balances", "layout": { "root": {
+						"root_key": "0x01000000",
+						"layout": { "leaf": { "key": "0x01000000", "ty": 5 } },
+						"ty": 8,
+					} } },
+				] } },
+			} },
+			"types": [
+				{ "id": 0, "type": { "def": { "primitive": "bool" } } },
+				{ "id": 1, "type": {
+					"path": ["Result"],
+					"def": { "variant": { "variants": [
+						{ "name": "Ok", "index": 0, "fields": [{ "type": 4 }] },
+						{ "name": "Err", "index": 1, "fields": [{ "type": 2 }] },
+					] } },
+				} },
+				{ "id": 2, "type": {
+					"path": ["ink_primitives", "LangError"],
+					"def": {
+						"variant": { "variants": [{ "name": "CouldNotReadInput", "index": 1 }] },
+					},
+				} },
+				{ "id": 3, "type": {
+					"path": ["ink_primitives", "types", "AccountId"],
+					"def": { "composite": { "fields": [{ "type": 6, "typeName": "[u8; 32]" }] } },
+				} },
+				{ "id": 4, "type": { "def": { "tuple": [] } } },
+				{ "id": 5, "type": { "def": { "primitive": "u128" } } },
+				{ "id": 6, "type": { "def": { "array": { "len": 32, "type": 7 } } } },
+				{ "id": 7, "type": { "def": { "primitive": "u8" } } },
+				{ "id": 8, "type": {
+					"path": ["ink_storage", "lazy", "mapping", "Mapping"],
+					"params": [{ "name": "K", "type": 3 }, { "name": "V", "type": 5 }],
+					"def": { "composite": {} },
+				} },
+			],
+			"version": "4",
+		});
+
+		let registry = MetadataRegistry::default();
+		assert_eq!(registry.register(metadata), Ok(H256::repeat_byte(1)));
+		let contract = registry.get(&H256::repeat_byte(1)).expect("metadata is registered");
+		(registry, contract)
+	}
+
+	#[test]
+	fn decodes_message_input() {
+		let (_, contract) = registry();
+		let mut input = vec![0, 0, 0, 1];
+		([2u8; 32], 1_000u128).encode_to(&mut input);
+
+		let message = contract.decode_input(false, &input).unwrap();
+
+		assert_eq!(
+			message.to_string(),
+			format!("transfer(to: \"0x{}\", amount: \"1000\")", "02".repeat(32))
+		);
+		assert_eq!(contract.decode_input(true, &input), Err("Unknown selector 0x00000001".into()));
+	}
+
+	#[test]
+	fn lists_messages_and_constructors() {
+		let (_, contract) = registry();
+
+		let messages = contract.messages(false);
+		assert_eq!(
+			messages.iter().map(|message| message.label.as_str()).collect::<Vec<_>>(),
+			["flip", "transfer"]
+		);
+		assert_eq!(messages[1].selector, [0, 0, 0, 1]);
+		assert_eq!(messages[1].args, [3, 5]);
+		assert_eq!(contract.messages(true)[0].args, [0]);
+	}
+
+	#[test]
+	fn decodes_events() {
+		let (_, contract) = registry();
+
+		let event = contract.decode_event(&[0, 1]).unwrap();
+
+		assert_eq!(event.to_string(), "Flipped { value: true }");
+		assert!(contract.decode_event(&[1]).is_err());
+	}
+
+	#[test]
+	fn decodes_output_and_lang_error() {
+		let (_, contract) = registry();
+		let flip = [0x63, 0x3a, 0xa5, 0x51];
+
+		assert_eq!(contract.decode_output(false, &flip, &[0]), Ok(json!({ "Ok": null })));
+		assert_eq!(
+			contract.decode_output(false, &[0xff; 4], &[1, 1]),
+			Ok(json!({ "Err": "CouldNotReadInput" }))
+		);
+	}
+
+	#[test]
+	fn decodes_storage_and_mapping_entries() {
+		let (_, contract) = registry();
+		let cells: HashMap<Vec<u8>, Vec<u8>> = [
+			(vec![0, 0, 0, 0], true.encode()),
+			([vec![1, 0, 0, 0], vec![2; 32]].concat(), 1_000u128.encode()),
+		]
+		.into_iter()
+		.collect();
+		let keys = [("balances".to_string(), vec![vec![2; 32].into(), vec![3; 32].into()])]
+			.into_iter()
+			.collect();
+
+		let storage = contract.decode_storage(|key| Ok(cells.get(key).cloned()), &keys).unwrap();
+
+		assert_eq!(
+			storage,
+			json!({
+				"value": true,
+				"balances": [
+					{ "key": format!("0x{}", "02".repeat(32)), "value": "1000" },
+					{ "key": format!("0x{}", "03".repeat(32)), "value": null },
+				],
+			})
+		);
+	}
+
+	#[test]
+	fn unknown_code_is_not_decoded() {
+		let (registry, _) = registry();
+
+		assert!(registry.get(&H256::repeat_byte(2)).is_none());
+		assert!(registry.register(json!({ "spec": {} })).is_err());
+	}
+}
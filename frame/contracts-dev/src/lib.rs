@@ -0,0 +1,247 @@
+//! # Contracts Dev Pallet
+//!
+//! [Swanky Node specific]
+//! Lets the node change deployed contracts in ways `pallet_contracts` only allows the contracts
+//! themselves or root: swap the code of contracts in place, keeping their storage, when their
+//! build artifacts change, remove contracts or clear their storage. Contracts configured in
+//! genesis are instantiated as the chain is built.
+//!
+//! Changes are made with unsigned extrinsics, which are only accepted from the local node.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::traits::Currency;
+
+pub use pallet::*;
+pub use types::GenesisContract;
+
+pub mod storage;
+mod types;
+
+type BalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		storage::{child, child::ChildInfo},
+		traits::ExistenceRequirement,
+	};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use pallet_contracts::{weights::WeightInfo, CollectEvents, DebugInfo, Determinism};
+	use pallet_contracts_primitives::Code;
+	use sp_runtime::traits::{Hash, Saturating, StaticLookup, Zero};
+	use sp_std::vec::Vec;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_contracts::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Contracts to instantiate, in order.
+		pub contracts: Vec<GenesisContract<T::AccountId, BalanceOf<T>>>,
+	}
+
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { contracts: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let gas_limit = <T as frame_system::Config>::BlockWeights::get().max_block;
+			for (index, contract) in self.contracts.iter().enumerate() {
+				let instantiated = pallet_contracts::Pallet::<T>::bare_instantiate(
+					contract.deployer.clone(),
+					contract.endowment,
+					gas_limit,
+					None,
+					Code::Upload(contract.code.clone()),
+					[&contract.selector[..], &contract.args[..]].concat(),
+					contract.salt.clone(),
+					DebugInfo::Skip,
+					CollectEvents::Skip,
+				)
+				.result
+				.unwrap_or_else(|e| panic!("genesis contract {} failed: {:?}", index, e));
+				assert!(
+					!instantiated.result.did_revert(),
+					"constructor of genesis contract {} reverted",
+					index
+				);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The code of a contract was swapped, keeping its storage.
+		CodeUpgraded { contract: T::AccountId, old_code_hash: T::Hash, new_code_hash: T::Hash },
+		/// A contract was removed, its balance and storage deposits sent to `beneficiary`.
+		ContractKilled { contract: T::AccountId, beneficiary: T::AccountId },
+		/// The storage of a contract was cleared.
+		ContractStorageReset { contract: T::AccountId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// There is no contract at the address.
+		ContractNotFound,
+	}
+
+	/// [Swanky Node specific]
+	/// Contracts are changed with unsigned extrinsics submitted by the node.
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			match (source, call) {
+				(
+					TransactionSource::Local | TransactionSource::InBlock,
+					Call::upgrade_code { .. } |
+					Call::kill_contract { .. } |
+					Call::reset_contract_storage { .. },
+				) => ValidTransaction::with_tag_prefix("ContractsDev")
+					.priority(TransactionPriority::max_value())
+					// The magic number makes otherwise identical calls distinct.
+					.and_provides(T::Hashing::hash_of(call))
+					.propagate(false)
+					.build(),
+				_ => InvalidTransaction::Call.into(),
+			}
+		}
+
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			match call {
+				Call::upgrade_code { .. } |
+				Call::kill_contract { .. } |
+				Call::reset_contract_storage { .. } => Ok(()),
+				_ => Err(InvalidTransaction::Call.into()),
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Upload `code` as `owner`, who pays its storage deposit, and switch `contracts` to it.
+		///
+		/// The dispatch origin for this call must be none, it's submitted by the node.
+		/// `_magic_number` only serves to give repeated upgrades distinct hashes.
+		#[pallet::call_index(0)]
+		#[pallet::weight({
+			let contracts = contracts.len() as u64;
+			<T as pallet_contracts::Config>::WeightInfo::upload_code(code.len() as u32)
+				.saturating_add(T::DbWeight::get().reads_writes(3 * contracts, 3 * contracts))
+		})]
+		pub fn upgrade_code(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			code: Vec<u8>,
+			contracts: Vec<T::AccountId>,
+			#[pallet::compact] _magic_number: u64,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let new_code_hash = pallet_contracts::Pallet::<T>::bare_upload_code(
+				owner,
+				code,
+				None,
+				Determinism::Enforced,
+			)?
+			.code_hash;
+			for contract in contracts {
+				let old_code_hash = pallet_contracts::Pallet::<T>::code_hash(&contract)
+					.ok_or(Error::<T>::ContractNotFound)?;
+				if old_code_hash == new_code_hash {
+					continue
+				}
+				pallet_contracts::Pallet::<T>::set_code(
+					RawOrigin::Root.into(),
+					T::Lookup::unlookup(contract.clone()),
+					new_code_hash,
+				)?;
+				Self::deposit_event(Event::CodeUpgraded { contract, old_code_hash, new_code_hash });
+			}
+			Ok(())
+		}
+
+		/// Remove the contract at `contract` as if it terminated itself, sending its balance and
+		/// the refund of its storage deposits to `beneficiary`. Its storage is deleted lazily.
+		///
+		/// The dispatch origin for this call must be none, it's submitted by the node.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(5, 8))]
+		pub fn kill_contract(
+			origin: OriginFor<T>,
+			contract: T::AccountId,
+			beneficiary: T::AccountId,
+			#[pallet::compact] _magic_number: u64,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let info = storage::ContractInfoOf::<T>::take(&contract)
+				.ok_or(Error::<T>::ContractNotFound)?;
+			storage::queue_trie_for_deletion::<T>(info.trie_id);
+			storage::remove_user::<T>(info.code_hash);
+			// Instantiation adds consumer references to the contract and deposit accounts, which
+			// keep them from being reaped while it's a contract. Removed first, as on termination,
+			// so that transferring their whole balance reaps them.
+			frame_system::Pallet::<T>::dec_consumers(&info.deposit_account);
+			frame_system::Pallet::<T>::dec_consumers(&contract);
+			for account in [&info.deposit_account, &contract] {
+				<T as pallet_contracts::Config>::Currency::transfer(
+					account,
+					&beneficiary,
+					<T as pallet_contracts::Config>::Currency::free_balance(account),
+					ExistenceRequirement::AllowDeath,
+				)?;
+			}
+			Self::deposit_event(Event::ContractKilled { contract, beneficiary });
+			Ok(())
+		}
+
+		/// Clear the storage of the contract at `contract`, keeping its code and balance. The
+		/// deposits of the storage items are refunded to the contract.
+		///
+		/// The dispatch origin for this call must be none, it's submitted by the node.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 3))]
+		pub fn reset_contract_storage(
+			origin: OriginFor<T>,
+			contract: T::AccountId,
+			#[pallet::compact] _magic_number: u64,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let mut info =
+				storage::ContractInfoOf::<T>::get(&contract).ok_or(Error::<T>::ContractNotFound)?;
+			let _ = child::clear_storage(&ChildInfo::new_default(&info.trie_id), None, None);
+			<T as pallet_contracts::Config>::Currency::transfer(
+				&info.deposit_account,
+				&contract,
+				info.storage_byte_deposit.saturating_add(info.storage_item_deposit),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			info.storage_bytes = 0;
+			info.storage_items = 0;
+			info.storage_byte_deposit = Zero::zero();
+			info.storage_item_deposit = Zero::zero();
+			storage::ContractInfoOf::<T>::insert(&contract, info);
+			Self::deposit_event(Event::ContractStorageReset { contract });
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,95 @@
+//! Storage of `pallet_contracts` changed by the pallet and read by the runtime.
+//!
+//! The storage items of `pallet_contracts` aren't accessible outside of it. They are aliased here,
+//! with their values mirrored from `pallet_contracts` as of `polkadot-v0.9.43`.
+
+use crate::{BalanceOf, Config};
+use codec::{Decode, Encode};
+use frame_support::{pallet_prelude::ValueQuery, storage_alias, Identity, Twox64Concat};
+use pallet_contracts::Determinism;
+use sp_std::prelude::*;
+
+type Contracts<T> = pallet_contracts::Pallet<T>;
+type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+type CodeHash<T> = <T as frame_system::Config>::Hash;
+
+/// `pallet_contracts::storage::ContractInfo`.
+#[derive(Encode, Decode)]
+pub struct ContractInfo<T: Config> {
+	pub trie_id: Vec<u8>,
+	pub deposit_account: AccountIdOf<T>,
+	pub code_hash: CodeHash<T>,
+	pub storage_bytes: u32,
+	pub storage_items: u32,
+	pub storage_byte_deposit: BalanceOf<T>,
+	pub storage_item_deposit: BalanceOf<T>,
+	pub storage_base_deposit: BalanceOf<T>,
+}
+
+/// `pallet_contracts::wasm::OwnerInfo`.
+#[derive(Encode, Decode)]
+pub struct OwnerInfo<T: Config> {
+	pub owner: AccountIdOf<T>,
+	#[codec(compact)]
+	pub deposit: BalanceOf<T>,
+	#[codec(compact)]
+	pub refcount: u64,
+}
+
+/// `pallet_contracts::wasm::PrefabWasmModule`.
+#[derive(Encode, Decode)]
+pub struct PrefabWasmModule {
+	#[codec(compact)]
+	pub instruction_weights_version: u32,
+	#[codec(compact)]
+	pub initial: u32,
+	#[codec(compact)]
+	pub maximum: u32,
+	pub code: Vec<u8>,
+	pub determinism: Determinism,
+}
+
+/// `pallet_contracts::storage::DeletionQueueManager`.
+#[derive(Encode, Decode, Default)]
+pub struct DeletionQueueManager {
+	pub insert_counter: u32,
+	pub delete_counter: u32,
+}
+
+#[storage_alias]
+pub type ContractInfoOf<T: Config> =
+	StorageMap<Contracts<T>, Twox64Concat, AccountIdOf<T>, ContractInfo<T>>;
+
+#[storage_alias]
+pub type OwnerInfoOf<T: Config> = StorageMap<Contracts<T>, Identity, CodeHash<T>, OwnerInfo<T>>;
+
+#[storage_alias]
+pub type CodeStorage<T: Config> = StorageMap<Contracts<T>, Identity, CodeHash<T>, PrefabWasmModule>;
+
+#[storage_alias]
+pub type PristineCode<T: Config> = StorageMap<Contracts<T>, Identity, CodeHash<T>, Vec<u8>>;
+
+#[storage_alias]
+pub type DeletionQueue<T: Config> = StorageMap<Contracts<T>, Twox64Concat, u32, Vec<u8>>;
+
+#[storage_alias]
+pub type DeletionQueueCounter<T: Config> =
+	StorageValue<Contracts<T>, DeletionQueueManager, ValueQuery>;
+
+/// Queues the child trie `trie_id` for deletion, which `pallet_contracts` does lazily in its
+/// `on_idle` hook.
+pub fn queue_trie_for_deletion<T: Config>(trie_id: Vec<u8>) {
+	DeletionQueueCounter::<T>::mutate(|queue| {
+		DeletionQueue::<T>::insert(queue.insert_counter, trie_id);
+		queue.insert_counter = queue.insert_counter.wrapping_add(1);
+	});
+}
+
+/// Decrements the number of contracts running the code `code_hash`.
+pub fn remove_user<T: Config>(code_hash: CodeHash<T>) {
+	OwnerInfoOf::<T>::mutate(code_hash, |owner_info| {
+		if let Some(owner_info) = owner_info {
+			owner_info.refcount = owner_info.refcount.saturating_sub(1);
+		}
+	});
+}
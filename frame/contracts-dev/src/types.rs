@@ -0,0 +1,32 @@
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A contract configured in the genesis of the chain, instantiated while its genesis storage is
+/// built. Building the chain spec fails if the instantiation fails or the constructor reverts.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct GenesisContract<AccountId, Balance> {
+	/// Account instantiating the contract, paying its storage deposit and endowment.
+	pub deployer: AccountId,
+	/// Wasm code of the contract, the `source.wasm` of a `.contract` bundle. Chain spec files
+	/// loaded by the node may give the bundle or its path instead.
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub code: Vec<u8>,
+	/// Selector of the constructor.
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub selector: Vec<u8>,
+	/// SCALE encoded arguments of the constructor.
+	#[cfg_attr(feature = "std", serde(default, with = "sp_core::bytes"))]
+	pub args: Vec<u8>,
+	/// Balance transferred to the contract.
+	#[cfg_attr(feature = "std", serde(default))]
+	pub endowment: Balance,
+	/// Salt telling apart the addresses of contracts with the same code, deployer and input.
+	#[cfg_attr(feature = "std", serde(default, with = "sp_core::bytes"))]
+	pub salt: Vec<u8>,
+}
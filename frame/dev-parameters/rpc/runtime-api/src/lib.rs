@@ -0,0 +1,11 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet_dev_parameters::AllowedCall;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait DevParametersApi {
+		/// Calls which contracts are currently allowed to dispatch.
+		fn contracts_call_filter() -> Vec<AllowedCall>;
+	}
+}
@@ -0,0 +1,72 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_dev_parameters_rpc_runtime_api::{
+	AllowedCall, DevParametersApi as DevParametersRuntimeApi,
+};
+
+/// RPC trait that provides methods for inspecting the dev parameters of the chain.
+#[rpc(server)]
+pub trait DevParametersApi<BlockHash> {
+	/// Calls which contracts are allowed to dispatch with `call_runtime`.
+	#[method(name = "dev_getCallFilter")]
+	fn get_call_filter(&self, at: Option<BlockHash>) -> RpcResult<Vec<AllowedCall>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// Provides RPC methods to query dev parameters.
+pub struct DevParameters<C, Block> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> DevParameters<C, Block> {
+	/// Creates a new instance of the DevParameters Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<Client, Block> DevParametersApiServer<<Block as BlockT>::Hash> for DevParameters<Client, Block>
+where
+	Block: BlockT,
+	Client: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	Client::Api: DevParametersRuntimeApi<Block>,
+{
+	fn get_call_filter(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<AllowedCall>> {
+		let runtime_api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let allowed_calls = runtime_api.contracts_call_filter(at_hash).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to get contracts call filter.",
+				Some(e.to_string()),
+			))
+		})?;
+
+		Ok(allowed_calls)
+	}
+}
@@ -0,0 +1,333 @@
+//! # Dev Parameters Pallet
+//!
+//! [Swanky Node specific]
+//! Makes values which are compile-time constants on production networks configurable at genesis
+//! and via root calls. This lets contracts be tested against the limits, deposits and fees of the
+//! network they will be deployed to, instead of swanky-node's permissive defaults.
+//!
+//! The runtime reads the values through the getters of this pallet, e.g. in its
+//! `parameter_types!`, uses [`WeightToFee`] and [`LengthToFee`] for transaction payment and
+//! [`ContractsCallFilter`] to decide which calls contracts can dispatch with `call_runtime`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Encode;
+use frame_support::{
+	traits::Contains,
+	weights::{Weight, WeightToFee as WeightToFeeT},
+};
+use sp_runtime::{
+	traits::{One, Saturating},
+	SaturatedConversion,
+};
+use sp_std::marker::PhantomData;
+
+pub use pallet::*;
+pub use types::{AllowedCall, ContractsParameters, FeeParameters};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod types;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{AtLeast32BitUnsigned, Zero};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The balance type of the runtime.
+		type Balance: Parameter
+			+ Member
+			+ AtLeast32BitUnsigned
+			+ Default
+			+ Copy
+			+ MaybeSerializeDeserialize
+			+ MaxEncodedLen;
+
+		/// Contracts parameters used until they are set in genesis or by root.
+		type DefaultContractsParameters: Get<ContractsParameters<Self::Balance>>;
+
+		/// Fee parameters used until they are set in genesis or by root.
+		type DefaultFeeParameters: Get<FeeParameters<Self::Balance>>;
+
+		/// Existential deposit used until it is set in genesis or by root.
+		#[pallet::constant]
+		type DefaultExistentialDeposit: Get<Self::Balance>;
+
+		/// Upper bound of [`ContractsParameters::max_code_len`]. This is the bound of the code
+		/// storage of `pallet_contracts`, which can't change at runtime.
+		#[pallet::constant]
+		type MaxCodeLenLimit: Get<u32>;
+
+		/// Maximum number of entries in the contracts call filter.
+		#[pallet::constant]
+		type MaxAllowedContractsCalls: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::type_value]
+	pub fn ContractsParametersOnEmpty<T: Config>() -> ContractsParameters<T::Balance> {
+		T::DefaultContractsParameters::get()
+	}
+
+	#[pallet::type_value]
+	pub fn FeeParametersOnEmpty<T: Config>() -> FeeParameters<T::Balance> {
+		T::DefaultFeeParameters::get()
+	}
+
+	#[pallet::type_value]
+	pub fn ExistentialDepositOnEmpty<T: Config>() -> T::Balance {
+		T::DefaultExistentialDeposit::get()
+	}
+
+	/// Limits and deposits applied to contracts.
+	#[pallet::storage]
+	#[pallet::getter(fn contracts_parameters)]
+	pub type ActiveContractsParameters<T: Config> =
+		StorageValue<_, ContractsParameters<T::Balance>, ValueQuery, ContractsParametersOnEmpty<T>>;
+
+	/// Conversion of weight and extrinsic length to fee.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_parameters)]
+	pub type ActiveFeeParameters<T: Config> =
+		StorageValue<_, FeeParameters<T::Balance>, ValueQuery, FeeParametersOnEmpty<T>>;
+
+	/// The minimum amount required to keep an account open.
+	#[pallet::storage]
+	#[pallet::getter(fn existential_deposit)]
+	pub type ExistentialDeposit<T: Config> =
+		StorageValue<_, T::Balance, ValueQuery, ExistentialDepositOnEmpty<T>>;
+
+	/// Calls which contracts are allowed to dispatch. Empty means none.
+	#[pallet::storage]
+	#[pallet::getter(fn allowed_contracts_calls)]
+	pub type AllowedContractsCalls<T: Config> =
+		StorageValue<_, BoundedVec<AllowedCall, T::MaxAllowedContractsCalls>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Contracts parameters, runtime defaults are used if `None`.
+		pub contracts_parameters: Option<ContractsParameters<T::Balance>>,
+		/// Fee parameters, runtime defaults are used if `None`.
+		pub fee_parameters: Option<FeeParameters<T::Balance>>,
+		/// Existential deposit, runtime default is used if `None`.
+		pub existential_deposit: Option<T::Balance>,
+		/// Calls which contracts are allowed to dispatch.
+		pub contracts_call_filter: Vec<AllowedCall>,
+	}
+
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				contracts_parameters: None,
+				fee_parameters: None,
+				existential_deposit: None,
+				contracts_call_filter: Default::default(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			if let Some(parameters) = &self.contracts_parameters {
+				Pallet::<T>::ensure_valid_contracts_parameters(parameters)
+					.expect("invalid contracts parameters in genesis");
+				ActiveContractsParameters::<T>::put(parameters);
+			}
+			if let Some(parameters) = &self.fee_parameters {
+				Pallet::<T>::ensure_valid_fee_parameters(parameters)
+					.expect("invalid fee parameters in genesis");
+				ActiveFeeParameters::<T>::put(parameters);
+			}
+			if let Some(existential_deposit) = self.existential_deposit {
+				assert!(!existential_deposit.is_zero(), "existential deposit must not be zero");
+				ExistentialDeposit::<T>::put(existential_deposit);
+			}
+			let allowed_calls: BoundedVec<_, _> = self
+				.contracts_call_filter
+				.clone()
+				.try_into()
+				.expect("too many entries in contracts call filter");
+			AllowedContractsCalls::<T>::put(allowed_calls);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Contracts parameters were updated.
+		ContractsParametersSet { parameters: ContractsParameters<T::Balance> },
+		/// Fee parameters were updated.
+		FeeParametersSet { parameters: FeeParameters<T::Balance> },
+		/// Existential deposit was updated.
+		ExistentialDepositSet { existential_deposit: T::Balance },
+		/// Contracts call filter was updated.
+		ContractsCallFilterSet { allowed_calls: Vec<AllowedCall> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// `max_code_len` is above `MaxCodeLenLimit`.
+		MaxCodeLenTooLarge,
+		/// `weight_fee_denominator` is zero.
+		ZeroWeightFeeDenominator,
+		/// Existential deposit is zero.
+		ZeroExistentialDeposit,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the limits and deposits applied to contracts.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_contracts_parameters(
+			origin: OriginFor<T>,
+			parameters: ContractsParameters<T::Balance>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			Self::ensure_valid_contracts_parameters(&parameters)?;
+
+			ActiveContractsParameters::<T>::put(&parameters);
+			Self::deposit_event(Event::ContractsParametersSet { parameters });
+			Ok(())
+		}
+
+		/// Set the conversion of weight and extrinsic length to fee.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_fee_parameters(
+			origin: OriginFor<T>,
+			parameters: FeeParameters<T::Balance>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			Self::ensure_valid_fee_parameters(&parameters)?;
+
+			ActiveFeeParameters::<T>::put(&parameters);
+			Self::deposit_event(Event::FeeParametersSet { parameters });
+			Ok(())
+		}
+
+		/// Set the existential deposit. Existing accounts below the new value are only reaped
+		/// once their balance changes.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_existential_deposit(
+			origin: OriginFor<T>,
+			#[pallet::compact] existential_deposit: T::Balance,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(!existential_deposit.is_zero(), Error::<T>::ZeroExistentialDeposit);
+
+			ExistentialDeposit::<T>::put(existential_deposit);
+			Self::deposit_event(Event::ExistentialDepositSet { existential_deposit });
+			Ok(())
+		}
+
+		/// Set the calls which contracts are allowed to dispatch with `call_runtime`, replacing
+		/// the previous ones. An empty list disallows every call.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_contracts_call_filter(
+			origin: OriginFor<T>,
+			allowed_calls: BoundedVec<AllowedCall, T::MaxAllowedContractsCalls>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			AllowedContractsCalls::<T>::put(&allowed_calls);
+			Self::deposit_event(Event::ContractsCallFilterSet {
+				allowed_calls: allowed_calls.into_inner(),
+			});
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub(crate) fn ensure_valid_contracts_parameters(
+			parameters: &ContractsParameters<T::Balance>,
+		) -> DispatchResult {
+			ensure!(
+				parameters.max_code_len <= T::MaxCodeLenLimit::get(),
+				Error::<T>::MaxCodeLenTooLarge
+			);
+			Ok(())
+		}
+
+		pub(crate) fn ensure_valid_fee_parameters(
+			parameters: &FeeParameters<T::Balance>,
+		) -> DispatchResult {
+			ensure!(
+				!parameters.weight_fee_denominator.is_zero(),
+				Error::<T>::ZeroWeightFeeDenominator
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Converts weight to fee according to the [`FeeParameters`] in storage.
+pub struct WeightToFee<T>(PhantomData<T>);
+
+impl<T: Config> WeightToFeeT for WeightToFee<T> {
+	type Balance = T::Balance;
+
+	fn weight_to_fee(weight: &Weight) -> Self::Balance {
+		let parameters = Pallet::<T>::fee_parameters();
+		let ref_time: T::Balance = weight.ref_time().saturated_into();
+		ref_time.saturating_mul(parameters.weight_fee_numerator) /
+			parameters.weight_fee_denominator.max(One::one())
+	}
+}
+
+/// Converts extrinsic length to fee according to the [`FeeParameters`] in storage.
+///
+/// `pallet_transaction_payment` passes the length as the `ref_time` of `weight`.
+pub struct LengthToFee<T>(PhantomData<T>);
+
+impl<T: Config> WeightToFeeT for LengthToFee<T> {
+	type Balance = T::Balance;
+
+	fn weight_to_fee(weight: &Weight) -> Self::Balance {
+		let length: T::Balance = weight.ref_time().saturated_into();
+		length.saturating_mul(Pallet::<T>::fee_parameters().length_fee_per_byte)
+	}
+}
+
+/// Lets contracts dispatch the calls in [`AllowedContractsCalls`]. Meant to be used as
+/// `pallet_contracts::Config::CallFilter`.
+///
+/// Only the outermost call is checked: allowing e.g. `Utility` lets contracts dispatch anything
+/// wrapped in a batch.
+pub struct ContractsCallFilter<T>(PhantomData<T>);
+
+impl<T: Config, Call: Encode> Contains<Call> for ContractsCallFilter<T> {
+	fn contains(call: &Call) -> bool {
+		// A runtime call is encoded as its pallet index, followed by its call index.
+		let indices =
+			call.using_encoded(|encoded| encoded.first().copied().zip(encoded.get(1).copied()));
+		match indices {
+			Some((pallet_index, call_index)) => Pallet::<T>::allowed_contracts_calls()
+				.iter()
+				.any(|allowed| allowed.matches(pallet_index, call_index)),
+			None => false,
+		}
+	}
+}
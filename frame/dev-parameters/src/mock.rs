@@ -0,0 +1,108 @@
+use crate::{self as pallet_dev_parameters, AllowedCall, ContractsParameters, FeeParameters};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, ConstU64},
+};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub struct Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		DevParameters: pallet_dev_parameters::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+	pub DefaultContractsParameters: ContractsParameters<u64> = ContractsParameters {
+		max_code_len: 64 * 1024,
+		deposit_per_item: 10,
+		deposit_per_byte: 1,
+		default_deposit_limit: 10_000,
+		unsafe_unstable_interface: true,
+		max_memory_pages: 16,
+		max_event_topics: 4,
+		max_subject_len: 32,
+		max_payload_len: 16 * 1024,
+	};
+	pub DefaultFeeParameters: FeeParameters<u64> = FeeParameters {
+		weight_fee_numerator: 1,
+		weight_fee_denominator: 1,
+		length_fee_per_byte: 1,
+	};
+}
+
+impl pallet_dev_parameters::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u64;
+	type DefaultContractsParameters = DefaultContractsParameters;
+	type DefaultFeeParameters = DefaultFeeParameters;
+	type DefaultExistentialDeposit = ConstU64<1>;
+	type MaxCodeLenLimit = ConstU32<{ 128 * 1024 }>;
+	type MaxAllowedContractsCalls = ConstU32<4>;
+}
+
+#[derive(Default)]
+pub struct ExtBuilder {
+	genesis: pallet_dev_parameters::GenesisConfig<Test>,
+}
+
+impl ExtBuilder {
+	pub fn existential_deposit(mut self, existential_deposit: u64) -> Self {
+		self.genesis.existential_deposit = Some(existential_deposit);
+		self
+	}
+
+	pub fn fee_parameters(mut self, parameters: FeeParameters<u64>) -> Self {
+		self.genesis.fee_parameters = Some(parameters);
+		self
+	}
+
+	pub fn contracts_call_filter(mut self, allowed_calls: Vec<AllowedCall>) -> Self {
+		self.genesis.contracts_call_filter = allowed_calls;
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		self.genesis.assimilate_storage(&mut t).unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
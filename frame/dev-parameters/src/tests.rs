@@ -0,0 +1,165 @@
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::Contains, weights::Weight};
+use mock::*;
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn defaults_are_used_until_set() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(DevParameters::contracts_parameters(), DefaultContractsParameters::get());
+		assert_eq!(DevParameters::fee_parameters(), DefaultFeeParameters::get());
+		assert_eq!(DevParameters::existential_deposit(), 1);
+	});
+}
+
+#[test]
+fn genesis_overrides_defaults() {
+	let fee_parameters = FeeParameters {
+		weight_fee_numerator: 3,
+		weight_fee_denominator: 2,
+		length_fee_per_byte: 7,
+	};
+
+	ExtBuilder::default()
+		.existential_deposit(500)
+		.fee_parameters(fee_parameters.clone())
+		.build()
+		.execute_with(|| {
+			assert_eq!(DevParameters::existential_deposit(), 500);
+			assert_eq!(DevParameters::fee_parameters(), fee_parameters);
+			assert_eq!(DevParameters::contracts_parameters(), DefaultContractsParameters::get());
+		});
+}
+
+#[test]
+fn set_contracts_parameters_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let parameters = ContractsParameters {
+			max_code_len: 32 * 1024,
+			unsafe_unstable_interface: false,
+			..DefaultContractsParameters::get()
+		};
+
+		assert_noop!(
+			DevParameters::set_contracts_parameters(RuntimeOrigin::signed(1), parameters.clone()),
+			BadOrigin
+		);
+		assert_ok!(DevParameters::set_contracts_parameters(
+			RuntimeOrigin::root(),
+			parameters.clone()
+		));
+		assert_eq!(DevParameters::contracts_parameters(), parameters);
+		System::assert_last_event(Event::ContractsParametersSet { parameters }.into());
+	});
+}
+
+#[test]
+fn max_code_len_is_bounded() {
+	ExtBuilder::default().build().execute_with(|| {
+		let parameters = ContractsParameters {
+			max_code_len: 128 * 1024 + 1,
+			..DefaultContractsParameters::get()
+		};
+
+		assert_noop!(
+			DevParameters::set_contracts_parameters(RuntimeOrigin::root(), parameters),
+			Error::<Test>::MaxCodeLenTooLarge
+		);
+	});
+}
+
+#[test]
+fn invalid_fee_parameters_and_existential_deposit_are_rejected() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			DevParameters::set_fee_parameters(
+				RuntimeOrigin::root(),
+				FeeParameters {
+					weight_fee_numerator: 1,
+					weight_fee_denominator: 0,
+					length_fee_per_byte: 1
+				}
+			),
+			Error::<Test>::ZeroWeightFeeDenominator
+		);
+		assert_noop!(
+			DevParameters::set_existential_deposit(RuntimeOrigin::root(), 0),
+			Error::<Test>::ZeroExistentialDeposit
+		);
+
+		assert_ok!(DevParameters::set_existential_deposit(RuntimeOrigin::root(), 42));
+		assert_eq!(DevParameters::existential_deposit(), 42);
+	});
+}
+
+#[test]
+fn fee_conversion_follows_fee_parameters() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Identity by default.
+		assert_eq!(WeightToFee::<Test>::weight_to_fee(&Weight::from_parts(1_000, 0)), 1_000);
+		assert_eq!(LengthToFee::<Test>::weight_to_fee(&Weight::from_parts(100, 0)), 100);
+
+		assert_ok!(DevParameters::set_fee_parameters(
+			RuntimeOrigin::root(),
+			FeeParameters {
+				weight_fee_numerator: 3,
+				weight_fee_denominator: 2,
+				length_fee_per_byte: 10
+			}
+		));
+		assert_eq!(WeightToFee::<Test>::weight_to_fee(&Weight::from_parts(1_000, 0)), 1_500);
+		assert_eq!(LengthToFee::<Test>::weight_to_fee(&Weight::from_parts(100, 0)), 1_000);
+	});
+}
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn set_existential_deposit() -> RuntimeCall {
+	RuntimeCall::DevParameters(crate::Call::set_existential_deposit { existential_deposit: 1 })
+}
+
+#[test]
+fn contracts_call_filter_allows_nothing_by_default() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(!ContractsCallFilter::<Test>::contains(&remark()));
+		assert!(!ContractsCallFilter::<Test>::contains(&set_existential_deposit()));
+	});
+}
+
+#[test]
+fn contracts_call_filter_matches_pallet_and_call_indices() {
+	// `System` is the pallet with index 0, `remark` its call with index 0.
+	ExtBuilder::default()
+		.contracts_call_filter(vec![AllowedCall { pallet_index: 0, call_index: Some(0) }])
+		.build()
+		.execute_with(|| {
+			assert!(ContractsCallFilter::<Test>::contains(&remark()));
+			assert!(!ContractsCallFilter::<Test>::contains(&RuntimeCall::System(
+				frame_system::Call::remark_with_event { remark: vec![] }
+			)));
+			assert!(!ContractsCallFilter::<Test>::contains(&set_existential_deposit()));
+
+			// Whole `DevParameters` pallet.
+			let allowed_calls = vec![AllowedCall { pallet_index: 1, call_index: None }];
+			assert_ok!(DevParameters::set_contracts_call_filter(
+				RuntimeOrigin::root(),
+				allowed_calls.clone().try_into().unwrap()
+			));
+			System::assert_last_event(Event::ContractsCallFilterSet { allowed_calls }.into());
+
+			assert!(!ContractsCallFilter::<Test>::contains(&remark()));
+			assert!(ContractsCallFilter::<Test>::contains(&set_existential_deposit()));
+		});
+}
+
+#[test]
+fn set_contracts_call_filter_requires_root() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			DevParameters::set_contracts_call_filter(RuntimeOrigin::signed(1), Default::default()),
+			BadOrigin
+		);
+	});
+}
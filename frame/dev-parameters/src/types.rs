@@ -0,0 +1,63 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+
+/// Limits and deposits applied to `pallet_contracts`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ContractsParameters<Balance> {
+	/// Maximum length of a contract code in bytes.
+	pub max_code_len: u32,
+	/// Deposit charged for every storage item a contract creates.
+	pub deposit_per_item: Balance,
+	/// Deposit charged for every byte of storage a contract occupies.
+	pub deposit_per_byte: Balance,
+	/// Storage deposit limit used when a call doesn't specify one.
+	pub default_deposit_limit: Balance,
+	/// Whether contracts are allowed to use `#[unstable]` host functions.
+	pub unsafe_unstable_interface: bool,
+	/// Maximum number of 64 KiB linear memory pages a contract can use.
+	pub max_memory_pages: u32,
+	/// Maximum number of topics an event can have.
+	pub max_event_topics: u32,
+	/// Maximum length of the subject passed to the randomness host functions.
+	pub max_subject_len: u32,
+	/// Maximum length of an event payload in bytes.
+	pub max_payload_len: u32,
+}
+
+/// Linear conversion of weight and extrinsic length to fee.
+///
+/// `fee = ref_time * weight_fee_numerator / weight_fee_denominator + length * length_fee_per_byte`
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FeeParameters<Balance> {
+	/// Numerator of the fee charged per unit of `ref_time`.
+	pub weight_fee_numerator: Balance,
+	/// Denominator of the fee charged per unit of `ref_time`. Must not be zero.
+	pub weight_fee_denominator: Balance,
+	/// Fee charged per byte of an extrinsic.
+	pub length_fee_per_byte: Balance,
+}
+
+/// A call, or all calls of a pallet, that contracts are allowed to dispatch.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AllowedCall {
+	/// Index of the pallet in `construct_runtime!`.
+	pub pallet_index: u8,
+	/// Index of the call within the pallet, `None` allows every call of the pallet.
+	pub call_index: Option<u8>,
+}
+
+impl AllowedCall {
+	/// Whether the call with the given indices is allowed by this entry.
+	pub fn matches(&self, pallet_index: u8, call_index: u8) -> bool {
+		self.pallet_index == pallet_index && self.call_index.map_or(true, |i| i == call_index)
+	}
+}
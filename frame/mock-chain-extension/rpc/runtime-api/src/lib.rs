@@ -0,0 +1,18 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet_mock_chain_extension::{MockData, MockResponse};
+use sp_runtime::traits::Block as BlockT;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait MockChainExtensionApi {
+		/// Unsigned extrinsic registering `mock` for the given chain extension function.
+		fn mock_chain_extension_extrinsic(ext_id: u16, func_id: u16, mock: MockResponse) -> <Block as BlockT>::Extrinsic;
+
+		/// Unsigned extrinsic removing all mocks.
+		fn clear_mocks_extrinsic() -> <Block as BlockT>::Extrinsic;
+
+		/// Registered mocks as `(ext_id, func_id, mock)`.
+		fn mocks() -> Vec<(u16, u16, MockResponse)>;
+	}
+}
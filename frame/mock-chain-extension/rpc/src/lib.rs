@@ -0,0 +1,189 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use futures::future::TryFutureExt;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sc_transaction_pool_api::TransactionPool;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_mock_chain_extension_rpc_runtime_api::MockChainExtensionApi as MockChainExtensionRuntimeApi;
+use pallet_mock_chain_extension_rpc_runtime_api::{MockData, MockResponse};
+
+/// Response returned to the contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MockOutput {
+	/// Status code returned by the chain extension call.
+	pub return_code: u32,
+	/// Bytes written to the output buffer of the contract.
+	pub data: Bytes,
+}
+
+/// A registered mock.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainExtensionMock {
+	pub ext_id: u16,
+	pub func_id: u16,
+	pub output: MockOutput,
+}
+
+/// RPC trait that provides methods for mocking contracts chain extensions.
+#[rpc(server)]
+#[async_trait]
+pub trait MockChainExtensionApi<BlockHash> {
+	/// Answer every call of `func_id` of chain extension `ext_id` with `output`, from the next
+	/// block on, instead of passing it on to the real extension.
+	#[method(name = "dev_mockChainExtension")]
+	async fn mock_chain_extension(
+		&self,
+		ext_id: u16,
+		func_id: u16,
+		output: MockOutput,
+	) -> RpcResult<()>;
+
+	/// Remove all mocks, from the next block on.
+	#[method(name = "dev_clearChainExtensionMocks")]
+	async fn clear_mocks(&self) -> RpcResult<()>;
+
+	/// Mocks registered at the given block.
+	#[method(name = "dev_getChainExtensionMocks")]
+	fn get_mocks(&self, at: Option<BlockHash>) -> RpcResult<Vec<ChainExtensionMock>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// Output bytes are too long.
+	InvalidParams,
+	/// The extrinsic wasn't accepted by the transaction pool.
+	PoolError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidParams => 2,
+			Error::PoolError => 3,
+		}
+	}
+}
+
+fn error(code: Error, message: &str, data: Option<String>) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(code.into(), message, data)).into()
+}
+
+fn mock_data(bytes: Bytes) -> RpcResult<MockData> {
+	bytes
+		.0
+		.try_into()
+		.map_err(|_| error(Error::InvalidParams, "Mock data too long.", None))
+}
+
+/// Provides RPC methods to mock chain extensions.
+pub struct MockChainExtension<C, P, Block> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	/// Shared reference to the transaction pool.
+	pool: Arc<P>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, P, Block> MockChainExtension<C, P, Block> {
+	/// Creates a new instance of the MockChainExtension Rpc helper.
+	pub fn new(client: Arc<C>, pool: Arc<P>) -> Self {
+		Self { client, pool, _marker: Default::default() }
+	}
+}
+
+impl<Client, Pool, Block> MockChainExtension<Client, Pool, Block>
+where
+	Block: BlockT,
+	Client: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	Pool: TransactionPool<Block = Block> + 'static,
+{
+	async fn submit(&self, at: Block::Hash, extrinsic: Block::Extrinsic) -> RpcResult<()> {
+		self.pool
+			.submit_one(
+				&BlockId::Hash(at),
+				sc_transaction_pool_api::TransactionSource::Local,
+				extrinsic,
+			)
+			.map_ok(|_| ())
+			.map_err(|e| {
+				error(Error::PoolError, "Unable to submit extrinsic.", Some(e.to_string()))
+			})
+			.await
+	}
+}
+
+fn runtime_error(message: &str, e: sp_api::ApiError) -> jsonrpsee::core::Error {
+	error(Error::RuntimeError, message, Some(e.to_string()))
+}
+
+#[async_trait]
+impl<Client, Pool, Block> MockChainExtensionApiServer<<Block as BlockT>::Hash>
+	for MockChainExtension<Client, Pool, Block>
+where
+	Block: BlockT,
+	Client: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	Client::Api: MockChainExtensionRuntimeApi<Block>,
+	Pool: TransactionPool<Block = Block> + 'static,
+{
+	async fn mock_chain_extension(
+		&self,
+		ext_id: u16,
+		func_id: u16,
+		output: MockOutput,
+	) -> RpcResult<()> {
+		let mock = MockResponse { ret_code: output.return_code, output: mock_data(output.data)? };
+
+		let best_hash = self.client.info().best_hash;
+		let extrinsic = self
+			.client
+			.runtime_api()
+			.mock_chain_extension_extrinsic(best_hash, ext_id, func_id, mock)
+			.map_err(|e| runtime_error("Unable to build mock extrinsic.", e))?;
+		self.submit(best_hash, extrinsic).await
+	}
+
+	async fn clear_mocks(&self) -> RpcResult<()> {
+		let best_hash = self.client.info().best_hash;
+		let extrinsic = self
+			.client
+			.runtime_api()
+			.clear_mocks_extrinsic(best_hash)
+			.map_err(|e| runtime_error("Unable to build clear mocks extrinsic.", e))?;
+		self.submit(best_hash, extrinsic).await
+	}
+
+	fn get_mocks(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<ChainExtensionMock>> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let mocks = self
+			.client
+			.runtime_api()
+			.mocks(at_hash)
+			.map_err(|e| runtime_error("Unable to get mocks.", e))?;
+
+		Ok(mocks
+			.into_iter()
+			.map(|(ext_id, func_id, mock)| ChainExtensionMock {
+				ext_id,
+				func_id,
+				output: MockOutput {
+					return_code: mock.ret_code,
+					data: mock.output.into_inner().into(),
+				},
+			})
+			.collect())
+	}
+}
@@ -0,0 +1,167 @@
+//! # Mock Chain Extension Pallet
+//!
+//! [Swanky Node specific]
+//! Lets tests answer chain extension calls of contracts with canned responses, so that contracts
+//! written against extensions swanky-node doesn't implement can still be tested.
+//!
+//! Responses are registered per `(ext_id, func_id)` through unsigned extrinsics, which the
+//! `dev_mockChainExtension` RPC submits. [`MockChainExtension`] wraps the chain extensions of the
+//! runtime: calls to a function without a mock are passed on to them untouched.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::traits::Get;
+use pallet_chain_extension_tracing::TracedEnvironment;
+use pallet_contracts::chain_extension::{ChainExtension, Environment, Ext, InitState, RetVal};
+use sp_runtime::DispatchError;
+use sp_std::marker::PhantomData;
+
+pub use pallet::*;
+pub use types::{MockData, MockResponse, MAX_MOCK_DATA_LEN};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod types;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Hash;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Mock per `(ext_id, func_id)`.
+	#[pallet::storage]
+	#[pallet::getter(fn mocks)]
+	pub type Mocks<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u16, Twox64Concat, u16, MockResponse, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A mock was registered for a chain extension function.
+		ChainExtensionMocked { ext_id: u16, func_id: u16 },
+		/// All mocks were removed.
+		MocksCleared,
+	}
+
+	/// [Swanky Node specific]
+	/// Mocks are registered with unsigned extrinsics submitted by the node's RPC.
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			match (source, call) {
+				(
+					TransactionSource::Local | TransactionSource::InBlock,
+					Call::mock_chain_extension { .. } | Call::clear_mocks { .. },
+				) => ValidTransaction::with_tag_prefix("MockChainExtension")
+					.priority(TransactionPriority::max_value())
+					// The magic number makes otherwise identical calls distinct.
+					.and_provides(T::Hashing::hash_of(call))
+					.propagate(false)
+					.build(),
+				_ => InvalidTransaction::Call.into(),
+			}
+		}
+
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			match call {
+				Call::mock_chain_extension { .. } | Call::clear_mocks { .. } => Ok(()),
+				_ => Err(InvalidTransaction::Call.into()),
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register `mock` for the chain extension function `func_id` of extension `ext_id`,
+		/// replacing its previous mock.
+		///
+		/// The dispatch origin for this call must be none, it's submitted by the node's RPC.
+		/// `_magic_number` only serves to give repeated registrations distinct hashes.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn mock_chain_extension(
+			origin: OriginFor<T>,
+			ext_id: u16,
+			func_id: u16,
+			mock: MockResponse,
+			#[pallet::compact] _magic_number: u64,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			Mocks::<T>::insert(ext_id, func_id, mock);
+			Self::deposit_event(Event::ChainExtensionMocked { ext_id, func_id });
+			Ok(())
+		}
+
+		/// Remove all mocks.
+		///
+		/// The dispatch origin for this call must be none, it's submitted by the node's RPC.
+		#[pallet::call_index(1)]
+		// Mocks are only ever registered by hand, so there are few of them.
+		#[pallet::weight(T::DbWeight::get().writes(64))]
+		pub fn clear_mocks(
+			origin: OriginFor<T>,
+			#[pallet::compact] _magic_number: u64,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let _ = Mocks::<T>::clear(u32::MAX, None);
+			Self::deposit_event(Event::MocksCleared);
+			Ok(())
+		}
+	}
+}
+
+/// Chain extension answering calls of mocked functions, passing other calls on to `E`.
+///
+/// Mocks answer every input of their function: `pallet_contracts` only gives access to the input
+/// by turning the [`Environment`] into a state which can't be passed on to `E` anymore, so calls
+/// can't be told apart by their input and still fall through.
+pub struct MockChainExtension<T, E> {
+	inner: E,
+	_phantom: PhantomData<T>,
+}
+
+impl<T, E: Default> Default for MockChainExtension<T, E> {
+	fn default() -> Self {
+		MockChainExtension { inner: E::default(), _phantom: PhantomData }
+	}
+}
+
+impl<T, E> ChainExtension<T> for MockChainExtension<T, E>
+where
+	T: Config + pallet_contracts::Config,
+	E: ChainExtension<T>,
+{
+	fn call<X: Ext<T = T>>(
+		&mut self,
+		mut env: Environment<X, InitState>,
+	) -> Result<RetVal, DispatchError> {
+		let Some(mock) = Mocks::<T>::get(env.ext_id(), env.func_id()) else {
+			env.charge_weight(T::DbWeight::get().reads(1))?;
+			return self.inner.call(env)
+		};
+
+		let mut env = env.buf_in_buf_out();
+		env.traced(|env| {
+			env.charge_weight(T::DbWeight::get().reads(1))?;
+			env.write_traced(&mock.output, false, None)?;
+			Ok(RetVal::Converging(mock.ret_code))
+		})
+	}
+}
@@ -0,0 +1,87 @@
+use super::*;
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::{InvalidTransaction, TransactionSource, ValidateUnsigned},
+};
+use mock::*;
+use sp_runtime::traits::BadOrigin;
+
+fn response(ret_code: u32, output: &[u8]) -> MockResponse {
+	MockResponse { ret_code, output: output.to_vec().try_into().unwrap() }
+}
+
+#[test]
+fn mock_chain_extension_works() {
+	new_test_ext().execute_with(|| {
+		let mock = response(0, b"out");
+		assert_ok!(MockChainExtension::mock_chain_extension(
+			RuntimeOrigin::none(),
+			7,
+			1,
+			mock.clone(),
+			0
+		));
+
+		assert_eq!(MockChainExtension::mocks(7, 1), Some(mock));
+		assert_eq!(MockChainExtension::mocks(7, 2), None);
+		assert_eq!(MockChainExtension::mocks(8, 1), None);
+		System::assert_last_event(
+			Event::<Test>::ChainExtensionMocked { ext_id: 7, func_id: 1 }.into(),
+		);
+	});
+}
+
+#[test]
+fn later_mock_replaces_earlier_one() {
+	new_test_ext().execute_with(|| {
+		let first = response(1, b"first");
+		let second = response(2, b"second");
+		assert_ok!(MockChainExtension::mock_chain_extension(RuntimeOrigin::none(), 7, 1, first, 0));
+		assert_ok!(MockChainExtension::mock_chain_extension(
+			RuntimeOrigin::none(),
+			7,
+			1,
+			second.clone(),
+			0
+		));
+
+		assert_eq!(MockChainExtension::mocks(7, 1), Some(second));
+		assert_eq!(Mocks::<Test>::iter().count(), 1);
+	});
+}
+
+#[test]
+fn clear_mocks_works() {
+	new_test_ext().execute_with(|| {
+		let mock = response(0, b"");
+		assert_ok!(MockChainExtension::mock_chain_extension(RuntimeOrigin::none(), 7, 1, mock, 0));
+
+		assert_ok!(MockChainExtension::clear_mocks(RuntimeOrigin::none(), 0));
+		assert!(!Mocks::<Test>::contains_key(7, 1));
+		System::assert_last_event(Event::<Test>::MocksCleared.into());
+	});
+}
+
+#[test]
+fn calls_require_none_origin() {
+	new_test_ext().execute_with(|| {
+		let mock = response(0, b"");
+		assert_noop!(
+			MockChainExtension::mock_chain_extension(RuntimeOrigin::signed(1), 7, 1, mock, 0),
+			BadOrigin
+		);
+		assert_noop!(MockChainExtension::clear_mocks(RuntimeOrigin::root(), 0), BadOrigin);
+	});
+}
+
+#[test]
+fn only_local_unsigned_calls_are_valid() {
+	new_test_ext().execute_with(|| {
+		let call = Call::<Test>::clear_mocks { magic_number: 0 };
+		assert_ok!(MockChainExtension::validate_unsigned(TransactionSource::Local, &call));
+		assert_eq!(
+			MockChainExtension::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Call.into())
+		);
+	});
+}
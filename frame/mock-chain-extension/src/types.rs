@@ -0,0 +1,19 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::ConstU32, BoundedVec};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// Maximum length of a mocked output.
+pub const MAX_MOCK_DATA_LEN: u32 = 16 * 1024;
+
+/// Bytes of a mocked output.
+pub type MockData = BoundedVec<u8, ConstU32<MAX_MOCK_DATA_LEN>>;
+
+/// Canned response to a chain extension call.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MockResponse {
+	/// Status code returned to the contract.
+	pub ret_code: u32,
+	/// Bytes written to the output buffer of the contract.
+	pub output: MockData,
+}
@@ -0,0 +1,152 @@
+//! Compact summary of every imported block, printed with `--print-blocks`.
+
+use std::{fmt::Write, sync::Arc};
+
+use codec::{Decode, Encode};
+use frame_support::traits::GetCallMetadata;
+use frame_system::Phase;
+use futures::StreamExt;
+use pallet_contracts_dev_rpc::{ContractsDevRuntimeApi, MetadataRegistry};
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sp_api::ProvideRuntimeApi;
+use sp_core::hexdisplay::HexDisplay;
+use sp_runtime::{traits::Header as _, DispatchError, MultiAddress};
+use swanky_runtime::{AccountId, Balance, Hash, RuntimeEvent, UncheckedExtrinsic, Weight};
+
+use crate::service::FullClient;
+
+/// Log target of the block summaries.
+const LOG_TARGET: &str = "swanky::blocks";
+
+/// What an extrinsic did, gathered from the events it deposited.
+#[derive(Default)]
+struct Outcome {
+	error: Option<DispatchError>,
+	fee: Option<Balance>,
+	weight: Weight,
+	/// Contract instantiations and events.
+	contracts: Vec<String>,
+}
+
+/// Prints the summary of every imported block, decoding contract events with the metadata in
+/// `registry`.
+pub async fn print_blocks(client: Arc<FullClient>, registry: MetadataRegistry<Hash>) {
+	let mut imported = client.import_notification_stream();
+	while let Some(notification) = imported.next().await {
+		match summarize(&client, &registry, notification.hash) {
+			Ok(summary) => log::info!(
+				target: LOG_TARGET,
+				"#{} ({:?})\n{}",
+				notification.header.number(),
+				notification.hash,
+				summary.trim_end(),
+			),
+			Err(e) => log::warn!(
+				target: LOG_TARGET,
+				"Unable to summarize block {:?}: {}",
+				notification.hash,
+				e
+			),
+		}
+	}
+}
+
+fn summarize(
+	client: &FullClient,
+	registry: &MetadataRegistry<Hash>,
+	hash: Hash,
+) -> Result<String, String> {
+	let extrinsics = client
+		.block_body(hash)
+		.map_err(|e| e.to_string())?
+		.ok_or_else(|| format!("Unknown block body {:?}", hash))?;
+	let events = crate::events::block_events(client, hash)?;
+
+	let mut outcomes: Vec<Outcome> = extrinsics.iter().map(|_| Outcome::default()).collect();
+	for record in events {
+		let Phase::ApplyExtrinsic(index) = record.phase else { continue };
+		let Some(outcome) = outcomes.get_mut(index as usize) else { continue };
+		match record.event {
+			RuntimeEvent::System(frame_system::Event::ExtrinsicSuccess { dispatch_info }) =>
+				outcome.weight = dispatch_info.weight,
+			RuntimeEvent::System(frame_system::Event::ExtrinsicFailed {
+				dispatch_error,
+				dispatch_info,
+			}) => {
+				outcome.error = Some(dispatch_error);
+				outcome.weight = dispatch_info.weight;
+			},
+			RuntimeEvent::TransactionPayment(
+				pallet_transaction_payment::Event::TransactionFeePaid { actual_fee, .. },
+			) => outcome.fee = Some(actual_fee),
+			RuntimeEvent::Contracts(pallet_contracts::Event::Instantiated {
+				deployer,
+				contract,
+			}) => outcome.contracts.push(format!("instantiated {} by {}", contract, deployer)),
+			RuntimeEvent::Contracts(pallet_contracts::Event::ContractEmitted {
+				contract,
+				data,
+			}) => outcome.contracts.push(contract_event(client, registry, hash, contract, &data)),
+			_ => {},
+		}
+	}
+
+	let mut summary = String::new();
+	let mut total = Weight::zero();
+	for (index, (xt, outcome)) in extrinsics.iter().zip(outcomes).enumerate() {
+		let xt = UncheckedExtrinsic::decode(&mut &xt.encode()[..]).map_err(|e| e.to_string())?;
+		let call = xt.function.get_call_metadata();
+		let signer = match &xt.signature {
+			Some((MultiAddress::Id(signer), _, _)) => signer.to_string(),
+			Some((address, _, _)) => format!("{:?}", address),
+			None => "unsigned".into(),
+		};
+		let result = match outcome.error {
+			Some(error) => format!("failed with {:?}", error),
+			None => "ok".into(),
+		};
+		let fee = outcome.fee.map(|fee| format!(", fee {}", fee)).unwrap_or_default();
+		let _ = writeln!(
+			summary,
+			"  {} {}.{} by {}: {}{}, weight {}/{}",
+			index,
+			call.pallet_name,
+			call.function_name,
+			signer,
+			result,
+			fee,
+			outcome.weight.ref_time(),
+			outcome.weight.proof_size(),
+		);
+		for line in outcome.contracts {
+			let _ = writeln!(summary, "      {}", line);
+		}
+		total = total.saturating_add(outcome.weight);
+	}
+	let _ = writeln!(summary, "  total weight {}/{}", total.ref_time(), total.proof_size());
+	Ok(summary)
+}
+
+/// Describes an event emitted by `contract`, decoded if metadata is registered for its code.
+fn contract_event(
+	client: &FullClient,
+	registry: &MetadataRegistry<Hash>,
+	hash: Hash,
+	contract: AccountId,
+	data: &[u8],
+) -> String {
+	let decoded = client
+		.runtime_api()
+		.code_hash(hash, contract.clone())
+		.ok()
+		.flatten()
+		.and_then(|code_hash| registry.get(&code_hash))
+		.and_then(|metadata| {
+			let event = metadata.decode_event(data).ok()?;
+			Some(format!("{}::{}", metadata.name(), event))
+		});
+	match decoded {
+		Some(event) => format!("event {} from {}", event, contract),
+		None => format!("event 0x{} from {}", HexDisplay::from(&data), contract),
+	}
+}
@@ -1,15 +1,92 @@
+use std::{fs, path::Path};
+
+use frame_support::{
+	dispatch::{GetCallIndex, GetCallName},
+	traits::PalletInfoAccess,
+};
+use sc_chain_spec::ChainSpecExtension;
 use sc_service::ChainType;
-use sp_core::{sr25519, Pair, Public};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sp_core::{sr25519, Bytes, Pair, Public};
 use sp_runtime::traits::{IdentifyAccount, Verify};
+#[cfg(feature = "dev-cheatcodes")]
+use swanky_runtime::CheatcodesConfig;
 use swanky_runtime::{
-	AccountId, BalancesConfig, GenesisConfig, Signature, SudoConfig, SystemConfig, WASM_BINARY,
+	AccountId, AllowedCall, Assets, AssetsConfig, Balance, Balances, BalancesCall, BalancesConfig,
+	ContractsDevConfig, ContractsParameters, DappsStaking, DefaultContractsParameters,
+	DevParametersConfig, ExtrinsicBaseWeight, FeeParameters, GenesisConfig, Runtime, Signature,
+	SudoConfig, SystemConfig, Uniques, MICROUNIT, MILLIUNIT, UNIT, WASM_BINARY,
 };
 
+use crate::contract_watcher::Artifact;
+
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
-pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
+pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig, Extensions>;
+
+/// Node specific fields of the chain spec.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ChainSpecExtension)]
+#[serde(rename_all = "camelCase")]
+pub struct Extensions {
+	/// Metadata of the `.contract` bundles of the genesis contracts, to decode their calls and
+	/// events.
+	#[serde(default)]
+	pub contract_metadata: Vec<Value>,
+}
+
+impl Extensions {
+	/// The extensions of `chain_spec`, if it is one of ours.
+	pub fn try_get(chain_spec: &dyn sc_service::ChainSpec) -> Option<&Self> {
+		sc_chain_spec::get_extension(chain_spec.extensions())
+	}
+}
+
+/// Loads the chain spec at `path`.
+///
+/// The `code` of a genesis contract is either its Wasm code, a `.contract` bundle or the path of a
+/// bundle, relative to the chain spec. Bundles are replaced with their code, their metadata being
+/// kept in the `contractMetadata` extension.
+pub fn from_json_file(path: &Path) -> Result<ChainSpec, String> {
+	let contents = fs::read(path).map_err(|e| format!("Error opening spec file: {}", e))?;
+	let mut json: Value =
+		serde_json::from_slice(&contents).map_err(|e| format!("Error parsing spec file: {}", e))?;
+	let dir = path.parent().unwrap_or_else(|| Path::new("."));
+	let bundles = genesis_bundles(&mut json, dir)?;
+	if let Some(spec) = json.as_object_mut() {
+		let metadata = spec.entry("contractMetadata").or_insert_with(|| Value::Array(Vec::new()));
+		metadata
+			.as_array_mut()
+			.ok_or("contractMetadata is not an array")?
+			.extend(bundles);
+	}
+	let contents = serde_json::to_vec(&json).map_err(|e| e.to_string())?;
+	ChainSpec::from_json_bytes(contents)
+}
+
+/// Replaces the bundles given as the code of genesis contracts in the plain chain spec `json`,
+/// read relative to `dir`, with their code. Returns the bundles.
+fn genesis_bundles(json: &mut Value, dir: &Path) -> Result<Vec<Value>, String> {
+	let contracts = json
+		.pointer_mut("/genesis/runtime/contractsDev/contracts")
+		.and_then(Value::as_array_mut);
+	let mut bundles = Vec::new();
+	for (index, contract) in contracts.into_iter().flatten().enumerate() {
+		let Some(code) = contract.get_mut("code") else { continue };
+		let artifact = match code {
+			Value::String(code) if code.starts_with("0x") => continue,
+			Value::String(bundle) => Artifact::read(&dir.join(bundle.as_str())),
+			Value::Object(_) => Artifact::from_bundle(index.to_string(), code.take()),
+			_ => continue,
+		}
+		.map_err(|e| format!("Invalid bundle of genesis contract {}: {}", index, e))?;
+		*code = serde_json::to_value(Bytes(artifact.code)).map_err(|e| e.to_string())?;
+		bundles.extend(artifact.metadata);
+	}
+	Ok(bundles)
+}
 
 /// Generate a crypto pair from seed.
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
@@ -28,60 +105,258 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
+/// Well-known development accounts, pre-funded by every preset.
+fn dev_accounts() -> Vec<AccountId> {
+	vec![
+		get_account_id_from_seed::<sr25519::Public>("Alice"),
+		get_account_id_from_seed::<sr25519::Public>("Bob"),
+		get_account_id_from_seed::<sr25519::Public>("Charlie"),
+		get_account_id_from_seed::<sr25519::Public>("Dave"),
+		get_account_id_from_seed::<sr25519::Public>("Eve"),
+		get_account_id_from_seed::<sr25519::Public>("Ferdie"),
+		get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
+	]
+}
+
+/// Chain properties shown by front-ends such as Polkadot-JS Apps.
+fn token_properties(symbol: Option<&str>, decimals: u32) -> sc_service::Properties {
+	let mut properties = sc_service::Properties::new();
+	if let Some(symbol) = symbol {
+		properties.insert("tokenSymbol".into(), symbol.into());
+	}
+	properties.insert("tokenDecimals".into(), decimals.into());
+	properties
+}
+
+/// Allows every call of pallet `P` to be dispatched by contracts.
+fn allow_pallet<P: PalletInfoAccess>() -> AllowedCall {
+	AllowedCall { pallet_index: P::index() as u8, call_index: None }
+}
+
+/// Allows the calls of pallet `P` with the given names to be dispatched by contracts.
+fn allow_calls<P: PalletInfoAccess, Call: GetCallName + GetCallIndex>(
+	names: &[&str],
+) -> Vec<AllowedCall> {
+	Call::get_call_names()
+		.iter()
+		.zip(Call::get_call_indices())
+		.filter(|(name, _)| names.contains(name))
+		.map(|(_, call_index)| AllowedCall {
+			pallet_index: P::index() as u8,
+			call_index: Some(*call_index),
+		})
+		.collect()
+}
+
+/// Calls which contracts can dispatch on `dev` chains: everything they may reasonably need.
+fn dev_call_filter() -> Vec<AllowedCall> {
+	vec![
+		allow_pallet::<Balances>(),
+		allow_pallet::<Assets>(),
+		allow_pallet::<DappsStaking>(),
+		allow_pallet::<Uniques>(),
+	]
+}
+
+/// Calls which contracts can dispatch on Astar-like chains: balance transfers, assets and dApps
+/// staking.
+fn astar_call_filter() -> Vec<AllowedCall> {
+	let mut allowed_calls = allow_calls::<Balances, BalancesCall<Runtime>>(&[
+		"transfer_allow_death",
+		"transfer",
+		"transfer_keep_alive",
+		"transfer_all",
+	]);
+	allowed_calls.extend([allow_pallet::<Assets>(), allow_pallet::<DappsStaking>()]);
+	allowed_calls
+}
+
+/// Contracts limits, deposits, fees and call filter approximating those of Astar
+/// (`supply_factor = 100`) and Shiden (`supply_factor = 1`).
+fn astar_dev_parameters(supply_factor: Balance) -> DevParametersConfig {
+	let contracts_deposit = |items: Balance, bytes: Balance| {
+		items * 4 * MILLIUNIT * supply_factor + bytes * 50 * MICROUNIT * supply_factor
+	};
+
+	DevParametersConfig {
+		contracts_parameters: Some(ContractsParameters {
+			deposit_per_item: contracts_deposit(1, 0),
+			deposit_per_byte: contracts_deposit(0, 1),
+			default_deposit_limit: contracts_deposit(16, 16 * 1024),
+			unsafe_unstable_interface: false,
+			..DefaultContractsParameters::get()
+		}),
+		fee_parameters: Some(FeeParameters {
+			// Fee of an extrinsic with `ExtrinsicBaseWeight` is `WeightFeeFactor`.
+			weight_fee_numerator: 308_550_000_000_000 * supply_factor,
+			weight_fee_denominator: ExtrinsicBaseWeight::get().ref_time().into(),
+			length_fee_per_byte: MILLIUNIT / 100 * supply_factor,
+		}),
+		existential_deposit: Some(1_000_000),
+		contracts_call_filter: astar_call_filter(),
+	}
+}
+
+/// Parameters which tell one chain spec preset apart from another.
+struct Preset {
+	name: &'static str,
+	id: &'static str,
+	chain_type: ChainType,
+	properties: sc_service::Properties,
+	genesis: fn(&[u8]) -> GenesisConfig,
+}
+
+impl Preset {
+	fn build(self) -> Result<ChainSpec, String> {
+		let wasm_binary =
+			WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+		let genesis = self.genesis;
+
+		Ok(ChainSpec::from_genesis(
+			// Name
+			self.name,
+			// ID
+			self.id,
+			self.chain_type,
+			move || genesis(wasm_binary),
+			// Bootnodes
+			vec![],
+			// Telemetry
+			None,
+			// Protocol ID
+			None,
+			None,
+			// Properties
+			Some(self.properties),
+			// Extensions
+			Default::default(),
+		))
+	}
+}
+
+/// Default chain. Used by `--dev` and when no `--chain` is supplied.
 pub fn development_config() -> Result<ChainSpec, String> {
-	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
-	let mut properties = serde_json::map::Map::new();
-	properties.insert("tokenDecimals".into(), 18.into());
-
-	Ok(ChainSpec::from_genesis(
-		// Name
-		"Development",
-		// ID
-		"dev",
-		ChainType::Development,
-		move || {
+	Preset {
+		name: "Development",
+		id: "dev",
+		chain_type: ChainType::Development,
+		properties: token_properties(None, 18),
+		genesis: |wasm_binary| {
 			testnet_genesis(
 				wasm_binary,
 				// Sudo account
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
 				// Pre-funded accounts
-				vec![
-					get_account_id_from_seed::<sr25519::Public>("Alice"),
-					get_account_id_from_seed::<sr25519::Public>("Bob"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie"),
-					get_account_id_from_seed::<sr25519::Public>("Dave"),
-					get_account_id_from_seed::<sr25519::Public>("Eve"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-				],
+				dev_accounts().into_iter().map(|k| (k, 1 << 100)).collect(),
+				Default::default(),
+				DevParametersConfig {
+					contracts_call_filter: dev_call_filter(),
+					..Default::default()
+				},
+				// Enable cheatcodes
+				true,
+			)
+		},
+	}
+	.build()
+}
+
+/// Same genesis as `dev`, but registered as a local chain so that its state is kept in a
+/// dedicated base path directory instead of being shared with `dev`.
+pub fn local_persistent_config() -> Result<ChainSpec, String> {
+	Preset {
+		name: "Local Persistent",
+		id: "local_persistent",
+		chain_type: ChainType::Local,
+		properties: token_properties(None, 18),
+		genesis: |wasm_binary| {
+			testnet_genesis(
+				wasm_binary,
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				dev_accounts().into_iter().map(|k| (k, 1 << 100)).collect(),
+				Default::default(),
+				DevParametersConfig {
+					contracts_call_filter: dev_call_filter(),
+					..Default::default()
+				},
+				// Enable cheatcodes
 				true,
 			)
 		},
-		// Bootnodes
-		vec![],
-		// Telemetry
-		None,
-		// Protocol ID
-		None,
-		None,
-		// Properties
-		Some(properties),
-		// Extensions
-		None,
-	))
+	}
+	.build()
+}
+
+/// Chain resembling Astar: `ASTR` token, realistic account balances and Astar's contracts deposits,
+/// fees and existential deposit, so that contracts under test hit the same limits as on mainnet.
+pub fn astar_like_config() -> Result<ChainSpec, String> {
+	Preset {
+		name: "Astar-like Development",
+		id: "astar_like",
+		chain_type: ChainType::Local,
+		properties: token_properties(Some("ASTR"), 18),
+		genesis: |wasm_binary| {
+			testnet_genesis(
+				wasm_binary,
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				dev_accounts().into_iter().map(|k| (k, 1_000_000 * UNIT)).collect(),
+				Default::default(),
+				astar_dev_parameters(100),
+				// Disable cheatcodes
+				false,
+			)
+		},
+	}
+	.build()
+}
+
+/// Chain resembling Shiden: `SDN` token, realistic account balances, Shiden's contracts deposits,
+/// fees and existential deposit, and a pre-created sufficient asset owned by Alice, as commonly
+/// used by contracts talking to pallet-assets.
+pub fn shiden_like_config() -> Result<ChainSpec, String> {
+	Preset {
+		name: "Shiden-like Development",
+		id: "shiden_like",
+		chain_type: ChainType::Local,
+		properties: token_properties(Some("SDN"), 18),
+		genesis: |wasm_binary| {
+			let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+			let assets = AssetsConfig {
+				assets: vec![(1, alice.clone(), true, 1)],
+				metadata: vec![(1, b"Test USD".to_vec(), b"TUSD".to_vec(), 6)],
+				accounts: dev_accounts()
+					.into_iter()
+					.map(|k| (1, k, 1_000_000 * 1_000_000))
+					.collect(),
+			};
+
+			testnet_genesis(
+				wasm_binary,
+				alice,
+				dev_accounts().into_iter().map(|k| (k, 100_000 * UNIT)).collect(),
+				assets,
+				astar_dev_parameters(1),
+				// Disable cheatcodes
+				false,
+			)
+		},
+	}
+	.build()
 }
 
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
 	root_key: AccountId,
-	endowed_accounts: Vec<AccountId>,
-	_enable_println: bool,
+	endowed_accounts: Vec<(AccountId, Balance)>,
+	assets: AssetsConfig,
+	dev_parameters: DevParametersConfig,
+	#[cfg_attr(not(feature = "dev-cheatcodes"), allow(unused_variables))] dev_chain: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
 		system: SystemConfig {
@@ -89,14 +364,20 @@ fn testnet_genesis(
 			code: wasm_binary.to_vec(),
 		},
 		balances: BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 100.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 100)).collect(),
+			// Configure endowed accounts with their initial balance.
+			balances: endowed_accounts,
 		},
 		sudo: SudoConfig {
 			// Assign network admin rights.
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
-		assets: Default::default(),
+		assets,
+		dev_parameters,
+		#[cfg(feature = "dev-cheatcodes")]
+		cheatcodes: CheatcodesConfig { dev_chain },
+		// Contracts to instantiate in genesis can be added to the `contractsDev` section of the
+		// output of `build-spec`.
+		contracts_dev: ContractsDevConfig { contracts: vec![] },
 	}
 }
@@ -1,4 +1,5 @@
 use sc_cli::RunCmd;
+use std::path::PathBuf;
 
 #[derive(Debug, clap::Parser)]
 pub struct Cli {
@@ -10,6 +11,25 @@ pub struct Cli {
 
 	#[clap(long)]
 	pub finalize_delay_sec: Option<u64>,
+
+	/// Print the debug messages of contract calls in imported blocks.
+	#[clap(long)]
+	pub print_contract_debug: bool,
+
+	/// Print a summary of every imported block: its extrinsics with their signer, call, result,
+	/// fee and weight, contract instantiations and contract events.
+	#[clap(long)]
+	pub print_blocks: bool,
+
+	/// Record the gas and storage deposit used by the contract messages executed on-chain,
+	/// reported with `dev_gasReport` and printed when the node stops.
+	#[clap(long)]
+	pub gas_report: bool,
+
+	/// Upload contracts again when their `.contract` or `.wasm` build artifacts in this directory
+	/// change, switching the contracts built from them to the new code in place.
+	#[clap(long, value_name = "DIR")]
+	pub watch_contracts: Option<PathBuf>,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -39,6 +59,10 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Call random sequences of messages of a contract bundle in an in-memory runtime, reporting
+	/// panics, traps and invariant violations.
+	Fuzz(crate::fuzz::FuzzCmd),
+
 	/// Sub-commands concerned with benchmarking.
 	#[cfg(feature = "frame-benchmarking")]
 	#[clap(subcommand)]
@@ -33,8 +33,12 @@ impl SubstrateCli for Cli {
 
 	fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
 		Ok(match id {
-			"dev" => Box::new(chain_spec::development_config()?),
-			_ => Box::new(chain_spec::development_config()?),
+			"" | "dev" => Box::new(chain_spec::development_config()?),
+			"local-persistent" => Box::new(chain_spec::local_persistent_config()?),
+			"astar-like" => Box::new(chain_spec::astar_like_config()?),
+			"shiden-like" => Box::new(chain_spec::shiden_like_config()?),
+			// Anything else is a path to a (plain or raw) chain spec, e.g. `build-spec` output.
+			path => Box::new(chain_spec::from_json_file(std::path::Path::new(path))?),
 		})
 	}
 
@@ -95,6 +99,7 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((cmd.run(client, backend, None), task_manager))
 			})
 		},
+		Some(Subcommand::Fuzz(cmd)) => cmd.run(),
 		#[cfg(feature = "frame-benchmarking")]
 		Some(Subcommand::Benchmark(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
@@ -157,7 +162,15 @@ pub fn run() -> sc_cli::Result<()> {
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
 			runner.run_node_until_exit(|config| async move {
-				service::new_full(config, cli.finalize_delay_sec).map_err(sc_cli::Error::Service)
+				service::new_full(
+					config,
+					cli.finalize_delay_sec,
+					cli.print_contract_debug,
+					cli.print_blocks,
+					cli.gas_report,
+					cli.watch_contracts,
+				)
+				.map_err(sc_cli::Error::Service)
 			})
 		},
 	}
@@ -0,0 +1,109 @@
+//! Removal of contracts and of their storage with `dev_killContract` and
+//! `dev_resetContractStorage`, for cleaning up contracts left in a broken state by tests.
+//!
+//! Both submit an unsigned extrinsic of the `ContractsDev` pallet, which only the local node may
+//! submit, and take effect in the next block.
+
+use std::sync::Arc;
+
+use futures::TryFutureExt;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_contracts_dev_rpc::{ContractsDevRuntimeApi, Error};
+use sc_transaction_pool_api::{TransactionPool, TransactionSource};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use swanky_runtime::{opaque::Block, AccountId, Balance, Hash};
+
+type Extrinsic = <Block as BlockT>::Extrinsic;
+
+/// RPC methods for removing contracts and their storage.
+#[rpc(server)]
+#[async_trait]
+pub trait ContractAdminApi {
+	/// Removes the contract at `address` as if it terminated itself: its storage is deleted, its
+	/// balance and the refund of its storage deposits are sent to `beneficiary` and its code is
+	/// no longer counted as used by it.
+	#[method(name = "dev_killContract")]
+	async fn kill_contract(&self, address: AccountId, beneficiary: AccountId) -> RpcResult<()>;
+
+	/// Clears the storage of the contract at `address`, keeping its code and balance.
+	#[method(name = "dev_resetContractStorage")]
+	async fn reset_contract_storage(&self, address: AccountId) -> RpcResult<()>;
+}
+
+/// Removes contracts and their storage on the chain of a client.
+pub struct ContractAdmin<C, P> {
+	client: Arc<C>,
+	pool: Arc<P>,
+}
+
+impl<C, P> ContractAdmin<C, P> {
+	pub fn new(client: Arc<C>, pool: Arc<P>) -> Self {
+		Self { client, pool }
+	}
+}
+
+fn error(code: Error, message: &str, data: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(code.into(), message, Some(data.to_string()))).into()
+}
+
+impl<C, P> ContractAdmin<C, P>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ContractsDevRuntimeApi<Block, AccountId, Balance>,
+	P: TransactionPool<Block = Block> + 'static,
+{
+	/// Builds the extrinsic changing the contract at `address` with `extrinsic` on the best block,
+	/// failing if there is no contract at the address.
+	fn extrinsic(
+		&self,
+		address: &AccountId,
+		extrinsic: impl FnOnce(&C::Api, Hash) -> Result<Extrinsic, sp_api::ApiError>,
+	) -> RpcResult<(Hash, Extrinsic)> {
+		let best_hash = self.client.info().best_hash;
+		let api = self.client.runtime_api();
+		api.code_hash(best_hash, address.clone())
+			.map_err(|e| error(Error::RuntimeError, "Unable to get code hash.", e))?
+			.ok_or_else(|| {
+				error(Error::ContractNotFound, "No contract at the address.", address)
+			})?;
+		let extrinsic = extrinsic(&api, best_hash)
+			.map_err(|e| error(Error::RuntimeError, "Unable to build extrinsic.", e))?;
+		Ok((best_hash, extrinsic))
+	}
+
+	async fn submit(&self, at: Hash, extrinsic: Extrinsic) -> RpcResult<()> {
+		self.pool
+			.submit_one(&BlockId::Hash(at), TransactionSource::Local, extrinsic)
+			.map_ok(|_| ())
+			.map_err(|e| error(Error::PoolError, "Unable to submit extrinsic.", e))
+			.await
+	}
+}
+
+#[async_trait]
+impl<C, P> ContractAdminApiServer for ContractAdmin<C, P>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ContractsDevRuntimeApi<Block, AccountId, Balance>,
+	P: TransactionPool<Block = Block> + 'static,
+{
+	async fn kill_contract(&self, address: AccountId, beneficiary: AccountId) -> RpcResult<()> {
+		let (at, extrinsic) = self.extrinsic(&address, |api, at| {
+			api.kill_contract_extrinsic(at, address.clone(), beneficiary)
+		})?;
+		self.submit(at, extrinsic).await
+	}
+
+	async fn reset_contract_storage(&self, address: AccountId) -> RpcResult<()> {
+		let (at, extrinsic) = self.extrinsic(&address, |api, at| {
+			api.reset_contract_storage_extrinsic(at, address.clone())
+		})?;
+		self.submit(at, extrinsic).await
+	}
+}
@@ -0,0 +1,176 @@
+//! Contracts deployed and code uploaded on the chain, listed with `contracts_list`,
+//! `contracts_codeList` and `contracts_info`.
+//!
+//! Contracts and code are read from the storage of the `contracts` pallet. The pallet doesn't
+//! keep the deployer of a contract, which is looked up in the event index instead.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_contracts_dev_rpc::{
+	CodeDetails, ContractDetails, ContractsDevRuntimeApi, Error, MetadataRegistry,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use swanky_runtime::{opaque::Block, AccountId, Balance, Hash};
+
+use crate::events::EventIndex;
+
+/// A contract deployed on the chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractInfo {
+	pub address: AccountId,
+	pub code_hash: Hash,
+	/// Name of the contract, if metadata is registered for its code.
+	pub name: Option<String>,
+	/// Account which instantiated the contract, unless it was instantiated in genesis. Only known
+	/// for the best block, as it's taken from the event index of the best chain.
+	pub deployer: Option<AccountId>,
+	/// Storage deposit held for the storage of the contract.
+	pub storage_deposit: Balance,
+	pub storage_items: u32,
+	/// Size of the storage of the contract, in bytes.
+	pub storage_bytes: u32,
+	pub balance: Balance,
+}
+
+/// Code uploaded to the chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeInfo {
+	pub code_hash: Hash,
+	/// Name of the contract, if metadata is registered for the code.
+	pub name: Option<String>,
+	/// Size of the code as uploaded, in bytes.
+	pub size: u32,
+	/// Whether the code may only be executed deterministically.
+	pub deterministic: bool,
+	/// Number of contracts running the code.
+	pub refcount: u64,
+	pub owner: AccountId,
+	/// Storage deposit held from the owner for the code.
+	pub deposit: Balance,
+}
+
+/// RPC methods for listing the contracts and code on the chain.
+#[rpc(server)]
+pub trait ContractRegistryApi {
+	/// Contracts deployed on the chain at block `at`.
+	///
+	/// Deployers are only returned at the best block, the default, and are `None` at other blocks.
+	#[method(name = "contracts_list")]
+	fn list(&self, at: Option<Hash>) -> RpcResult<Vec<ContractInfo>>;
+
+	/// Code uploaded to the chain at block `at`.
+	#[method(name = "contracts_codeList")]
+	fn code_list(&self, at: Option<Hash>) -> RpcResult<Vec<CodeInfo>>;
+
+	/// Contract deployed at `address` at block `at`.
+	///
+	/// The deployer is only returned at the best block, the default, and is `None` at other
+	/// blocks.
+	#[method(name = "contracts_info")]
+	fn info(&self, address: AccountId, at: Option<Hash>) -> RpcResult<Option<ContractInfo>>;
+}
+
+/// Lists the contracts and code on the chain of a client.
+pub struct ContractRegistry<C> {
+	client: Arc<C>,
+	events: EventIndex,
+	registry: MetadataRegistry<Hash>,
+}
+
+impl<C> ContractRegistry<C> {
+	pub fn new(client: Arc<C>, events: EventIndex, registry: MetadataRegistry<Hash>) -> Self {
+		Self { client, events, registry }
+	}
+
+	fn name(&self, code_hash: &Hash) -> Option<String> {
+		self.registry.get(code_hash).map(|metadata| metadata.name().to_string())
+	}
+
+	/// Info of the contract with `details`, with its deployer if `best` is set, as the event index
+	/// only holds the deployers of the contracts on the best chain.
+	fn contract_info(
+		&self,
+		details: ContractDetails<AccountId, Balance, Hash>,
+		best: bool,
+	) -> ContractInfo {
+		ContractInfo {
+			name: self.name(&details.code_hash),
+			deployer: best.then(|| self.events.deployer(&details.address)).flatten(),
+			address: details.address,
+			code_hash: details.code_hash,
+			storage_deposit: details.storage_deposit,
+			storage_items: details.storage_items,
+			storage_bytes: details.storage_bytes,
+			balance: details.balance,
+		}
+	}
+}
+
+fn runtime_error(message: &str, e: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(Error::RuntimeError.into(), message, Some(e.to_string())))
+		.into()
+}
+
+impl<C> ContractRegistryApiServer for ContractRegistry<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ContractsDevRuntimeApi<Block, AccountId, Balance>,
+{
+	fn list(&self, at: Option<Hash>) -> RpcResult<Vec<ContractInfo>> {
+		let best_hash = self.client.info().best_hash;
+		let at = at.unwrap_or(best_hash);
+		let contracts = self
+			.client
+			.runtime_api()
+			.contracts(at)
+			.map_err(|e| runtime_error("Unable to list contracts.", e))?;
+
+		Ok(contracts
+			.into_iter()
+			.map(|details| self.contract_info(details, at == best_hash))
+			.collect())
+	}
+
+	fn code_list(&self, at: Option<Hash>) -> RpcResult<Vec<CodeInfo>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let codes = self
+			.client
+			.runtime_api()
+			.codes(at)
+			.map_err(|e| runtime_error("Unable to list code.", e))?;
+
+		Ok(codes
+			.into_iter()
+			.map(|details: CodeDetails<AccountId, Balance, Hash>| CodeInfo {
+				name: self.name(&details.code_hash),
+				code_hash: details.code_hash,
+				size: details.size,
+				deterministic: details.deterministic,
+				refcount: details.refcount,
+				owner: details.owner,
+				deposit: details.deposit,
+			})
+			.collect())
+	}
+
+	fn info(&self, address: AccountId, at: Option<Hash>) -> RpcResult<Option<ContractInfo>> {
+		let best_hash = self.client.info().best_hash;
+		let at = at.unwrap_or(best_hash);
+		let details = self
+			.client
+			.runtime_api()
+			.contract_info(at, address)
+			.map_err(|e| runtime_error("Unable to get contract.", e))?;
+
+		Ok(details.map(|details| self.contract_info(details, at == best_hash)))
+	}
+}
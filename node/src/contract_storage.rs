@@ -0,0 +1,83 @@
+//! Storage of contracts decoded with `contracts_decodeStorage`, walking the storage layout of the
+//! registered ink! metadata of their code.
+
+use std::{collections::HashMap, sync::Arc};
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_contracts::ContractsApi;
+use pallet_contracts_dev_rpc::{ContractsDevRuntimeApi, Error, MetadataRegistry};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use swanky_runtime::{opaque::Block, AccountId, Balance, BlockNumber, EventRecord, Hash};
+
+/// RPC methods for decoding the storage of contracts.
+#[rpc(server)]
+pub trait ContractStorageApi {
+	/// Storage of the contract at `address` at block `at`, decoded with the metadata registered
+	/// for its code.
+	///
+	/// `mapping_keys` holds the SCALE encoded keys of the entries to read from each `Mapping`, by
+	/// the path of its field, e.g. `balances` or `inner.allowances`.
+	#[method(name = "contracts_decodeStorage")]
+	fn decode_storage(
+		&self,
+		address: AccountId,
+		mapping_keys: Option<HashMap<String, Vec<Bytes>>>,
+		at: Option<Hash>,
+	) -> RpcResult<serde_json::Value>;
+}
+
+/// Decodes the storage of contracts on the chain of a client.
+pub struct ContractStorage<C> {
+	client: Arc<C>,
+	registry: MetadataRegistry<Hash>,
+}
+
+impl<C> ContractStorage<C> {
+	pub fn new(client: Arc<C>, registry: MetadataRegistry<Hash>) -> Self {
+		Self { client, registry }
+	}
+}
+
+fn error(code: Error, message: &str, data: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(code.into(), message, Some(data.to_string()))).into()
+}
+
+impl<C> ContractStorageApiServer for ContractStorage<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ContractsDevRuntimeApi<Block, AccountId, Balance>,
+	C::Api: ContractsApi<Block, AccountId, Balance, BlockNumber, Hash, EventRecord>,
+{
+	fn decode_storage(
+		&self,
+		address: AccountId,
+		mapping_keys: Option<HashMap<String, Vec<Bytes>>>,
+		at: Option<Hash>,
+	) -> RpcResult<serde_json::Value> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let api = self.client.runtime_api();
+		let code_hash = api
+			.code_hash(at, address.clone())
+			.map_err(|e| error(Error::RuntimeError, "Unable to get code hash.", e))?
+			.ok_or_else(|| error(Error::InvalidStorage, "No contract at the address.", &address))?;
+		let metadata = self.registry.get(&code_hash).ok_or_else(|| {
+			let code_hash = format!("{:?}", code_hash);
+			error(Error::InvalidMetadata, "No metadata registered for the code.", code_hash)
+		})?;
+
+		let read = |key: &[u8]| {
+			api.get_storage(at, address.clone(), key.to_vec())
+				.map_err(|e| e.to_string())?
+				.map_err(|e| format!("{:?}", e))
+		};
+		metadata
+			.decode_storage(read, &mapping_keys.unwrap_or_default())
+			.map_err(|e| error(Error::InvalidStorage, "Unable to decode storage.", e))
+	}
+}
@@ -0,0 +1,345 @@
+//! Watch mode, enabled with `--watch-contracts <dir>`: contracts are upgraded in place when their
+//! build artifacts change.
+//!
+//! The directory is polled for `.contract` bundles and `.wasm` files, as written by
+//! `cargo contract build`. An artifact is known by its file name, so the bundle and the code of a
+//! contract count as one. When the code of an artifact changes, it's uploaded and the contracts
+//! running the code previously built from the artifact are switched to it, keeping their storage.
+//! The metadata of bundles is registered as well.
+//!
+//! The upgrade is only known to be done once its extrinsic is in a new best block: the contracts
+//! switched are logged from its `CodeUpgraded` events, and the new code becomes the one later
+//! changes are upgraded from if the extrinsic succeeded. Until then, changes of the artifact wait.
+//! An upgrade whose extrinsic isn't in any of the next [`UPGRADE_TIMEOUT`] best blocks, e.g.
+//! because the pool dropped it, is given up so that later changes are uploaded again.
+
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+	sync::Arc,
+	time::{Duration, SystemTime},
+};
+
+use frame_system::Phase;
+use futures::{FutureExt, StreamExt};
+use futures_timer::Delay;
+use pallet_contracts_dev_rpc::{ContractsDevRuntimeApi, MetadataRegistry};
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sc_transaction_pool_api::{TransactionPool, TransactionSource};
+use serde::Deserialize;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{sr25519, Bytes};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{BlakeTwo256, Block as BlockT, Hash as _, Header as _},
+};
+use swanky_runtime::{opaque::Block, AccountId, BlockNumber, Hash, RuntimeEvent};
+
+use crate::{chain_spec::get_account_id_from_seed, service::FullClient};
+
+/// Log target of watch mode.
+const LOG_TARGET: &str = "swanky::watch";
+
+/// Interval at which the directory is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Number of best blocks after which an upgrade whose extrinsic isn't in any of them is given up.
+const UPGRADE_TIMEOUT: BlockNumber = 10;
+
+/// Directory of the unoptimized code built by `cargo contract`, which isn't deployed.
+const BUILD_DIR: &str = "wasm32-unknown-unknown";
+
+#[derive(Deserialize)]
+struct Bundle {
+	source: BundleSource,
+}
+
+#[derive(Deserialize)]
+struct BundleSource {
+	wasm: Bytes,
+}
+
+/// Code of a contract read from an artifact, along with its metadata if it's a bundle.
+pub(crate) struct Artifact {
+	pub(crate) name: String,
+	pub(crate) code: Vec<u8>,
+	pub(crate) metadata: Option<serde_json::Value>,
+}
+
+impl Artifact {
+	pub(crate) fn read(path: &Path) -> Result<Self, String> {
+		let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+		let contents = fs::read(path).map_err(|e| e.to_string())?;
+		if path.extension().map_or(false, |extension| extension == "wasm") {
+			return Ok(Artifact { name, code: contents, metadata: None })
+		}
+		let metadata = serde_json::from_slice(&contents).map_err(|e| e.to_string())?;
+		Self::from_bundle(name, metadata)
+	}
+
+	/// Reads the code of the `.contract` bundle `bundle`, keeping the bundle as metadata.
+	pub(crate) fn from_bundle(name: String, bundle: serde_json::Value) -> Result<Self, String> {
+		let Bundle { source } = serde_json::from_value(bundle.clone())
+			.map_err(|e| format!("Not a contract bundle: {}", e))?;
+		Ok(Artifact { name, code: source.wasm.0, metadata: Some(bundle) })
+	}
+}
+
+/// Paths and modification times of the artifacts in `dir` and its subdirectories.
+fn scan(dir: &Path, artifacts: &mut Vec<(PathBuf, SystemTime)>) {
+	let Ok(entries) = fs::read_dir(dir) else { return };
+	for entry in entries.flatten() {
+		let path = entry.path();
+		let Ok(metadata) = entry.metadata() else { continue };
+		if metadata.is_dir() {
+			if path.file_name().map_or(true, |name| name != BUILD_DIR) {
+				scan(&path, artifacts);
+			}
+			continue
+		}
+		let is_artifact = path
+			.extension()
+			.map_or(false, |extension| extension == "contract" || extension == "wasm");
+		if let (true, Ok(modified)) = (is_artifact, metadata.modified()) {
+			artifacts.push((path, modified));
+		}
+	}
+}
+
+/// Upgrade submitted to the pool, whose extrinsic isn't in a best block yet.
+struct PendingUpgrade {
+	name: String,
+	code_hash: Hash,
+	/// Best block when the upgrade was submitted.
+	submitted_at: BlockNumber,
+}
+
+struct Watcher<P> {
+	client: Arc<FullClient>,
+	pool: Arc<P>,
+	registry: MetadataRegistry<Hash>,
+	/// Modification times of the artifacts, by path.
+	modified: HashMap<PathBuf, SystemTime>,
+	/// Hashes of the code the contracts were last switched to, by artifact name.
+	code_hashes: HashMap<String, Hash>,
+	/// Upgrades submitted, by extrinsic hash.
+	pending: HashMap<Hash, PendingUpgrade>,
+}
+
+impl<P: TransactionPool<Block = Block>> Watcher<P> {
+	/// Checks the artifacts in `dir`, upgrading the contracts of those which changed unless this
+	/// is the first check.
+	async fn check(&mut self, dir: &Path, first: bool) {
+		let mut artifacts = Vec::new();
+		scan(dir, &mut artifacts);
+		for (path, modified) in artifacts {
+			if self.modified.get(&path) == Some(&modified) {
+				continue
+			}
+			// Artifacts which can't be read yet, e.g. while they are written, are read again on
+			// the next check.
+			let artifact = match Artifact::read(&path) {
+				Ok(artifact) => artifact,
+				Err(e) => {
+					log::debug!(target: LOG_TARGET, "Unable to read {}: {}", path.display(), e);
+					continue
+				},
+			};
+			// The upgrade from the previous change must be done first, to know which contracts
+			// to switch.
+			if self.pending.values().any(|upgrade| upgrade.name == artifact.name) {
+				continue
+			}
+			self.modified.insert(path, modified);
+
+			if let Some(metadata) = artifact.metadata.clone() {
+				if let Err(e) = self.registry.register(metadata) {
+					log::warn!(target: LOG_TARGET, "Unable to register {}: {}", artifact.name, e);
+				}
+			}
+			let code_hash = BlakeTwo256::hash(&artifact.code);
+			if first {
+				self.code_hashes.insert(artifact.name, code_hash);
+				continue
+			}
+			let old_code_hash = self.code_hashes.get(&artifact.name).copied();
+			if old_code_hash == Some(code_hash) {
+				continue
+			}
+			if let Err(e) = self.upgrade(artifact, old_code_hash, code_hash).await {
+				log::warn!(target: LOG_TARGET, "Unable to upgrade contracts: {}", e);
+			}
+		}
+	}
+
+	/// Extrinsic uploading `code` and switching the contracts running `old_code_hash` to it.
+	fn upgrade_extrinsic(
+		&self,
+		code: Vec<u8>,
+		old_code_hash: Option<Hash>,
+	) -> Result<<Block as BlockT>::Extrinsic, String> {
+		let best_hash = self.client.info().best_hash;
+		let api = self.client.runtime_api();
+		let contracts: Vec<AccountId> = match old_code_hash {
+			Some(old_code_hash) => api
+				.contracts(best_hash)
+				.map_err(|e| e.to_string())?
+				.into_iter()
+				.filter(|contract| contract.code_hash == old_code_hash)
+				.map(|contract| contract.address)
+				.collect(),
+			None => Vec::new(),
+		};
+		// The owner of the previous code pays the deposit of the new one.
+		let owner = api
+			.codes(best_hash)
+			.map_err(|e| e.to_string())?
+			.into_iter()
+			.find(|code| Some(code.code_hash) == old_code_hash)
+			.map(|code| code.owner)
+			.unwrap_or_else(|| get_account_id_from_seed::<sr25519::Public>("Alice"));
+
+		api.upgrade_code_extrinsic(best_hash, owner, code, contracts)
+			.map_err(|e| e.to_string())
+	}
+
+	/// Submits the extrinsic uploading the code of `artifact` and switching the contracts running
+	/// `old_code_hash` to it.
+	async fn upgrade(
+		&mut self,
+		artifact: Artifact,
+		old_code_hash: Option<Hash>,
+		code_hash: Hash,
+	) -> Result<(), String> {
+		let extrinsic = self.upgrade_extrinsic(artifact.code, old_code_hash)?;
+		let extrinsic_hash = BlakeTwo256::hash_of(&extrinsic);
+		let info = self.client.info();
+		self.pool
+			.submit_one(&BlockId::Hash(info.best_hash), TransactionSource::Local, extrinsic)
+			.await
+			.map_err(|e| e.to_string())?;
+
+		log::info!(target: LOG_TARGET, "Uploading {} as {:?}", artifact.name, code_hash);
+		let upgrade =
+			PendingUpgrade { name: artifact.name, code_hash, submitted_at: info.best_number };
+		self.pending.insert(extrinsic_hash, upgrade);
+		Ok(())
+	}
+
+	/// Completes the pending upgrades whose extrinsics are in the new best block `hash`, logging
+	/// the contracts they switched.
+	fn imported(&mut self, hash: Hash) -> Result<(), String> {
+		if self.pending.is_empty() {
+			return Ok(())
+		}
+		let extrinsics = self
+			.client
+			.block_body(hash)
+			.map_err(|e| e.to_string())?
+			.ok_or_else(|| format!("Unknown block body {:?}", hash))?;
+		let events = crate::events::block_events(&self.client, hash)?;
+
+		for (index, extrinsic) in extrinsics.iter().enumerate() {
+			let Some(upgrade) = self.pending.remove(&BlakeTwo256::hash_of(extrinsic)) else {
+				continue
+			};
+			let mut succeeded = false;
+			for record in &events {
+				if record.phase != Phase::ApplyExtrinsic(index as u32) {
+					continue
+				}
+				match &record.event {
+					RuntimeEvent::ContractsDev(pallet_contracts_dev::Event::CodeUpgraded {
+						contract,
+						old_code_hash,
+						new_code_hash,
+					}) => log::info!(
+						target: LOG_TARGET,
+						"Switched {} at {} from {:?} to {:?}",
+						upgrade.name,
+						contract,
+						old_code_hash,
+						new_code_hash
+					),
+					RuntimeEvent::System(frame_system::Event::ExtrinsicSuccess { .. }) =>
+						succeeded = true,
+					_ => {},
+				}
+			}
+			if succeeded {
+				self.code_hashes.insert(upgrade.name, upgrade.code_hash);
+			} else {
+				log::warn!(
+					target: LOG_TARGET,
+					"Unable to upgrade {}: the extrinsic failed",
+					upgrade.name
+				);
+			}
+		}
+		Ok(())
+	}
+
+	/// Gives up the pending upgrades submitted more than [`UPGRADE_TIMEOUT`] blocks before the
+	/// new best block `number`.
+	fn expire(&mut self, number: BlockNumber) {
+		self.pending.retain(|_, upgrade| {
+			if number.saturating_sub(upgrade.submitted_at) <= UPGRADE_TIMEOUT {
+				return true
+			}
+			log::warn!(
+				target: LOG_TARGET,
+				"Gave up upgrading {}: the extrinsic isn't in the {} best blocks since its \
+				 submission, the next change is uploaded again",
+				upgrade.name,
+				UPGRADE_TIMEOUT
+			);
+			false
+		});
+	}
+}
+
+/// Polls `dir` for changed artifacts, upgrading the contracts built from them.
+pub async fn watch_contracts<P: TransactionPool<Block = Block>>(
+	dir: PathBuf,
+	client: Arc<FullClient>,
+	pool: Arc<P>,
+	registry: MetadataRegistry<Hash>,
+) {
+	log::info!(target: LOG_TARGET, "Watching contracts in {}", dir.display());
+	let mut imported = client.import_notification_stream().fuse();
+	let mut watcher = Watcher {
+		client,
+		pool,
+		registry,
+		modified: HashMap::new(),
+		code_hashes: HashMap::new(),
+		pending: HashMap::new(),
+	};
+	watcher.check(&dir, true).await;
+	let mut poll = Delay::new(POLL_INTERVAL).fuse();
+	loop {
+		futures::select! {
+			notification = imported.next() => match notification {
+				Some(notification) if notification.is_new_best => {
+					if let Err(e) = watcher.imported(notification.hash) {
+						log::warn!(
+							target: LOG_TARGET,
+							"Unable to check upgrades in block {:?}: {}",
+							notification.hash,
+							e
+						);
+					}
+					watcher.expire(*notification.header.number());
+				},
+				Some(_) => {},
+				None => return,
+			},
+			() = poll => {
+				watcher.check(&dir, false).await;
+				poll = Delay::new(POLL_INTERVAL).fuse();
+			},
+		}
+	}
+}
@@ -0,0 +1,565 @@
+//! Index of the events deposited in the blocks of the chain, queried and subscribed to with
+//! `dev_getEvents` and `dev_subscribeEvents`.
+//!
+//! The index is kept in memory. It's filled with the events of the existing blocks when the node
+//! starts, then with those of every new best block. Events of blocks replaced by a new best block,
+//! e.g. after reverting blocks or switching to a longer fork, are dropped, and only the events of
+//! the last [`RETAINED_BLOCKS`] blocks are kept. Older blocks are pruned [`PRUNE_INTERVAL`] blocks
+//! at a time, as pruning rebuilds the lookup tables of the index.
+
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::{Arc, Mutex, RwLock},
+};
+
+use codec::{Decode, Encode};
+use futures::{
+	channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
+	FutureExt, StreamExt,
+};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::SubscriptionResult, SubscriptionSink};
+use pallet_contracts_dev_rpc::{ContractsDevRuntimeApi, DecodedEvent, MetadataRegistry};
+use sc_client_api::{BlockBackend, BlockchainEvents, StorageProvider};
+use sc_rpc::SubscriptionTaskExecutor;
+use scale_info::{form::PortableForm, MetaType, PortableRegistry, Registry, TypeDef, Variant};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{storage::StorageKey, Bytes};
+use sp_runtime::traits::Header as _;
+use swanky_runtime::{AccountId, BlockNumber, EventRecord, Hash, RuntimeEvent};
+
+use crate::service::FullClient;
+
+/// Log target of the event index.
+const LOG_TARGET: &str = "swanky::events";
+
+/// Number of blocks whose events are kept, counting back from the best block.
+pub const RETAINED_BLOCKS: BlockNumber = 10_000;
+
+/// Number of blocks older than the retained ones after which they are pruned.
+const PRUNE_INTERVAL: BlockNumber = RETAINED_BLOCKS / 10;
+
+/// An event deposited in a block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedEvent {
+	pub block_number: BlockNumber,
+	pub block_hash: Hash,
+	/// Index of the extrinsic which deposited the event, if it was deposited by one.
+	pub extrinsic_index: Option<u32>,
+	/// Index of the event in the block.
+	pub event_index: u32,
+	pub pallet: String,
+	pub event: String,
+	/// Contract the event is about, for events of the contracts pallet.
+	pub contract: Option<AccountId>,
+	pub topics: Vec<Hash>,
+	/// SCALE encoded `RuntimeEvent`.
+	pub data: Bytes,
+	/// Event emitted by a contract, decoded with the registered metadata of its code.
+	pub decoded: Option<DecodedEvent>,
+	/// Code hash and data of an event emitted by a contract.
+	#[serde(skip)]
+	emitted: Option<(Hash, Vec<u8>)>,
+	/// Deployer of the contract, for `Instantiated` events.
+	#[serde(skip)]
+	deployer: Option<AccountId>,
+}
+
+/// Criteria events must all match. Unset criteria match every event.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct EventFilter {
+	/// Name of the pallet, as in `construct_runtime!`.
+	pub pallet: Option<String>,
+	/// Name of the event variant.
+	pub event: Option<String>,
+	pub contract: Option<AccountId>,
+	/// Topics the event must all have.
+	#[serde(default)]
+	pub topics: Vec<Hash>,
+	pub from_block: Option<BlockNumber>,
+	pub to_block: Option<BlockNumber>,
+}
+
+impl EventFilter {
+	fn matches(&self, event: &IndexedEvent) -> bool {
+		self.pallet.as_ref().map_or(true, |pallet| *pallet == event.pallet) &&
+			self.event.as_ref().map_or(true, |name| *name == event.event) &&
+			(self.contract.is_none() || self.contract == event.contract) &&
+			self.topics.iter().all(|topic| event.topics.contains(topic)) &&
+			self.from_block.map_or(true, |from| event.block_number >= from) &&
+			self.to_block.map_or(true, |to| event.block_number <= to)
+	}
+}
+
+/// Events of the retained blocks, along with their positions by contract and by pallet and event.
+///
+/// Positions count the events indexed since the node started, the pruned ones included.
+#[derive(Default)]
+struct Indexed {
+	/// Events in the order they were deposited, the first one at position `pruned`.
+	events: VecDeque<IndexedEvent>,
+	pruned: usize,
+	/// Blocks before this one are pruned.
+	pruned_before: BlockNumber,
+	by_contract: HashMap<AccountId, Vec<usize>>,
+	by_pallet: HashMap<String, Vec<usize>>,
+	by_event: HashMap<(String, String), Vec<usize>>,
+	/// Deployers of the contracts instantiated in the indexed blocks, by contract address. Kept
+	/// when their blocks are pruned.
+	deployers: HashMap<AccountId, AccountId>,
+}
+
+impl Indexed {
+	fn end(&self) -> usize {
+		self.pruned + self.events.len()
+	}
+
+	/// Position of the first event deposited in block `number` or a later one.
+	fn position(&self, number: BlockNumber) -> usize {
+		self.pruned + self.events.partition_point(|event| event.block_number < number)
+	}
+
+	/// Positions in `positions` which are within `start..end`.
+	fn within(positions: &[usize], start: usize, end: usize) -> &[usize] {
+		let first = positions.partition_point(|position| *position < start);
+		let last = positions.partition_point(|position| *position < end);
+		&positions[first..last]
+	}
+
+	/// Drops the positions outside of `start..end` from the lookup tables.
+	fn retain_positions(&mut self, start: usize, end: usize) {
+		fn retain<K>(table: &mut HashMap<K, Vec<usize>>, start: usize, end: usize) {
+			table.retain(|_, positions| {
+				*positions = Indexed::within(positions, start, end).to_vec();
+				!positions.is_empty()
+			});
+		}
+		retain(&mut self.by_contract, start, end);
+		retain(&mut self.by_pallet, start, end);
+		retain(&mut self.by_event, start, end);
+	}
+
+	fn push(&mut self, event: IndexedEvent) {
+		let position = self.end();
+		if let Some(contract) = &event.contract {
+			self.by_contract.entry(contract.clone()).or_default().push(position);
+			if let Some(deployer) = &event.deployer {
+				self.deployers.insert(contract.clone(), deployer.clone());
+			}
+		}
+		self.by_pallet.entry(event.pallet.clone()).or_default().push(position);
+		self.by_event
+			.entry((event.pallet.clone(), event.event.clone()))
+			.or_default()
+			.push(position);
+		self.events.push_back(event);
+	}
+
+	/// Drops the events of the blocks from `number` on.
+	fn truncate(&mut self, number: BlockNumber) {
+		let first = self.position(number);
+		if first == self.end() {
+			return
+		}
+		for event in self.events.drain(first - self.pruned..) {
+			if let (Some(contract), Some(_)) = (&event.contract, &event.deployer) {
+				self.deployers.remove(contract);
+			}
+		}
+		self.retain_positions(self.pruned, first);
+	}
+
+	/// Drops the events of the blocks before `number`, once there are [`PRUNE_INTERVAL`] blocks to
+	/// drop.
+	fn prune(&mut self, number: BlockNumber) {
+		if number < self.pruned_before.saturating_add(PRUNE_INTERVAL) {
+			return
+		}
+		self.pruned_before = number;
+		let first = self.position(number);
+		self.events.drain(..first - self.pruned);
+		self.pruned = first;
+		self.retain_positions(first, self.end());
+	}
+
+	fn query(&self, filter: &EventFilter) -> Vec<IndexedEvent> {
+		let start = filter.from_block.map_or(self.pruned, |from| self.position(from));
+		let end = filter.to_block.map_or(self.end(), |to| self.position(to.saturating_add(1)));
+		if start >= end {
+			return Vec::new()
+		}
+		let positions = match (&filter.contract, &filter.pallet, &filter.event) {
+			(Some(contract), ..) => self.by_contract.get(contract),
+			(None, Some(pallet), Some(event)) =>
+				self.by_event.get(&(pallet.clone(), event.clone())),
+			(None, Some(pallet), None) => self.by_pallet.get(pallet),
+			(None, None, _) =>
+				return self
+					.events
+					.range(start - self.pruned..end - self.pruned)
+					.filter(|event| filter.matches(event))
+					.cloned()
+					.collect(),
+		};
+		let Some(positions) = positions else { return Vec::new() };
+		Self::within(positions, start, end)
+			.iter()
+			.map(|position| &self.events[position - self.pruned])
+			.filter(|event| filter.matches(event))
+			.cloned()
+			.collect()
+	}
+}
+
+/// Events of the chain, shared by the indexing task and the RPC.
+#[derive(Clone, Default)]
+pub struct EventIndex {
+	indexed: Arc<RwLock<Indexed>>,
+	subscribers: Arc<Mutex<Vec<UnboundedSender<Vec<IndexedEvent>>>>>,
+}
+
+impl EventIndex {
+	/// Replaces the events of the blocks from `number` on with `events`, deposited in `number`,
+	/// and prunes the blocks which aren't retained anymore.
+	fn insert(&self, number: BlockNumber, events: Vec<IndexedEvent>) {
+		let mut indexed = self.indexed.write().expect("lock is not poisoned; qed");
+		indexed.truncate(number);
+		events.iter().cloned().for_each(|event| indexed.push(event));
+		indexed.prune((number + 1).saturating_sub(RETAINED_BLOCKS));
+		drop(indexed);
+
+		let mut subscribers = self.subscribers.lock().expect("lock is not poisoned; qed");
+		subscribers.retain(|subscriber| subscriber.unbounded_send(events.clone()).is_ok());
+	}
+
+	fn query(&self, filter: &EventFilter) -> Vec<IndexedEvent> {
+		self.indexed.read().expect("lock is not poisoned; qed").query(filter)
+	}
+
+	/// Deployer of the contract at `address`, if it was instantiated in an indexed block.
+	pub(crate) fn deployer(&self, address: &AccountId) -> Option<AccountId> {
+		let indexed = self.indexed.read().expect("lock is not poisoned; qed");
+		indexed.deployers.get(address).cloned()
+	}
+
+	/// Stream of the events of every new best block.
+	fn subscribe(&self) -> UnboundedReceiver<Vec<IndexedEvent>> {
+		let (sender, receiver) = unbounded();
+		self.subscribers.lock().expect("lock is not poisoned; qed").push(sender);
+		receiver
+	}
+}
+
+/// Events deposited in block `hash`.
+pub(crate) fn block_events(client: &FullClient, hash: Hash) -> Result<Vec<EventRecord>, String> {
+	let key = StorageKey(frame_support::storage::storage_prefix(b"System", b"Events").to_vec());
+	match client.storage(hash, &key).map_err(|e| e.to_string())? {
+		Some(data) => Vec::<EventRecord>::decode(&mut &data.0[..]).map_err(|e| e.to_string()),
+		None => Ok(Vec::new()),
+	}
+}
+
+/// Names of the pallets and their events, from the type information of `RuntimeEvent`.
+struct EventNames {
+	registry: PortableRegistry,
+	runtime_event: u32,
+}
+
+impl EventNames {
+	fn new() -> Self {
+		let mut registry = Registry::new();
+		let runtime_event = registry.register_type(&MetaType::new::<RuntimeEvent>()).id;
+		EventNames { registry: registry.into(), runtime_event }
+	}
+
+	fn variant(&self, id: u32, index: u8) -> Option<&Variant<PortableForm>> {
+		match &self.registry.resolve(id)?.type_def {
+			TypeDef::Variant(variants) => variants.variants.iter().find(|v| v.index == index),
+			_ => None,
+		}
+	}
+
+	/// Names of the pallet and the event of a SCALE encoded `RuntimeEvent`.
+	fn of(&self, encoded: &[u8]) -> Option<(String, String)> {
+		let [pallet_index, event_index, ..] = encoded else { return None };
+		let pallet = self.variant(self.runtime_event, *pallet_index)?;
+		let event = self.variant(pallet.fields.first()?.ty.id, *event_index)?;
+		Some((pallet.name.clone(), event.name.clone()))
+	}
+}
+
+/// Contract an event of the contracts pallet is about.
+fn contract_of(event: &RuntimeEvent) -> Option<&AccountId> {
+	use pallet_contracts::Event::*;
+	match event {
+		RuntimeEvent::Contracts(
+			Instantiated { contract, .. } |
+			Terminated { contract, .. } |
+			ContractEmitted { contract, .. } |
+			ContractCodeUpdated { contract, .. } |
+			Called { contract, .. } |
+			DelegateCalled { contract, .. },
+		) => Some(contract),
+		_ => None,
+	}
+}
+
+fn index_block(
+	client: &FullClient,
+	names: &EventNames,
+	index: &EventIndex,
+	hash: Hash,
+	number: BlockNumber,
+) -> Result<(), String> {
+	let events = block_events(client, hash)?
+		.into_iter()
+		.enumerate()
+		.map(|(event_index, record)| {
+			let data = record.event.encode();
+			let (pallet, event) = names.of(&data).unwrap_or_default();
+			let emitted = match &record.event {
+				RuntimeEvent::Contracts(pallet_contracts::Event::ContractEmitted {
+					contract,
+					data,
+				}) => client
+					.runtime_api()
+					.code_hash(hash, contract.clone())
+					.ok()
+					.flatten()
+					.map(|code_hash| (code_hash, data.clone())),
+				_ => None,
+			};
+			IndexedEvent {
+				block_number: number,
+				block_hash: hash,
+				extrinsic_index: match record.phase {
+					frame_system::Phase::ApplyExtrinsic(extrinsic) => Some(extrinsic),
+					_ => None,
+				},
+				event_index: event_index as u32,
+				pallet,
+				event,
+				contract: contract_of(&record.event).cloned(),
+				topics: record.topics,
+				data: data.into(),
+				decoded: None,
+				emitted,
+				deployer: match &record.event {
+					RuntimeEvent::Contracts(pallet_contracts::Event::Instantiated {
+						deployer,
+						..
+					}) => Some(deployer.clone()),
+					_ => None,
+				},
+			}
+		})
+		.collect();
+	index.insert(number, events);
+	Ok(())
+}
+
+/// Indexes the events of the existing blocks, then of every new best block.
+pub async fn index_events(client: Arc<FullClient>, index: EventIndex) {
+	let names = EventNames::new();
+	// Subscribed first not to miss blocks imported while indexing the existing ones.
+	let mut imported = client.import_notification_stream();
+
+	let existing = (0..=client.info().best_number)
+		.map_while(|number| client.hash(number).ok().flatten().map(|hash| (hash, number)));
+	for (hash, number) in existing {
+		if let Err(e) = index_block(&client, &names, &index, hash, number) {
+			log::warn!(target: LOG_TARGET, "Unable to index events of block {:?}: {}", hash, e);
+		}
+	}
+
+	while let Some(notification) = imported.next().await {
+		if !notification.is_new_best {
+			continue
+		}
+		// The new best block is on another fork: the blocks of the fork it's built on replace
+		// those of the previous best chain after their common ancestor.
+		let enacted = notification.tree_route.iter().flat_map(|route| route.enacted().to_vec());
+		let blocks = enacted
+			.map(|block| (block.hash, block.number))
+			.chain([(notification.hash, *notification.header.number())]);
+		for (hash, number) in blocks {
+			if let Err(e) = index_block(&client, &names, &index, hash, number) {
+				log::warn!(target: LOG_TARGET, "Unable to index events of block {:?}: {}", hash, e);
+			}
+		}
+	}
+}
+
+/// RPC methods for querying the events of the chain.
+#[rpc(server)]
+pub trait EventsApi {
+	/// Events matching `filter`, in the order they were deposited.
+	#[method(name = "dev_getEvents")]
+	fn get_events(&self, filter: EventFilter) -> RpcResult<Vec<IndexedEvent>>;
+
+	/// Events matching `filter` deposited in the new best blocks.
+	#[subscription(
+		name = "dev_subscribeEvents" => "dev_events",
+		unsubscribe = "dev_unsubscribeEvents",
+		item = IndexedEvent
+	)]
+	fn subscribe_events(&self, filter: EventFilter);
+}
+
+/// Serves the events of an [`EventIndex`].
+pub struct Events {
+	index: EventIndex,
+	registry: MetadataRegistry<Hash>,
+	executor: SubscriptionTaskExecutor,
+}
+
+impl Events {
+	pub fn new(
+		index: EventIndex,
+		registry: MetadataRegistry<Hash>,
+		executor: SubscriptionTaskExecutor,
+	) -> Self {
+		Self { index, registry, executor }
+	}
+}
+
+/// Decodes the event emitted by a contract with the metadata in `registry`.
+fn decode_emitted(mut event: IndexedEvent, registry: &MetadataRegistry<Hash>) -> IndexedEvent {
+	event.decoded = event.emitted.as_ref().and_then(|(code_hash, data)| {
+		registry.get(code_hash).and_then(|metadata| metadata.decode_event(data).ok())
+	});
+	event
+}
+
+impl EventsApiServer for Events {
+	fn get_events(&self, filter: EventFilter) -> RpcResult<Vec<IndexedEvent>> {
+		let events = self.index.query(&filter);
+		Ok(events.into_iter().map(|event| decode_emitted(event, &self.registry)).collect())
+	}
+
+	fn subscribe_events(
+		&self,
+		mut sink: SubscriptionSink,
+		filter: EventFilter,
+	) -> SubscriptionResult {
+		let registry = self.registry.clone();
+		let stream = self.index.subscribe().flat_map(move |events| {
+			let events = events
+				.into_iter()
+				.filter(|event| filter.matches(event))
+				.map(|event| decode_emitted(event, &registry))
+				.collect::<Vec<_>>();
+			futures::stream::iter(events)
+		});
+
+		let fut = async move {
+			let _ = sink.pipe_from_stream(stream).await;
+		};
+		self.executor.spawn("dev-events-subscription", Some("rpc"), fut.boxed());
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_keyring::AccountKeyring::{Alice, Bob, Charlie};
+
+	fn event(block_number: BlockNumber, event: &str, contract: Option<AccountId>) -> IndexedEvent {
+		IndexedEvent {
+			block_number,
+			block_hash: Hash::repeat_byte(block_number as u8),
+			extrinsic_index: None,
+			event_index: 0,
+			pallet: "Contracts".to_string(),
+			event: event.to_string(),
+			deployer: (event == "Instantiated").then(|| Alice.to_account_id()),
+			contract,
+			topics: Vec::new(),
+			data: Bytes(Vec::new()),
+			decoded: None,
+			emitted: None,
+		}
+	}
+
+	fn blocks(events: &[IndexedEvent]) -> Vec<BlockNumber> {
+		events.iter().map(|event| event.block_number).collect()
+	}
+
+	#[test]
+	fn queries_by_block_range_contract_and_event() {
+		let (bob, charlie) = (Bob.to_account_id(), Charlie.to_account_id());
+		let index = EventIndex::default();
+		index.insert(1, vec![event(1, "Instantiated", Some(bob.clone()))]);
+		index.insert(2, vec![event(2, "Called", Some(charlie.clone())), event(2, "Called", None)]);
+		index.insert(3, vec![event(3, "Called", Some(bob.clone()))]);
+
+		let filter = |filter: EventFilter| blocks(&index.query(&filter));
+		assert_eq!(filter(EventFilter::default()), vec![1, 2, 2, 3]);
+		assert_eq!(
+			filter(EventFilter { from_block: Some(2), ..Default::default() }),
+			vec![2, 2, 3]
+		);
+		assert_eq!(filter(EventFilter { to_block: Some(1), ..Default::default() }), vec![1]);
+		assert_eq!(
+			filter(EventFilter { from_block: Some(3), to_block: Some(2), ..Default::default() }),
+			Vec::<BlockNumber>::new()
+		);
+		assert_eq!(filter(EventFilter { contract: Some(bob), ..Default::default() }), vec![1, 3]);
+		assert_eq!(
+			filter(EventFilter {
+				pallet: Some("Contracts".to_string()),
+				event: Some("Called".to_string()),
+				to_block: Some(2),
+				..Default::default()
+			}),
+			vec![2, 2]
+		);
+		assert!(filter(EventFilter { pallet: Some("Balances".to_string()), ..Default::default() })
+			.is_empty());
+	}
+
+	#[test]
+	fn replaced_blocks_are_dropped() {
+		let bob = Bob.to_account_id();
+		let index = EventIndex::default();
+		index.insert(1, vec![event(1, "Called", None)]);
+		index.insert(2, vec![event(2, "Instantiated", Some(bob.clone()))]);
+		index.insert(3, vec![event(3, "Called", Some(bob.clone()))]);
+		assert_eq!(index.deployer(&bob), Some(Alice.to_account_id()));
+
+		index.insert(2, vec![event(2, "Called", None)]);
+
+		assert_eq!(blocks(&index.query(&EventFilter::default())), vec![1, 2]);
+		assert!(index
+			.query(&EventFilter { contract: Some(bob.clone()), ..Default::default() })
+			.is_empty());
+		assert_eq!(index.deployer(&bob), None);
+	}
+
+	#[test]
+	fn old_blocks_are_pruned_but_deployers_kept() {
+		let bob = Bob.to_account_id();
+		let index = EventIndex::default();
+		let filter = EventFilter { contract: Some(bob.clone()), ..Default::default() };
+		index.insert(1, vec![event(1, "Instantiated", Some(bob.clone()))]);
+		index.insert(PRUNE_INTERVAL, vec![event(PRUNE_INTERVAL, "Called", Some(bob.clone()))]);
+
+		// Block 1 is out of the retained blocks, but pruned along with the next ones.
+		let number = RETAINED_BLOCKS + 1;
+		index.insert(number, vec![event(number, "Called", Some(bob.clone()))]);
+		assert_eq!(blocks(&index.query(&filter)), vec![1, PRUNE_INTERVAL, number]);
+
+		let number = RETAINED_BLOCKS + PRUNE_INTERVAL - 1;
+		index.insert(number, vec![event(number, "Called", Some(bob.clone()))]);
+		assert_eq!(
+			blocks(&index.query(&filter)),
+			vec![PRUNE_INTERVAL, RETAINED_BLOCKS + 1, number]
+		);
+		assert_eq!(index.deployer(&bob), Some(Alice.to_account_id()));
+	}
+}
@@ -0,0 +1,714 @@
+//! `swanky-node fuzz`, calling random sequences of messages of a contract in an in-memory instance
+//! of the runtime to find calls which make it panic or trap and invariants which don't hold.
+//!
+//! Each run starts from the genesis state of the development chain, instantiates the contract as
+//! Alice and calls random messages as random development accounts, with arguments generated from
+//! the types of the contract's metadata. A failing sequence is minimised by dropping the calls
+//! which aren't needed for it to fail the same way. Nothing is run over RPC and no node is started.
+
+use std::{
+	fmt,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+use codec::{Compact, Encode};
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	weights::Weight,
+};
+use pallet_contracts::{CollectEvents, DebugInfo, Determinism};
+use pallet_contracts_dev_rpc::{ContractMetadata, MessageInfo, MetadataRegistry};
+use pallet_contracts_primitives::Code;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde_json::Value;
+use sp_core::{hexdisplay::HexDisplay, sr25519, storage::Storage};
+use sp_runtime::DispatchError;
+use swanky_runtime::{
+	AccountId, BuildStorage, Contracts, Hash, Runtime, RuntimeBlockWeights, System,
+};
+
+use crate::{
+	chain_spec::{self, get_account_id_from_seed},
+	contract_watcher::Artifact,
+	invariants::{message_result, Predicate},
+};
+
+/// Development accounts calling the contract, the first one instantiating it.
+const ACCOUNTS: [&str; 6] = ["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+
+/// Number of times the contract is instantiated with other constructor arguments before giving up.
+const INSTANTIATE_ATTEMPTS: usize = 16;
+
+/// Nesting of generated values past which sequences are empty and variants without fields are
+/// preferred, so that values of recursive types end.
+const MAX_DEPTH: u32 = 8;
+
+/// Maximum length of generated sequences and strings.
+const MAX_LEN: usize = 8;
+
+/// Parses `<MESSAGE>=<PREDICATE>`, the predicate being the JSON of a [`Predicate`].
+fn parse_invariant(invariant: &str) -> Result<(String, Predicate), String> {
+	let (message, predicate) = invariant.split_once('=').ok_or("expected <MESSAGE>=<PREDICATE>")?;
+	let predicate =
+		serde_json::from_str(predicate).map_err(|e| format!("invalid predicate: {}", e))?;
+	Ok((message.to_string(), predicate))
+}
+
+/// The `fuzz` subcommand.
+#[derive(Debug, clap::Parser)]
+pub struct FuzzCmd {
+	/// `.contract` bundle of the contract, as built by `cargo contract build`.
+	#[clap(value_name = "BUNDLE")]
+	pub bundle: PathBuf,
+
+	/// Label of the constructor instantiating the contract, by default its first one.
+	#[clap(long, value_name = "LABEL")]
+	pub constructor: Option<String>,
+
+	/// Number of sequences of calls.
+	#[clap(long, default_value_t = 100)]
+	pub runs: u32,
+
+	/// Number of calls in a sequence.
+	#[clap(long, default_value_t = 20)]
+	pub depth: u32,
+
+	/// Seed of the random sequences, to replay the sequences of a previous run of the command.
+	#[clap(long)]
+	pub seed: Option<u64>,
+
+	/// Message without arguments whose return value must satisfy a predicate after every call,
+	/// given as `<MESSAGE>=<PREDICATE>`, e.g. `total_supply={"le":"1000000"}` or
+	/// `get="succeeds"`. May be given several times.
+	#[clap(long = "invariant", value_name = "MESSAGE=PREDICATE", value_parser = parse_invariant)]
+	pub invariants: Vec<(String, Predicate)>,
+}
+
+/// How a sequence of calls failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Failure {
+	/// The contract panicked, with the message it printed.
+	Panicked(String),
+	/// The contract trapped without panicking, e.g. on `unreachable` or a stack overflow.
+	Trapped,
+	/// The message of an invariant returned a value not satisfying its predicate, or failed.
+	Violated {
+		invariant: String,
+		expected: Predicate,
+		actual: Option<Value>,
+		error: Option<String>,
+	},
+}
+
+impl Failure {
+	/// Whether both failures are the same bug, whatever values were involved.
+	fn same_as(&self, other: &Failure) -> bool {
+		match (self, other) {
+			(Failure::Panicked(a), Failure::Panicked(b)) => a == b,
+			(Failure::Trapped, Failure::Trapped) => true,
+			(Failure::Violated { invariant: a, .. }, Failure::Violated { invariant: b, .. }) =>
+				a == b,
+			_ => false,
+		}
+	}
+}
+
+impl fmt::Display for Failure {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Failure::Panicked(message) => write!(f, "contract {}", message),
+			Failure::Trapped => write!(f, "contract trapped"),
+			Failure::Violated { invariant, expected, actual, error } => {
+				write!(f, "invariant {}", invariant)?;
+				match (actual, error) {
+					(_, Some(error)) => write!(f, " failed with {}", error)?,
+					(Some(actual), None) => write!(f, " returned {}", actual)?,
+					(None, None) => (),
+				}
+				write!(f, ", expected {}", expected)
+			},
+		}
+	}
+}
+
+/// A call of a message as one of the [`ACCOUNTS`].
+#[derive(Clone, Debug)]
+struct Step {
+	caller: usize,
+	/// Selector and SCALE encoded arguments of the message.
+	input: Vec<u8>,
+}
+
+/// A failing sequence of calls, minimised.
+struct Finding {
+	run: u32,
+	constructor: Vec<u8>,
+	steps: Vec<Step>,
+	failure: Failure,
+}
+
+fn gas_limit() -> Weight {
+	RuntimeBlockWeights::get().max_block
+}
+
+/// Random integer of `bits` bits, often one of the values arithmetic overflows on.
+fn integer(rng: &mut StdRng, bits: u32, signed: bool) -> u128 {
+	let max = if bits == 128 { u128::MAX } else { (1 << bits) - 1 };
+	match rng.gen_range(0..8) {
+		0 => 0,
+		1 => 1,
+		// `-1` of signed integers.
+		2 => max,
+		3 => max - 1,
+		// The maximum and minimum of signed integers.
+		4 if signed => max >> 1,
+		5 if signed => (max >> 1) + 1,
+		4 | 5 => rng.gen_range(0..=max.min(1_000)),
+		_ => rng.gen::<u128>() & max,
+	}
+}
+
+fn primitive(
+	primitive: &TypeDefPrimitive,
+	rng: &mut StdRng,
+	out: &mut Vec<u8>,
+) -> Result<(), String> {
+	let (bits, signed) = match primitive {
+		TypeDefPrimitive::Bool => {
+			rng.gen::<bool>().encode_to(out);
+			return Ok(())
+		},
+		TypeDefPrimitive::Str => {
+			let len = rng.gen_range(0..=MAX_LEN);
+			(0..len).map(|_| rng.gen_range('a'..='z')).collect::<String>().encode_to(out);
+			return Ok(())
+		},
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => {
+			out.extend(rng.gen::<[u8; 32]>());
+			return Ok(())
+		},
+		TypeDefPrimitive::Char => return Err("Chars are not supported".into()),
+		TypeDefPrimitive::U8 => (8, false),
+		TypeDefPrimitive::U16 => (16, false),
+		TypeDefPrimitive::U32 => (32, false),
+		TypeDefPrimitive::U64 => (64, false),
+		TypeDefPrimitive::U128 => (128, false),
+		TypeDefPrimitive::I8 => (8, true),
+		TypeDefPrimitive::I16 => (16, true),
+		TypeDefPrimitive::I32 => (32, true),
+		TypeDefPrimitive::I64 => (64, true),
+		TypeDefPrimitive::I128 => (128, true),
+	};
+	out.extend(&integer(rng, bits, signed).to_le_bytes()[..bits as usize / 8]);
+	Ok(())
+}
+
+/// Appends a random SCALE encoded value of the type `id` of `types` to `out`, account ids being
+/// the ones of the development `accounts`.
+fn generate(
+	types: &PortableRegistry,
+	accounts: &[AccountId],
+	id: u32,
+	depth: u32,
+	rng: &mut StdRng,
+	out: &mut Vec<u8>,
+) -> Result<(), String> {
+	let ty = types.resolve(id).ok_or_else(|| format!("Unknown type {}", id))?;
+	if ty.path.segments.last().map_or(false, |name| name == "AccountId") {
+		accounts[rng.gen_range(0..accounts.len())].encode_to(out);
+		return Ok(())
+	}
+	let nested = |id, rng: &mut StdRng, out: &mut Vec<u8>| {
+		generate(types, accounts, id, depth + 1, rng, out)
+	};
+	match &ty.type_def {
+		TypeDef::Composite(composite) =>
+			for field in &composite.fields {
+				nested(field.ty.id, rng, out)?;
+			},
+		TypeDef::Variant(variants) => {
+			let mut candidates: Vec<_> = variants.variants.iter().collect();
+			if depth >= MAX_DEPTH && candidates.iter().any(|variant| variant.fields.is_empty()) {
+				candidates.retain(|variant| variant.fields.is_empty());
+			}
+			if candidates.is_empty() {
+				return Err(format!("Type {} has no variants", id))
+			}
+			let variant = candidates[rng.gen_range(0..candidates.len())];
+			out.push(variant.index);
+			for field in &variant.fields {
+				nested(field.ty.id, rng, out)?;
+			}
+		},
+		TypeDef::Sequence(sequence) => {
+			let len = if depth >= MAX_DEPTH { 0 } else { rng.gen_range(0..=MAX_LEN) };
+			Compact(len as u32).encode_to(out);
+			for _ in 0..len {
+				nested(sequence.type_param.id, rng, out)?;
+			}
+		},
+		TypeDef::Array(array) =>
+			for _ in 0..array.len {
+				nested(array.type_param.id, rng, out)?;
+			},
+		TypeDef::Tuple(tuple) =>
+			for field in &tuple.fields {
+				nested(field.id, rng, out)?;
+			},
+		TypeDef::Primitive(def) => primitive(def, rng, out)?,
+		TypeDef::Compact(compact) => {
+			let bits = match types.resolve(compact.type_param.id).map(|ty| &ty.type_def) {
+				Some(TypeDef::Primitive(TypeDefPrimitive::U8)) => 8,
+				Some(TypeDef::Primitive(TypeDefPrimitive::U16)) => 16,
+				Some(TypeDef::Primitive(TypeDefPrimitive::U32)) => 32,
+				Some(TypeDef::Primitive(TypeDefPrimitive::U64)) => 64,
+				Some(TypeDef::Primitive(TypeDefPrimitive::U128)) => 128,
+				_ => return Err(format!("Compact type {} is not supported", id)),
+			};
+			Compact(integer(rng, bits, false)).encode_to(out);
+		},
+		TypeDef::BitSequence(_) => return Err("Bit sequences are not supported".into()),
+	}
+	Ok(())
+}
+
+/// Runs sequences of calls of a contract on the genesis state of the development chain.
+struct Fuzzer {
+	code: Vec<u8>,
+	metadata: Arc<ContractMetadata>,
+	storage: Storage,
+	accounts: Vec<AccountId>,
+	constructor: MessageInfo,
+	messages: Vec<MessageInfo>,
+	invariants: Vec<(MessageInfo, Predicate)>,
+}
+
+impl Fuzzer {
+	/// Fuzzer of the contract of the `.contract` bundle `bundle`, instantiated with the constructor
+	/// labelled `constructor` or the first one.
+	fn new(
+		bundle: &Path,
+		constructor: Option<&str>,
+		invariants: &[(String, Predicate)],
+	) -> Result<Self, String> {
+		let artifact = Artifact::read(bundle)?;
+		let registry = MetadataRegistry::<Hash>::default();
+		let code_hash = registry.register(artifact.metadata.ok_or("Not a contract bundle")?)?;
+		let metadata = registry.get(&code_hash).expect("metadata was just registered; qed");
+
+		let constructors = metadata.messages(true);
+		let constructor = match constructor {
+			Some(label) => constructors.into_iter().find(|constructor| constructor.label == label),
+			None => constructors.into_iter().next(),
+		}
+		.ok_or("No such constructor")?;
+
+		let invariants = invariants
+			.iter()
+			.map(|(label, expected)| {
+				let message = metadata
+					.messages(false)
+					.into_iter()
+					.find(|message| &message.label == label)
+					.ok_or_else(|| format!("No message {} for invariant", label))?;
+				if !message.args.is_empty() {
+					return Err(format!("Message {} of invariant takes arguments", label))
+				}
+				Ok((message, expected.clone()))
+			})
+			.collect::<Result<_, String>>()?;
+
+		Ok(Self {
+			code: artifact.code,
+			storage: chain_spec::development_config()?.build_storage()?,
+			accounts: ACCOUNTS
+				.iter()
+				.map(|seed| get_account_id_from_seed::<sr25519::Public>(seed))
+				.collect(),
+			messages: metadata.messages(false),
+			metadata,
+			constructor,
+			invariants,
+		})
+	}
+
+	/// Random input of `message`.
+	fn input(&self, message: &MessageInfo, rng: &mut StdRng) -> Result<Vec<u8>, String> {
+		let mut input = message.selector.clone();
+		for &arg in &message.args {
+			generate(self.metadata.types(), &self.accounts, arg, 0, rng, &mut input)?;
+		}
+		Ok(input)
+	}
+
+	fn step(&self, rng: &mut StdRng) -> Result<Step, String> {
+		let message = &self.messages[rng.gen_range(0..self.messages.len())];
+		Ok(Step { caller: rng.gen_range(0..self.accounts.len()), input: self.input(message, rng)? })
+	}
+
+	/// Random input of the constructor with which the contract can be instantiated.
+	fn constructor_input(&self, rng: &mut StdRng) -> Result<Vec<u8>, String> {
+		let mut error = String::new();
+		for _ in 0..INSTANTIATE_ATTEMPTS {
+			let input = self.input(&self.constructor, rng)?;
+			match self.externalities().execute_with(|| self.instantiate(&input)) {
+				Ok(_) => return Ok(input),
+				Err(e) => error = e,
+			}
+		}
+		Err(format!(
+			"Unable to instantiate the contract with {}: {}",
+			self.constructor.label, error
+		))
+	}
+
+	fn externalities(&self) -> sp_io::TestExternalities {
+		let mut ext = sp_io::TestExternalities::new(self.storage.clone());
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn instantiate(&self, input: &[u8]) -> Result<AccountId, String> {
+		let result = Contracts::bare_instantiate(
+			self.accounts[0].clone(),
+			0,
+			gas_limit(),
+			None,
+			Code::Upload(self.code.clone()),
+			input.to_vec(),
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		)
+		.result
+		.map_err(|e| format!("{:?}", e))?;
+		if result.result.did_revert() {
+			return Err("reverted".into())
+		}
+		Ok(result.account_id)
+	}
+
+	/// Calls the message of `step`, returning how the contract failed if it trapped.
+	fn call(&self, contract: &AccountId, step: &Step) -> Option<Failure> {
+		let result = Contracts::bare_call(
+			self.accounts[step.caller].clone(),
+			contract.clone(),
+			0,
+			gas_limit(),
+			None,
+			step.input.clone(),
+			DebugInfo::UnsafeDebug,
+			CollectEvents::Skip,
+			Determinism::Enforced,
+		);
+		let trapped = DispatchError::from(pallet_contracts::Error::<Runtime>::ContractTrapped);
+		if result.result.err() != Some(trapped) {
+			return None
+		}
+		// ink! prints the panic message before trapping, if the contract is built with debug
+		// messages enabled.
+		let debug_message = String::from_utf8_lossy(&result.debug_message);
+		Some(match debug_message.lines().find(|line| line.contains("panicked at")) {
+			Some(line) => Failure::Panicked(line.trim().to_string()),
+			None => Failure::Trapped,
+		})
+	}
+
+	/// The first invariant which doesn't hold. Like `dev_addInvariant`, their messages are called
+	/// as Alice and their changes are discarded.
+	fn check_invariants(&self, contract: &AccountId) -> Option<Failure> {
+		self.invariants.iter().find_map(|(message, expected)| {
+			let result = with_transaction(|| {
+				let result = Contracts::bare_call(
+					self.accounts[0].clone(),
+					contract.clone(),
+					0,
+					gas_limit(),
+					None,
+					message.selector.clone(),
+					DebugInfo::Skip,
+					CollectEvents::Skip,
+					Determinism::Enforced,
+				);
+				TransactionOutcome::Rollback(Ok::<_, DispatchError>(result.result))
+			})
+			.and_then(|result| result);
+			let (actual, error) = message_result(Some(&*self.metadata), &message.selector, result);
+			let holds =
+				error.is_none() && actual.as_ref().map_or(false, |actual| expected.holds(actual));
+			(!holds).then(|| Failure::Violated {
+				invariant: message.label.clone(),
+				expected: expected.clone(),
+				actual,
+				error,
+			})
+		})
+	}
+
+	/// Runs `steps` on a new instance of the contract, returning the number of steps after which
+	/// it failed and how.
+	fn execute(
+		&self,
+		constructor: &[u8],
+		steps: &[Step],
+	) -> Result<Option<(usize, Failure)>, String> {
+		self.externalities().execute_with(|| {
+			let contract = self.instantiate(constructor)?;
+			if let Some(failure) = self.check_invariants(&contract) {
+				return Ok(Some((0, failure)))
+			}
+			for (n, step) in steps.iter().enumerate() {
+				let failure =
+					self.call(&contract, step).or_else(|| self.check_invariants(&contract));
+				if let Some(failure) = failure {
+					return Ok(Some((n + 1, failure)))
+				}
+			}
+			Ok(None)
+		})
+	}
+
+	/// Drops the steps which aren't needed for the sequence to fail like `failure`.
+	fn minimise(&self, constructor: &[u8], mut steps: Vec<Step>, failure: &Failure) -> Vec<Step> {
+		let mut i = steps.len();
+		while i > 0 {
+			i -= 1;
+			let mut candidate = steps.clone();
+			candidate.remove(i);
+			if let Ok(Some((n, found))) = self.execute(constructor, &candidate) {
+				if found.same_as(failure) {
+					candidate.truncate(n);
+					steps = candidate;
+					i = i.min(steps.len());
+				}
+			}
+		}
+		steps
+	}
+
+	/// `label(arg: value, ..)` of a call, or its hex encoded input if it can't be decoded.
+	fn describe(&self, constructor: bool, input: &[u8]) -> String {
+		match self.metadata.decode_input(constructor, input) {
+			Ok(message) => {
+				let args: Vec<_> =
+					message.args.iter().map(|arg| format!("{}: {}", arg.name, arg.value)).collect();
+				format!("{}({})", message.label, args.join(", "))
+			},
+			Err(_) => format!("0x{}", HexDisplay::from(&input)),
+		}
+	}
+
+	/// The failure of `finding` and its steps, numbered `number` among the findings.
+	fn report(&self, number: usize, finding: &Finding) -> String {
+		let mut report =
+			format!("Finding {} in run {}: {}\n", number, finding.run, finding.failure);
+		report +=
+			&format!("   as {}: {}\n", ACCOUNTS[0], self.describe(true, &finding.constructor));
+		for (n, step) in finding.steps.iter().enumerate() {
+			report += &format!(
+				"{:>2}. as {}: {} (input 0x{})\n",
+				n + 1,
+				ACCOUNTS[step.caller],
+				self.describe(false, &step.input),
+				HexDisplay::from(&step.input)
+			);
+		}
+		report
+	}
+
+	/// Runs `runs` random sequences of `depth` calls, reporting and returning the minimised
+	/// sequence of each distinct failure.
+	fn fuzz(&mut self, runs: u32, depth: u32, rng: &mut StdRng) -> Result<Vec<Finding>, String> {
+		// Messages with arguments of unsupported types can't be called.
+		let messages = std::mem::take(&mut self.messages);
+		for message in messages {
+			match self.input(&message, rng) {
+				Ok(_) => self.messages.push(message),
+				Err(e) => eprintln!("Skipping message {}: {}", message.label, e),
+			}
+		}
+		if self.messages.is_empty() {
+			return Err("The contract has no messages which can be called".into())
+		}
+
+		let mut findings: Vec<Finding> = Vec::new();
+		for run in 1..=runs {
+			let constructor = self.constructor_input(rng)?;
+			let steps = (0..depth).map(|_| self.step(rng)).collect::<Result<Vec<_>, _>>()?;
+			let (n, failure) = match self.execute(&constructor, &steps)? {
+				Some(found) => found,
+				None => continue,
+			};
+			if findings.iter().any(|finding| finding.failure.same_as(&failure)) {
+				continue
+			}
+			let steps = self.minimise(&constructor, steps[..n].to_vec(), &failure);
+			let finding = Finding { run, constructor, steps, failure };
+			print!("{}", self.report(findings.len() + 1, &finding));
+			findings.push(finding);
+		}
+		Ok(findings)
+	}
+}
+
+impl FuzzCmd {
+	/// Runs the command, failing if any sequence of calls failed.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let mut fuzzer = Fuzzer::new(&self.bundle, self.constructor.as_deref(), &self.invariants)?;
+		let seed = self.seed.unwrap_or_else(rand::random);
+		let mut rng = StdRng::seed_from_u64(seed);
+
+		println!(
+			"Fuzzing {} with {} runs of {} calls, seed {}",
+			self.bundle.display(),
+			self.runs,
+			self.depth,
+			seed
+		);
+		let findings = fuzzer.fuzz(self.runs, self.depth, &mut rng)?;
+		if findings.is_empty() {
+			println!("No findings");
+			Ok(())
+		} else {
+			Err(format!("Sequences failed, replay them with --seed {}", seed).into())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+	use std::collections::HashSet;
+
+	const BUNDLE: &str = concat!(
+		env!("CARGO_MANIFEST_DIR"),
+		"/../contracts/fuzz-test/target/ink/fuzz_test.contract"
+	);
+
+	/// `u8`, `enum Tree { Leaf, Node(Vec<Tree>) }`, `Vec<Tree>` and an `AccountId` of 32 `u8`.
+	fn types() -> PortableRegistry {
+		serde_json::from_value(json!({ "types": [
+			{ "id": 0, "type": { "def": { "primitive": "u8" } } },
+			{ "id": 1, "type": { "path": ["Tree"], "def": { "variant": { "variants": [
+				{ "name": "Leaf", "index": 0 },
+				{ "name": "Node", "index": 1, "fields": [{ "type": 2 }] },
+			] } } } },
+			{ "id": 2, "type": { "def": { "sequence": { "type": 1 } } } },
+			{ "id": 3, "type": {
+				"path": ["ink_primitives", "types", "AccountId"],
+				"def": { "composite": { "fields": [{ "type": 4 }] } },
+			} },
+			{ "id": 4, "type": { "def": { "array": { "len": 32, "type": 0 } } } },
+		] }))
+		.unwrap()
+	}
+
+	/// Decodes a `Tree` from `input`, returning the number of nested `Node`s.
+	fn tree_height(input: &mut &[u8]) -> u32 {
+		let (&index, rest) = input.split_first().expect("truncated tree");
+		*input = rest;
+		if index == 0 {
+			return 0
+		}
+		let len = <Compact<u32> as codec::Decode>::decode(input).unwrap().0;
+		(0..len).map(|_| tree_height(input)).max().unwrap_or(0) + 1
+	}
+
+	fn accounts() -> Vec<AccountId> {
+		ACCOUNTS
+			.iter()
+			.map(|seed| get_account_id_from_seed::<sr25519::Public>(seed))
+			.collect()
+	}
+
+	fn step(caller: usize, selector: u8, arg: u32) -> Step {
+		Step { caller, input: [vec![0, 0, 0, selector], arg.encode()].concat() }
+	}
+
+	#[test]
+	fn integers_include_edge_values() {
+		let mut rng = StdRng::seed_from_u64(0);
+		for bits in [8, 16, 32, 64, 128] {
+			let max = if bits == 128 { u128::MAX } else { (1 << bits) - 1 };
+			for signed in [false, true] {
+				let values: HashSet<_> =
+					(0..1_000).map(|_| integer(&mut rng, bits, signed)).collect();
+				assert!(values.iter().all(|&value| value <= max));
+				for edge in [0, 1, max, max - 1] {
+					assert!(values.contains(&edge), "{} bits lack {}", bits, edge);
+				}
+				if signed {
+					assert!(values.contains(&(max >> 1)) && values.contains(&((max >> 1) + 1)));
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn recursive_types_stop_at_max_depth() {
+		let (types, accounts) = (types(), accounts());
+		let mut rng = StdRng::seed_from_u64(0);
+		for _ in 0..100 {
+			let mut out = Vec::new();
+			generate(&types, &accounts, 1, MAX_DEPTH, &mut rng, &mut out).unwrap();
+			assert_eq!(out, vec![0]);
+			out.clear();
+			generate(&types, &accounts, 2, MAX_DEPTH, &mut rng, &mut out).unwrap();
+			assert_eq!(out, vec![0]);
+
+			out.clear();
+			generate(&types, &accounts, 1, 0, &mut rng, &mut out).unwrap();
+			let mut input = &out[..];
+			// A `Node` and its `Vec` each take a level.
+			assert!(tree_height(&mut input) <= MAX_DEPTH / 2);
+			assert!(input.is_empty());
+		}
+	}
+
+	#[test]
+	fn account_ids_are_development_accounts() {
+		let (types, accounts) = (types(), accounts());
+		let mut rng = StdRng::seed_from_u64(0);
+		for _ in 0..20 {
+			let mut out = Vec::new();
+			generate(&types, &accounts, 3, 0, &mut rng, &mut out).unwrap();
+			assert!(accounts.iter().any(|account| account.encode() == out));
+		}
+	}
+
+	#[test]
+	fn failing_sequence_is_minimised() {
+		let fuzzer = Fuzzer::new(Path::new(BUNDLE), None, &[]).unwrap();
+		let constructor = fuzzer.constructor.selector.clone();
+		// `set(5)`, `divide(3)`, `set(0)`, `divide(7)`, `set(2)`.
+		let steps = vec![step(1, 1, 5), step(2, 2, 3), step(3, 1, 0), step(4, 2, 7), step(5, 1, 2)];
+
+		let (n, failure) = fuzzer.execute(&constructor, &steps).unwrap().unwrap();
+		assert_eq!(n, 4);
+		assert!(matches!(failure, Failure::Panicked(_) | Failure::Trapped));
+
+		let minimised = fuzzer.minimise(&constructor, steps[..n].to_vec(), &failure);
+		let inputs: Vec<_> = minimised.iter().map(|step| step.input.clone()).collect();
+		assert_eq!(inputs, vec![steps[2].input.clone(), steps[3].input.clone()]);
+	}
+
+	#[test]
+	fn fuzzing_reports_reduced_sequence() {
+		let mut fuzzer = Fuzzer::new(Path::new(BUNDLE), None, &[]).unwrap();
+		let findings = fuzzer.fuzz(50, 10, &mut StdRng::seed_from_u64(0)).unwrap();
+		assert_eq!(findings.len(), 1);
+
+		let finding = &findings[0];
+		assert_eq!(finding.steps.len(), 2);
+		let report = fuzzer.report(1, finding);
+		let lines: Vec<_> = report.lines().collect();
+		assert_eq!(lines.len(), 4);
+		assert!(lines[0].starts_with(&format!("Finding 1 in run {}: contract ", finding.run)));
+		assert_eq!(lines[1], "   as Alice: new()");
+		assert!(lines[2].starts_with(" 1. as ") && lines[2].contains(": set(divisor: 0) (input "));
+		assert!(lines[3].starts_with(" 2. as ") && lines[3].contains(": divide(value: "));
+	}
+}
@@ -0,0 +1,262 @@
+//! Gas and storage deposit usage of the contract messages executed on-chain, reported with
+//! `dev_gasReport` and printed as a table when the node shuts down.
+//!
+//! With `--gas-report`, contract calls and instantiations of every new best block are replayed as
+//! dry-runs to learn their usage, which is recorded by code hash and selector. Messages which
+//! reverted are left out, as their changes aren't kept. Only the outermost call of each extrinsic
+//! is recorded: the usage of nested calls isn't exposed by the `contracts` pallet, see
+//! `contracts_calls` in the runtime.
+
+use std::{
+	collections::BTreeMap,
+	fmt::{self, Write},
+	sync::{Arc, Mutex},
+};
+
+use futures::StreamExt;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use pallet_contracts_dev_rpc::{
+	block_calls, CallKind, CallResult, CallSummary, MetadataRegistry, StorageDeposit,
+};
+use sc_client_api::BlockchainEvents;
+use serde::{Deserialize, Serialize};
+use sp_core::{hexdisplay::HexDisplay, Bytes};
+use swanky_runtime::{AccountId, Balance, Hash};
+
+use crate::service::FullClient;
+
+/// Log target of the gas report.
+const LOG_TARGET: &str = "swanky::gas";
+
+/// Minimum, average and maximum of a quantity over the calls of a message.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats<T> {
+	pub min: T,
+	pub avg: T,
+	pub max: T,
+}
+
+/// Usage of a contract message or constructor.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasUsage {
+	pub code_hash: Hash,
+	pub selector: Bytes,
+	/// Name of the contract, if metadata is registered for its code.
+	pub contract: Option<String>,
+	/// Label of the message or constructor, if metadata is registered for its code.
+	pub message: Option<String>,
+	pub calls: u64,
+	pub ref_time: Stats<u64>,
+	pub proof_size: Stats<u64>,
+	/// Storage deposit charged, negative if refunded.
+	pub storage_deposit: Stats<i128>,
+}
+
+impl<T: fmt::Display> fmt::Display for Stats<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}/{}/{}", self.min, self.avg, self.max)
+	}
+}
+
+struct Totals {
+	min: i128,
+	max: i128,
+	sum: i128,
+}
+
+impl Default for Totals {
+	fn default() -> Self {
+		Totals { min: i128::MAX, max: i128::MIN, sum: 0 }
+	}
+}
+
+impl Totals {
+	fn add(&mut self, value: i128) {
+		self.min = self.min.min(value);
+		self.max = self.max.max(value);
+		self.sum = self.sum.saturating_add(value);
+	}
+
+	fn stats<T: TryFrom<i128> + Default>(&self, calls: u64) -> Stats<T> {
+		let convert = |value: i128| T::try_from(value).unwrap_or_default();
+		Stats {
+			min: convert(self.min),
+			avg: convert(self.sum / calls as i128),
+			max: convert(self.max),
+		}
+	}
+}
+
+#[derive(Default)]
+struct Entry {
+	contract: Option<String>,
+	message: Option<String>,
+	calls: u64,
+	ref_time: Totals,
+	proof_size: Totals,
+	storage_deposit: Totals,
+}
+
+/// Usage of the contract messages, shared by the recording task and the RPC.
+#[derive(Clone, Default)]
+pub struct GasReport {
+	entries: Arc<Mutex<BTreeMap<(Hash, [u8; 4]), Entry>>>,
+}
+
+impl GasReport {
+	/// Records the usage of the outermost call of `call`, naming it with the metadata in
+	/// `registry`.
+	fn record(
+		&self,
+		call: &CallSummary<AccountId, Balance, Hash>,
+		registry: &MetadataRegistry<Hash>,
+	) {
+		let (Some(code_hash), Some(selector)) = (call.code_hash, call.selector) else { return };
+		if !matches!(call.result, CallResult::Returned(_)) {
+			return
+		}
+		let gas = call.gas_consumed;
+		let (ref_time, proof_size) = (gas.ref_time() as i128, gas.proof_size() as i128);
+		let deposit = match call.storage_deposit {
+			StorageDeposit::Charge(amount) => amount as i128,
+			StorageDeposit::Refund(amount) => -(amount as i128),
+		};
+		let names = registry.get(&code_hash).and_then(|metadata| {
+			let constructor = matches!(call.kind, CallKind::Instantiate);
+			let message = metadata.decode_input(constructor, &call.input).ok()?;
+			Some((metadata.name().to_string(), message.label))
+		});
+
+		let mut entries = self.entries.lock().expect("lock is not poisoned; qed");
+		let entry = entries.entry((code_hash, selector)).or_default();
+		entry.calls += 1;
+		entry.ref_time.add(ref_time);
+		entry.proof_size.add(proof_size);
+		entry.storage_deposit.add(deposit);
+		if let Some((contract, message)) = names {
+			entry.contract = Some(contract);
+			entry.message = Some(message);
+		}
+	}
+
+	fn usage(&self) -> Vec<GasUsage> {
+		let entries = self.entries.lock().expect("lock is not poisoned; qed");
+		entries
+			.iter()
+			.map(|((code_hash, selector), entry)| GasUsage {
+				code_hash: *code_hash,
+				selector: selector.to_vec().into(),
+				contract: entry.contract.clone(),
+				message: entry.message.clone(),
+				calls: entry.calls,
+				ref_time: entry.ref_time.stats(entry.calls),
+				proof_size: entry.proof_size.stats(entry.calls),
+				storage_deposit: entry.storage_deposit.stats(entry.calls),
+			})
+			.collect()
+	}
+
+	fn reset(&self) {
+		self.entries.lock().expect("lock is not poisoned; qed").clear();
+	}
+
+	/// The report as a table, one message per row.
+	fn table(&self) -> String {
+		let mut rows = vec![[
+			"Contract".to_string(),
+			"Message".into(),
+			"Calls".into(),
+			"ref_time min/avg/max".into(),
+			"proof_size min/avg/max".into(),
+			"Storage deposit min/avg/max".into(),
+		]];
+		for usage in self.usage() {
+			rows.push([
+				usage.contract.unwrap_or_else(|| format!("{:?}", usage.code_hash)),
+				usage
+					.message
+					.unwrap_or_else(|| format!("0x{}", HexDisplay::from(&usage.selector.0))),
+				usage.calls.to_string(),
+				usage.ref_time.to_string(),
+				usage.proof_size.to_string(),
+				usage.storage_deposit.to_string(),
+			]);
+		}
+
+		let mut widths = [0; 6];
+		for row in &rows {
+			for (width, cell) in widths.iter_mut().zip(row) {
+				*width = (*width).max(cell.len());
+			}
+		}
+		let mut table = String::new();
+		for row in rows {
+			for (width, cell) in widths.iter().zip(row) {
+				let _ = write!(table, "  {:<width$}", cell, width = width);
+			}
+			table.push('\n');
+		}
+		table
+	}
+}
+
+/// Prints the report when the recording task is dropped, as the node shuts down.
+struct PrintOnShutdown(GasReport);
+
+impl Drop for PrintOnShutdown {
+	fn drop(&mut self) {
+		if self.0.entries.lock().map_or(true, |entries| entries.is_empty()) {
+			return
+		}
+		log::info!(target: LOG_TARGET, "Gas report:\n{}", self.0.table().trim_end());
+	}
+}
+
+/// Records the usage of the contract calls and instantiations of every new best block.
+pub async fn record_gas_usage(
+	client: Arc<FullClient>,
+	report: GasReport,
+	registry: MetadataRegistry<Hash>,
+) {
+	let _print = PrintOnShutdown(report.clone());
+	let mut imported = client.import_notification_stream();
+	while let Some(notification) = imported.next().await {
+		if !notification.is_new_best {
+			continue
+		}
+		match block_calls::<_, _, AccountId, Balance>(&*client, notification.hash) {
+			Ok(calls) => calls.iter().for_each(|(_, call)| report.record(call, &registry)),
+			Err(e) => log::warn!(
+				target: LOG_TARGET,
+				"Unable to record gas usage of block {:?}: {}",
+				notification.hash,
+				e
+			),
+		}
+	}
+}
+
+/// RPC methods for the gas usage of contract messages.
+#[rpc(server)]
+pub trait GasReportApi {
+	/// Usage of the contract messages executed on-chain since the node started or the report was
+	/// reset, by code hash and selector.
+	#[method(name = "dev_gasReport")]
+	fn gas_report(&self) -> RpcResult<Vec<GasUsage>>;
+
+	/// Clears the report.
+	#[method(name = "dev_resetGasReport")]
+	fn reset_gas_report(&self) -> RpcResult<()>;
+}
+
+impl GasReportApiServer for GasReport {
+	fn gas_report(&self) -> RpcResult<Vec<GasUsage>> {
+		Ok(self.usage())
+	}
+
+	fn reset_gas_report(&self) -> RpcResult<()> {
+		self.reset();
+		Ok(())
+	}
+}
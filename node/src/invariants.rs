@@ -0,0 +1,563 @@
+//! Invariants of contracts, read-only messages registered with `dev_addInvariant` whose result is
+//! checked against a predicate on every new best block.
+//!
+//! Messages are called with `ContractsApi::call` as Alice. Their return value is decoded with the
+//! registered metadata of the contract's code, unwrapped from the `Ok` ink! wraps it in, or taken
+//! as the hex encoded output otherwise. Violations are logged, streamed to the subscribers of
+//! `dev_subscribeInvariantViolations` and returned by `engine_createBlock` for the block created.
+
+use std::{
+	collections::VecDeque,
+	fmt,
+	sync::{Arc, Mutex},
+};
+
+use futures::{
+	channel::{
+		mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
+		oneshot,
+	},
+	FutureExt, StreamExt,
+};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::SubscriptionResult, SubscriptionSink};
+use pallet_contracts::ContractsApi;
+use pallet_contracts_dev_rpc::{ContractMetadata, ContractsDevRuntimeApi, MetadataRegistry};
+use pallet_contracts_primitives::ExecReturnValue;
+use sc_client_api::BlockchainEvents;
+use sc_consensus_manual_seal::InvariantCheck;
+use sc_rpc::SubscriptionTaskExecutor;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{hexdisplay::HexDisplay, sr25519, Bytes};
+use swanky_runtime::{opaque::Block, AccountId, Balance, BlockNumber, EventRecord, Hash};
+
+use crate::{chain_spec::get_account_id_from_seed, service::FullClient};
+
+/// Log target of the invariant checks.
+const LOG_TARGET: &str = "swanky::invariants";
+
+/// Number of recently checked blocks whose violations are kept, so that a block is checked once.
+const CHECKED_BLOCKS: usize = 16;
+
+/// Predicate the value returned by the message of an invariant must satisfy.
+///
+/// Numbers may be given as strings, as 128-bit integers are decoded as such.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Predicate {
+	/// The message succeeds, whatever it returns.
+	Succeeds,
+	Eq(Value),
+	Ne(Value),
+	Gt(Value),
+	Ge(Value),
+	Lt(Value),
+	Le(Value),
+}
+
+/// An integer of up to 128 bits, signed or not.
+///
+/// Values which fit in an `i128` are always `Signed`, so that the derived order is the numeric one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Integer {
+	Signed(i128),
+	/// Above `i128::MAX`.
+	Unsigned(u128),
+}
+
+/// Value of an integer, or of an integer encoded as a string.
+fn number(value: &Value) -> Option<Integer> {
+	let parse = |string: &str| {
+		string
+			.parse()
+			.map(Integer::Signed)
+			.or_else(|_| string.parse().map(Integer::Unsigned))
+			.ok()
+	};
+	match value {
+		Value::Number(number) => parse(&number.to_string()),
+		Value::String(string) => parse(string),
+		_ => None,
+	}
+}
+
+fn equals(actual: &Value, expected: &Value) -> bool {
+	actual == expected || number(actual).zip(number(expected)).map_or(false, |(a, b)| a == b)
+}
+
+impl Predicate {
+	pub(crate) fn holds(&self, actual: &Value) -> bool {
+		let compare = |expected: &Value, holds: fn(Integer, Integer) -> bool| {
+			number(actual).zip(number(expected)).map_or(false, |(a, b)| holds(a, b))
+		};
+		match self {
+			Predicate::Succeeds => true,
+			Predicate::Eq(expected) => equals(actual, expected),
+			Predicate::Ne(expected) => !equals(actual, expected),
+			Predicate::Gt(expected) => compare(expected, |a, b| a > b),
+			Predicate::Ge(expected) => compare(expected, |a, b| a >= b),
+			Predicate::Lt(expected) => compare(expected, |a, b| a < b),
+			Predicate::Le(expected) => compare(expected, |a, b| a <= b),
+		}
+	}
+}
+
+impl fmt::Display for Predicate {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Predicate::Succeeds => write!(f, "to succeed"),
+			Predicate::Eq(expected) => write!(f, "== {}", expected),
+			Predicate::Ne(expected) => write!(f, "!= {}", expected),
+			Predicate::Gt(expected) => write!(f, "> {}", expected),
+			Predicate::Ge(expected) => write!(f, ">= {}", expected),
+			Predicate::Lt(expected) => write!(f, "< {}", expected),
+			Predicate::Le(expected) => write!(f, "<= {}", expected),
+		}
+	}
+}
+
+/// A read-only message of a contract, whose return value must satisfy `expected`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Invariant {
+	pub id: u32,
+	pub address: AccountId,
+	pub selector: Bytes,
+	/// SCALE encoded arguments of the message.
+	pub args: Bytes,
+	pub expected: Predicate,
+}
+
+/// An invariant which doesn't hold in a block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvariantViolation {
+	pub id: u32,
+	pub block_hash: Hash,
+	pub block_number: BlockNumber,
+	pub address: AccountId,
+	/// Label of the message, if metadata is registered for the contract's code, or its selector.
+	pub message: String,
+	pub expected: Predicate,
+	/// Value returned by the message, if it could be read.
+	pub actual: Option<Value>,
+	/// Why the message failed, e.g. `reverted` or the error of the call.
+	pub error: Option<String>,
+}
+
+impl fmt::Display for InvariantViolation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invariant {} of {}: {}", self.id, self.address, self.message)?;
+		match (&self.actual, &self.error) {
+			(_, Some(error)) => write!(f, " failed with {}", error)?,
+			(Some(actual), None) => write!(f, " returned {}", actual)?,
+			(None, None) => (),
+		}
+		write!(f, ", expected {}", self.expected)
+	}
+}
+
+/// Registered invariants and their recent violations, shared by the checking task, the RPC and
+/// `engine_createBlock`.
+#[derive(Clone, Default)]
+pub struct Invariants {
+	invariants: Arc<Mutex<(u32, Vec<Invariant>)>>,
+	/// Violations found in the recently checked blocks.
+	checked: Arc<Mutex<VecDeque<(Hash, Vec<InvariantViolation>)>>>,
+	subscribers: Arc<Mutex<Vec<UnboundedSender<Vec<InvariantViolation>>>>>,
+}
+
+impl Invariants {
+	fn add(&self, address: AccountId, selector: Bytes, args: Bytes, expected: Predicate) -> u32 {
+		let mut invariants = self.invariants.lock().expect("lock is not poisoned; qed");
+		let id = invariants.0;
+		invariants.0 += 1;
+		invariants.1.push(Invariant { id, address, selector, args, expected });
+		id
+	}
+
+	fn remove(&self, id: u32) -> bool {
+		let mut invariants = self.invariants.lock().expect("lock is not poisoned; qed");
+		let count = invariants.1.len();
+		invariants.1.retain(|invariant| invariant.id != id);
+		invariants.1.len() != count
+	}
+
+	fn list(&self) -> Vec<Invariant> {
+		self.invariants.lock().expect("lock is not poisoned; qed").1.clone()
+	}
+
+	/// Stream of the violations found in every checked block.
+	fn subscribe(&self) -> UnboundedReceiver<Vec<InvariantViolation>> {
+		let (sender, receiver) = unbounded();
+		self.subscribers.lock().expect("lock is not poisoned; qed").push(sender);
+		receiver
+	}
+
+	/// Violations of the invariants in block `hash`. Blocks are checked once, the violations
+	/// found are reported as they are.
+	pub fn check<C>(
+		&self,
+		client: &C,
+		registry: &MetadataRegistry<Hash>,
+		hash: Hash,
+	) -> Vec<InvariantViolation>
+	where
+		C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+		C::Api: ContractsDevRuntimeApi<Block, AccountId, Balance>,
+		C::Api: ContractsApi<Block, AccountId, Balance, BlockNumber, Hash, EventRecord>,
+	{
+		let invariants = self.list();
+		if invariants.is_empty() {
+			return Vec::new()
+		}
+		// Held while checking, so that the block isn't checked twice by concurrent callers.
+		let mut checked = self.checked.lock().expect("lock is not poisoned; qed");
+		if let Some((_, violations)) = checked.iter().find(|(checked, _)| *checked == hash) {
+			return violations.clone()
+		}
+		let number = match client.number(hash) {
+			Ok(Some(number)) => number,
+			_ => return Vec::new(),
+		};
+		let violations: Vec<_> = invariants
+			.iter()
+			.filter_map(|invariant| evaluate(client, registry, hash, number, invariant))
+			.collect();
+
+		for violation in &violations {
+			log::warn!(target: LOG_TARGET, "#{} ({:?}) {}", number, hash, violation);
+		}
+		if !violations.is_empty() {
+			let mut subscribers = self.subscribers.lock().expect("lock is not poisoned; qed");
+			subscribers.retain(|subscriber| subscriber.unbounded_send(violations.clone()).is_ok());
+		}
+		if checked.len() == CHECKED_BLOCKS {
+			checked.pop_front();
+		}
+		checked.push_back((hash, violations.clone()));
+		violations
+	}
+
+	/// Check of the blocks created by `engine_createBlock`, run as a blocking task of `executor`
+	/// as it calls into the runtime. The result of the check of the background task is reused if
+	/// it's done first.
+	pub fn checker<C>(
+		&self,
+		client: Arc<C>,
+		registry: MetadataRegistry<Hash>,
+		executor: SubscriptionTaskExecutor,
+	) -> InvariantCheck<Hash>
+	where
+		C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+		C::Api: ContractsDevRuntimeApi<Block, AccountId, Balance>,
+		C::Api: ContractsApi<Block, AccountId, Balance, BlockNumber, Hash, EventRecord>,
+	{
+		let invariants = self.clone();
+		Arc::new(move |hash| {
+			let (sender, receiver) = oneshot::channel();
+			let (invariants, client, registry) =
+				(invariants.clone(), client.clone(), registry.clone());
+			let check = async move {
+				let violations = invariants
+					.check(&*client, &registry, hash)
+					.into_iter()
+					.filter_map(|violation| serde_json::to_value(violation).ok())
+					.collect::<Vec<_>>();
+				let _ = sender.send(violations);
+			};
+			executor.spawn_blocking("dev-invariants-check", Some("rpc"), check.boxed());
+			receiver.map(Result::unwrap_or_default).boxed()
+		})
+	}
+}
+
+/// Value returned by the message `selector` and why it failed, if it did: the call failed, the
+/// contract reverted or the message returned an `Err`.
+pub(crate) fn message_result(
+	metadata: Option<&ContractMetadata>,
+	selector: &[u8],
+	result: Result<ExecReturnValue, impl fmt::Debug>,
+) -> (Option<Value>, Option<String>) {
+	let output = match result {
+		Ok(output) => output,
+		Err(e) => return (None, Some(format!("{:?}", e))),
+	};
+	let value = match metadata {
+		Some(metadata) => metadata.decode_output(false, selector, &output.data),
+		None => Ok(Value::String(format!("0x{}", HexDisplay::from(&output.data)))),
+	};
+	match value {
+		// The `MessageResult` returned by ink!.
+		Ok(Value::Object(result)) if result.len() == 1 && result.contains_key("Ok") => {
+			let error = output.did_revert().then(|| "reverted".to_string());
+			(result.get("Ok").cloned(), error)
+		},
+		Ok(Value::Object(result)) if result.len() == 1 && result.contains_key("Err") =>
+			(None, result.get("Err").map(|e| e.to_string())),
+		Ok(value) => (Some(value), output.did_revert().then(|| "reverted".into())),
+		Err(e) => (None, Some(e)),
+	}
+}
+
+/// Calls the message of `invariant` in block `hash`, returning the violation if its predicate
+/// doesn't hold.
+fn evaluate<C>(
+	client: &C,
+	registry: &MetadataRegistry<Hash>,
+	hash: Hash,
+	number: BlockNumber,
+	invariant: &Invariant,
+) -> Option<InvariantViolation>
+where
+	C: ProvideRuntimeApi<Block>,
+	C::Api: ContractsDevRuntimeApi<Block, AccountId, Balance>,
+	C::Api: ContractsApi<Block, AccountId, Balance, BlockNumber, Hash, EventRecord>,
+{
+	let api = client.runtime_api();
+	let metadata = api
+		.code_hash(hash, invariant.address.clone())
+		.ok()
+		.flatten()
+		.and_then(|code_hash| registry.get(&code_hash));
+	let selector = &invariant.selector.0;
+	let message = metadata
+		.as_ref()
+		.and_then(|metadata| metadata.decode_input(false, selector).ok())
+		.map_or_else(|| format!("0x{}", HexDisplay::from(selector)), |message| message.label);
+
+	let input = [&selector[..], &invariant.args.0[..]].concat();
+	let origin = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let (actual, error) =
+		match api.call(hash, origin, invariant.address.clone(), 0, None, None, input) {
+			Err(e) => (None, Some(e.to_string())),
+			Ok(result) => message_result(metadata.as_deref(), selector, result.result),
+		};
+
+	let holds =
+		error.is_none() && actual.as_ref().map_or(false, |actual| invariant.expected.holds(actual));
+	(!holds).then(|| InvariantViolation {
+		id: invariant.id,
+		block_hash: hash,
+		block_number: number,
+		address: invariant.address.clone(),
+		message,
+		expected: invariant.expected.clone(),
+		actual,
+		error,
+	})
+}
+
+/// Checks the invariants on every new best block.
+pub async fn check_invariants(
+	client: Arc<FullClient>,
+	invariants: Invariants,
+	registry: MetadataRegistry<Hash>,
+) {
+	let mut imported = client.import_notification_stream();
+	while let Some(notification) = imported.next().await {
+		if notification.is_new_best {
+			invariants.check(&*client, &registry, notification.hash);
+		}
+	}
+}
+
+/// RPC methods for registering invariants and following their violations.
+#[rpc(server)]
+pub trait InvariantsApi {
+	/// Registers the message `selector` of the contract at `address`, called with the SCALE
+	/// encoded `args`, as an invariant whose return value must satisfy `expected` in every new
+	/// best block. Returns the id of the invariant.
+	#[method(name = "dev_addInvariant")]
+	fn add_invariant(
+		&self,
+		address: AccountId,
+		selector: Bytes,
+		args: Bytes,
+		expected: Predicate,
+	) -> RpcResult<u32>;
+
+	/// Unregisters the invariant `id`, returning whether it was registered.
+	#[method(name = "dev_removeInvariant")]
+	fn remove_invariant(&self, id: u32) -> RpcResult<bool>;
+
+	/// Registered invariants.
+	#[method(name = "dev_listInvariants")]
+	fn list_invariants(&self) -> RpcResult<Vec<Invariant>>;
+
+	/// Violations of the invariants found in the new best blocks.
+	#[subscription(
+		name = "dev_subscribeInvariantViolations" => "dev_invariantViolations",
+		unsubscribe = "dev_unsubscribeInvariantViolations",
+		item = InvariantViolation
+	)]
+	fn subscribe_violations(&self);
+}
+
+/// Serves the invariants of an [`Invariants`].
+pub struct InvariantChecks {
+	invariants: Invariants,
+	executor: SubscriptionTaskExecutor,
+}
+
+impl InvariantChecks {
+	pub fn new(invariants: Invariants, executor: SubscriptionTaskExecutor) -> Self {
+		Self { invariants, executor }
+	}
+}
+
+impl InvariantsApiServer for InvariantChecks {
+	fn add_invariant(
+		&self,
+		address: AccountId,
+		selector: Bytes,
+		args: Bytes,
+		expected: Predicate,
+	) -> RpcResult<u32> {
+		Ok(self.invariants.add(address, selector, args, expected))
+	}
+
+	fn remove_invariant(&self, id: u32) -> RpcResult<bool> {
+		Ok(self.invariants.remove(id))
+	}
+
+	fn list_invariants(&self) -> RpcResult<Vec<Invariant>> {
+		Ok(self.invariants.list())
+	}
+
+	fn subscribe_violations(&self, mut sink: SubscriptionSink) -> SubscriptionResult {
+		let stream = self.invariants.subscribe().flat_map(futures::stream::iter);
+		let fut = async move {
+			let _ = sink.pipe_from_stream(stream).await;
+		};
+		self.executor.spawn("dev-invariants-subscription", Some("rpc"), fut.boxed());
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use pallet_contracts_primitives::ReturnFlags;
+	use serde_json::json;
+
+	const BIG: u128 = i128::MAX as u128 + 1;
+
+	#[test]
+	fn numbers_and_strings_compare_as_integers() {
+		assert!(Predicate::Eq(json!("5")).holds(&json!(5)));
+		assert!(Predicate::Eq(json!(5)).holds(&json!("5")));
+		assert!(Predicate::Ne(json!("5")).holds(&json!(6)));
+		assert!(Predicate::Lt(json!(-1)).holds(&json!("-5")));
+		assert!(Predicate::Ge(json!("7")).holds(&json!(7)));
+		assert!(!Predicate::Gt(json!(7)).holds(&json!("7")));
+	}
+
+	#[test]
+	fn integers_above_i128_max_compare() {
+		assert!(Predicate::Gt(json!(i128::MAX.to_string())).holds(&json!(BIG.to_string())));
+		assert!(Predicate::Gt(json!(-1)).holds(&json!(u128::MAX.to_string())));
+		assert!(Predicate::Lt(json!(u128::MAX.to_string())).holds(&json!(BIG.to_string())));
+		assert!(Predicate::Eq(json!(BIG.to_string())).holds(&json!(BIG.to_string())));
+		assert!(!Predicate::Le(json!(0)).holds(&json!(BIG.to_string())));
+	}
+
+	#[test]
+	fn other_values_compare_only_for_equality() {
+		assert!(Predicate::Eq(json!({ "a": true })).holds(&json!({ "a": true })));
+		assert!(Predicate::Ne(json!("a")).holds(&json!("b")));
+		assert!(!Predicate::Gt(json!(1)).holds(&json!(true)));
+		assert!(!Predicate::Lt(json!("a")).holds(&json!("b")));
+		assert!(!Predicate::Ge(json!("1.5")).holds(&json!(2)));
+		assert!(Predicate::Succeeds.holds(&Value::Null));
+	}
+
+	fn output(flags: ReturnFlags, data: Vec<u8>) -> Result<ExecReturnValue, &'static str> {
+		Ok(ExecReturnValue { flags, data })
+	}
+
+	/// Metadata of a contract whose message `0x00000001` returns a `u32`.
+	fn metadata() -> Arc<ContractMetadata> {
+		let metadata = json!({
+			"source": { "hash": Hash::repeat_byte(1) },
+			"contract": { "name": "counter" },
+			"spec": {
+				"constructors": [],
+				"messages": [{
+					"label": "get",
+					"selector": "0x00000001",
+					"args": [],
+					"returnType": { "type": 0 },
+				}],
+				"events": [],
+				"lang_error": { "type": 2 },
+			},
+			"types": [
+				{ "id": 0, "type": {
+					"path": ["Result"],
+					"def": { "variant": { "variants": [
+						{ "name": "Ok", "index": 0, "fields": [{ "type": 1 }] },
+						{ "name": "Err", "index": 1, "fields": [{ "type": 2 }] },
+					] } },
+				} },
+				{ "id": 1, "type": { "def": { "primitive": "u32" } } },
+				{ "id": 2, "type": {
+					"path": ["ink_primitives", "LangError"],
+					"def": {
+						"variant": { "variants": [{ "name": "CouldNotReadInput", "index": 1 }] },
+					},
+				} },
+			],
+			"version": "4",
+		});
+		let registry = MetadataRegistry::default();
+		let code_hash = registry.register(metadata).expect("metadata is valid");
+		registry.get(&code_hash).expect("metadata is registered")
+	}
+
+	#[test]
+	fn output_without_metadata_is_hex() {
+		let selector = [0, 0, 0, 1];
+		assert_eq!(
+			message_result(None, &selector, output(ReturnFlags::empty(), vec![1, 2])),
+			(Some(json!("0x0102")), None)
+		);
+		assert_eq!(
+			message_result(None, &selector, output(ReturnFlags::REVERT, vec![1])),
+			(Some(json!("0x01")), Some("reverted".to_string()))
+		);
+		assert_eq!(
+			message_result(None, &selector, Err::<ExecReturnValue, _>("ContractTrapped")),
+			(None, Some("\"ContractTrapped\"".to_string()))
+		);
+	}
+
+	#[test]
+	fn message_result_is_unwrapped() {
+		let metadata = metadata();
+		let selector = [0, 0, 0, 1];
+		let ok = Ok::<u32, u8>(7).encode();
+
+		assert_eq!(
+			message_result(Some(&*metadata), &selector, output(ReturnFlags::empty(), ok.clone())),
+			(Some(json!(7)), None)
+		);
+		assert_eq!(
+			message_result(Some(&*metadata), &selector, output(ReturnFlags::REVERT, ok)),
+			(Some(json!(7)), Some("reverted".to_string()))
+		);
+		// `Err(LangError::CouldNotReadInput)`.
+		let (actual, error) =
+			message_result(Some(&*metadata), &selector, output(ReturnFlags::REVERT, vec![1, 1]));
+		assert_eq!(actual, None);
+		assert!(error.is_some());
+		// Output which doesn't decode.
+		let (actual, error) =
+			message_result(Some(&*metadata), &selector, output(ReturnFlags::empty(), vec![2]));
+		assert_eq!(actual, None);
+		assert!(error.is_some());
+	}
+}
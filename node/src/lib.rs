@@ -1,3 +1,12 @@
+pub mod block_summary;
 pub mod chain_spec;
+pub mod contract_admin;
+pub mod contract_registry;
+pub mod contract_storage;
+pub mod contract_watcher;
+pub mod events;
+pub mod fuzz;
+pub mod gas_report;
+pub mod invariants;
 pub mod rpc;
 pub mod service;
@@ -1,11 +1,20 @@
 //! Substrate Node Template CLI library.
 #![warn(missing_docs)]
 
+mod block_summary;
 mod chain_spec;
 #[macro_use]
 mod service;
 mod cli;
 mod command;
+mod contract_admin;
+mod contract_registry;
+mod contract_storage;
+mod contract_watcher;
+mod events;
+mod fuzz;
+mod gas_report;
+mod invariants;
 mod rpc;
 
 fn main() -> sc_cli::Result<()> {
@@ -10,7 +10,7 @@ use std::sync::Arc;
 use futures::channel::mpsc::Sender;
 use jsonrpsee::RpcModule;
 use sp_runtime::traits::Block as BlockT;
-use swanky_runtime::{opaque::Block, AccountId, Balance, Hash, Index};
+use swanky_runtime::{opaque::Block, AccountId, Balance, BlockNumber, EventRecord, Hash, Index};
 
 use sc_consensus_manual_seal::{
 	rpc::{ManualSeal, ManualSealApiServer},
@@ -34,6 +34,16 @@ pub struct FullDeps<C, B, P> {
 	pub deny_unsafe: DenyUnsafe,
 	/// A command stream to send authoring commands to manual seal consensus engine
 	pub command_sink: Sender<EngineCommand<Hash>>,
+	/// Contract metadata registered by clients, shared with the contract debug log
+	pub contract_metadata: pallet_contracts_dev_rpc::MetadataRegistry<Hash>,
+	/// Index of the events of the chain
+	pub events: crate::events::EventIndex,
+	/// Gas usage of the contract messages executed on-chain
+	pub gas_report: crate::gas_report::GasReport,
+	/// Invariants of contracts checked on every new best block
+	pub invariants: crate::invariants::Invariants,
+	/// Executor of the tasks feeding subscriptions
+	pub subscription_executor: sc_rpc::SubscriptionTaskExecutor,
 }
 
 /// Instantiate all full RPC extensions.
@@ -43,28 +53,76 @@ pub fn create_full<C, B, P>(
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
+	C: sc_client_api::BlockBackend<Block>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: pallet_balances_rpc::BalancesRuntimeApi<Block, AccountId, Balance>,
+	C::Api: pallet_contracts_dev_rpc::ContractsDevRuntimeApi<Block, AccountId, Balance>,
+	C::Api:
+		pallet_contracts::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash, EventRecord>,
+	C::Api: pallet_dev_parameters_rpc::DevParametersRuntimeApi<Block>,
+	C::Api: pallet_mock_chain_extension_rpc::MockChainExtensionRuntimeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool<Block = Block, Hash = <Block as BlockT>::Hash> + 'static,
 	B: sc_client_api::backend::Backend<Block> + Send + Sync + 'static,
 	P: TransactionPool + 'static,
 {
+	use crate::{
+		contract_admin::{ContractAdmin, ContractAdminApiServer},
+		contract_registry::{ContractRegistry, ContractRegistryApiServer},
+		contract_storage::{ContractStorage, ContractStorageApiServer},
+		events::{Events, EventsApiServer},
+		gas_report::GasReportApiServer,
+		invariants::{InvariantChecks, InvariantsApiServer},
+	};
 	use pallet_balances_rpc::{Balances, BalancesApiServer};
+	use pallet_contracts_dev_rpc::{ContractsDev, ContractsDevApiServer};
+	use pallet_dev_parameters_rpc::{DevParameters, DevParametersApiServer};
+	use pallet_mock_chain_extension_rpc::{MockChainExtension, MockChainExtensionApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
 	let mut io = RpcModule::new(());
-	let FullDeps { client, backend, pool, deny_unsafe, command_sink } = deps;
+	let FullDeps {
+		client,
+		backend,
+		pool,
+		deny_unsafe,
+		command_sink,
+		contract_metadata,
+		events,
+		gas_report,
+		invariants,
+		subscription_executor,
+	} = deps;
 
 	io.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
 	io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
 	io.merge(Balances::new(client.clone(), pool.clone()).into_rpc())?;
+	io.merge(DevParameters::new(client.clone()).into_rpc())?;
+	io.merge(ContractsDev::new(client.clone(), contract_metadata.clone()).into_rpc())?;
+	io.merge(
+		ContractRegistry::new(client.clone(), events.clone(), contract_metadata.clone()).into_rpc(),
+	)?;
+	io.merge(ContractStorage::new(client.clone(), contract_metadata.clone()).into_rpc())?;
+	io.merge(ContractAdmin::new(client.clone(), pool.clone()).into_rpc())?;
+	io.merge(
+		Events::new(events, contract_metadata.clone(), subscription_executor.clone()).into_rpc(),
+	)?;
+	io.merge(gas_report.into_rpc())?;
+	io.merge(MockChainExtension::new(client.clone(), pool.clone()).into_rpc())?;
+	io.merge(InvariantChecks::new(invariants.clone(), subscription_executor.clone()).into_rpc())?;
 
-	// The final RPC extension receives commands for the manual seal consensus engine.
-	io.merge(ManualSeal::new(client, backend, command_sink).into_rpc())?;
+	// The final RPC extension receives commands for the manual seal consensus engine. Blocks it
+	// creates are checked for invariant violations.
+	let invariant_check =
+		invariants.checker(client.clone(), contract_metadata, subscription_executor);
+	io.merge(
+		ManualSeal::new(client, backend, command_sink)
+			.with_invariant_check(invariant_check)
+			.into_rpc(),
+	)?;
 
 	Ok(io)
 }
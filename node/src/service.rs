@@ -7,7 +7,7 @@ use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sc_transaction_pool_api::TransactionPool;
 use std::sync::Arc;
-use swanky_runtime::{self, opaque::Block, RuntimeApi};
+use swanky_runtime::{self, opaque::Block, AccountId, Balance, RuntimeApi};
 // Our native executor instance.
 pub struct ExecutorDispatch;
 
@@ -105,6 +105,10 @@ pub fn new_partial(
 pub fn new_full(
 	config: Configuration,
 	finalize_delay_sec: Option<u64>,
+	print_contract_debug: bool,
+	print_blocks: bool,
+	gas_report_enabled: bool,
+	watch_contracts: Option<std::path::PathBuf>,
 ) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
@@ -142,19 +146,41 @@ pub fn new_full(
 
 	let prometheus_registry = config.prometheus_registry().cloned();
 	let (rpc_command_sink, rpc_commands_stream) = futures::channel::mpsc::channel(1000);
+	let contract_metadata = pallet_contracts_dev_rpc::MetadataRegistry::default();
+	// Metadata of the bundles of genesis contracts, to decode their calls and events.
+	let genesis_metadata = crate::chain_spec::Extensions::try_get(&*config.chain_spec)
+		.map(|extensions| extensions.contract_metadata.clone())
+		.unwrap_or_default();
+	for metadata in genesis_metadata {
+		if let Err(e) = contract_metadata.register(metadata) {
+			log::warn!("Unable to register the metadata of a genesis contract: {}", e);
+		}
+	}
+	let events = crate::events::EventIndex::default();
+	let gas_report = crate::gas_report::GasReport::default();
+	let invariants = crate::invariants::Invariants::default();
 
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let backend = backend.clone();
 		let pool = transaction_pool.clone();
+		let contract_metadata = contract_metadata.clone();
+		let events = events.clone();
+		let gas_report = gas_report.clone();
+		let invariants = invariants.clone();
 
-		Box::new(move |deny_unsafe, _| {
+		Box::new(move |deny_unsafe, subscription_executor| {
 			let deps = crate::rpc::FullDeps {
 				client: client.clone(),
 				backend: backend.clone(),
 				pool: pool.clone(),
 				deny_unsafe,
 				command_sink: rpc_command_sink.clone(),
+				contract_metadata: contract_metadata.clone(),
+				events: events.clone(),
+				gas_report: gas_report.clone(),
+				invariants: invariants.clone(),
+				subscription_executor,
 			};
 			crate::rpc::create_full(deps).map_err(Into::into)
 		})
@@ -175,6 +201,68 @@ pub fn new_full(
 		telemetry: telemetry.as_mut(),
 	})?;
 
+	// Indexing reads the events of every existing block first, synchronously.
+	task_manager.spawn_handle().spawn_blocking(
+		"event-index",
+		None,
+		crate::events::index_events(client.clone(), events),
+	);
+
+	// Replays call into the runtime synchronously.
+	if gas_report_enabled {
+		task_manager.spawn_handle().spawn_blocking(
+			"gas-report",
+			None,
+			crate::gas_report::record_gas_usage(
+				client.clone(),
+				gas_report,
+				contract_metadata.clone(),
+			),
+		);
+	}
+
+	// Checks call into the runtime synchronously.
+	task_manager.spawn_handle().spawn_blocking(
+		"invariant-checks",
+		None,
+		crate::invariants::check_invariants(client.clone(), invariants, contract_metadata.clone()),
+	);
+
+	// Replays call into the runtime synchronously.
+	if print_contract_debug {
+		task_manager.spawn_handle().spawn_blocking(
+			"contracts-debug-log",
+			None,
+			pallet_contracts_dev_rpc::log_debug_messages::<_, _, AccountId, Balance>(
+				client.clone(),
+				contract_metadata.clone(),
+			),
+		);
+	}
+
+	// Upgrades read block bodies and call into the runtime synchronously.
+	if let Some(dir) = watch_contracts {
+		task_manager.spawn_handle().spawn_blocking(
+			"contract-watcher",
+			None,
+			crate::contract_watcher::watch_contracts(
+				dir,
+				client.clone(),
+				transaction_pool.clone(),
+				contract_metadata.clone(),
+			),
+		);
+	}
+
+	// Summaries read block bodies and call into the runtime synchronously.
+	if print_blocks {
+		task_manager.spawn_handle().spawn_blocking(
+			"block-summary",
+			None,
+			crate::block_summary::print_blocks(client.clone(), contract_metadata),
+		);
+	}
+
 	let proposer = sc_basic_authorship::ProposerFactory::new(
 		task_manager.spawn_handle(),
 		client.clone(),
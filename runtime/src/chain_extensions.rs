@@ -1,11 +1,30 @@
 //!
 use super::Runtime;
 pub use pallet_chain_extension_assets::AssetsExtension;
+pub use pallet_chain_extension_dapps_staking::DappsStakingExtension;
+pub use pallet_chain_extension_uniques::UniquesExtension;
+#[cfg(feature = "dev-cheatcodes")]
+pub use pallet_cheatcodes::chain_extension::CheatcodesExtension;
 /// Registered WASM contracts chain extensions.
 use pallet_contracts::chain_extension::RegisteredChainExtension;
 
 // Following impls defines chain extension IDs.
 
+impl RegisteredChainExtension<Runtime> for DappsStakingExtension<Runtime> {
+	const ID: u16 = 0;
+}
+
 impl RegisteredChainExtension<Runtime> for AssetsExtension<Runtime> {
 	const ID: u16 = 2;
 }
+
+// Swanky-only extensions use IDs from 100 upwards, clear of the ones Astar assigns.
+
+impl RegisteredChainExtension<Runtime> for UniquesExtension<Runtime> {
+	const ID: u16 = 100;
+}
+
+#[cfg(feature = "dev-cheatcodes")]
+impl RegisteredChainExtension<Runtime> for CheatcodesExtension<Runtime> {
+	const ID: u16 = 101;
+}
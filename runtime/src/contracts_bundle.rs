@@ -0,0 +1,129 @@
+//! Simulation of bundles of contract calls, instantiations and runtime calls.
+//!
+//! The steps of a bundle are run one after another on the same state, each rolled back on its
+//! own if it fails, and the whole bundle rolled back at the end. Contract calls and instantiations
+//! are dry-runs, runtime calls are dispatched without charging fees.
+use super::{
+	contracts_debug::{rolled_back, ContractCall, Target},
+	AccountId, Balance, Contracts, Hash, RuntimeBlockWeights, RuntimeCall, RuntimeEvent,
+	RuntimeOrigin, System,
+};
+use codec::{Decode, Encode};
+use frame_support::dispatch::GetDispatchInfo;
+use pallet_contracts_dev_rpc_runtime_api::{CallResult, ContractEvent};
+use sp_runtime::traits::Dispatchable;
+use sp_std::prelude::*;
+
+pub type CallSpec = pallet_contracts_dev_rpc_runtime_api::CallSpec<AccountId, Balance, Hash>;
+pub type StepOutcome = pallet_contracts_dev_rpc_runtime_api::StepOutcome<AccountId, Balance, Hash>;
+
+/// Step of a bundle, with its runtime call decoded.
+enum Step {
+	Contract(ContractCall),
+	Dispatch { origin: AccountId, call: RuntimeCall },
+}
+
+impl Step {
+	fn decode(spec: CallSpec) -> Option<Self> {
+		let max_gas = RuntimeBlockWeights::get().max_block;
+		let step = match spec {
+			CallSpec::Call {
+				origin,
+				dest,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				input_data,
+			} => Step::Contract(ContractCall {
+				origin,
+				target: Target::Call(dest),
+				value,
+				gas_limit: gas_limit.unwrap_or(max_gas),
+				storage_deposit_limit,
+				data: input_data,
+			}),
+			CallSpec::Instantiate {
+				origin,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				code,
+				data,
+				salt,
+			} => Step::Contract(ContractCall {
+				origin,
+				target: Target::Instantiate { code, salt },
+				value,
+				gas_limit: gas_limit.unwrap_or(max_gas),
+				storage_deposit_limit,
+				data,
+			}),
+			CallSpec::Dispatch { origin, call } =>
+				Step::Dispatch { origin, call: RuntimeCall::decode(&mut &call[..]).ok()? },
+		};
+		Some(step)
+	}
+}
+
+/// Runs `steps` one after another, returning the outcome of each. Fails with the index of the
+/// first step whose runtime call can't be decoded, before running any.
+pub fn simulate(steps: Vec<CallSpec>) -> Result<Vec<StepOutcome>, u32> {
+	let steps = steps
+		.into_iter()
+		.enumerate()
+		.map(|(index, spec)| Step::decode(spec).ok_or(index as u32))
+		.collect::<Result<Vec<_>, _>>()?;
+	Ok(rolled_back(|| steps.into_iter().map(run).collect()))
+}
+
+fn run(step: Step) -> StepOutcome {
+	let first_event = System::event_count() as usize;
+	let mut outcome = match step {
+		Step::Contract(call) => {
+			let instantiated = match call.target {
+				Target::Instantiate { .. } => Some(call.callee()),
+				Target::Call(_) => None,
+			};
+			let result = call.dry_run();
+			let succeeded = matches!(&result.result, Ok(output) if !output.did_revert());
+			StepOutcome {
+				result: result.result.into(),
+				gas_consumed: result.gas_consumed,
+				storage_deposit: Some(result.storage_deposit.into()),
+				account_id: instantiated.filter(|_| succeeded),
+				events: Vec::new(),
+				contract_events: Vec::new(),
+				debug_message: result.debug_message,
+			}
+		},
+		Step::Dispatch { origin, call } => {
+			let info = call.get_dispatch_info();
+			let (result, post_info) = match call.dispatch(RuntimeOrigin::signed(origin)) {
+				Ok(post_info) => (CallResult::Completed, post_info),
+				Err(error) => (CallResult::Failed(error.error), error.post_info),
+			};
+			StepOutcome {
+				result,
+				gas_consumed: post_info.actual_weight.unwrap_or(info.weight),
+				storage_deposit: None,
+				account_id: None,
+				events: Vec::new(),
+				contract_events: Vec::new(),
+				debug_message: Vec::new(),
+			}
+		},
+	};
+
+	for record in System::read_events_no_consensus().skip(first_event) {
+		use pallet_contracts::Event::ContractEmitted;
+		if let RuntimeEvent::Contracts(ContractEmitted { contract, data }) = &record.event {
+			outcome.contract_events.push(ContractEvent {
+				contract: contract.clone(),
+				code_hash: Contracts::code_hash(contract),
+				data: data.clone(),
+			});
+		}
+		outcome.events.push(record.event.encode());
+	}
+	outcome
+}
@@ -0,0 +1,67 @@
+//! Summaries of the contract calls and instantiations of a block, decoded in the debug output of
+//! the node and recorded by its gas report.
+//!
+//! The `contracts` pallet doesn't expose the frames of a call, so only the outermost call is
+//! summarized. Nested calls can't be told apart reliably: their `Called`, `DelegateCalled` and
+//! `Instantiated` events are discarded when they revert, and their input, output and weight aren't
+//! recorded anywhere. The events emitted by every contract during the call are kept instead.
+use super::{
+	contracts_debug::{rolled_back, ContractCall, Target},
+	AccountId, Balance, Contracts, Executive, Hash, RuntimeEvent, UncheckedExtrinsic,
+};
+use pallet_contracts_dev_rpc_runtime_api::{CallKind, CallSummary, ContractEvent};
+use sp_std::prelude::*;
+
+pub type Summary = CallSummary<AccountId, Balance, Hash>;
+
+/// Dry-runs `call`, returning its summary.
+pub fn summarize(call: ContractCall) -> Summary {
+	let kind = match call.target {
+		Target::Call(_) => CallKind::Call,
+		Target::Instantiate { .. } => CallKind::Instantiate,
+	};
+	let (caller, callee, code_hash) = (call.origin.clone(), call.callee(), call.code_hash());
+	let selector = call.data.get(..4).and_then(|selector| selector.try_into().ok());
+	let (input, value) = (call.data.clone(), call.value);
+	let result = call.dry_run();
+
+	use pallet_contracts::Event::ContractEmitted;
+	let events = result
+		.events
+		.unwrap_or_default()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::Contracts(ContractEmitted { contract, data }) =>
+				Some(ContractEvent { code_hash: Contracts::code_hash(&contract), contract, data }),
+			_ => None,
+		})
+		.collect();
+	Summary {
+		kind,
+		caller,
+		callee,
+		code_hash,
+		selector,
+		input,
+		value,
+		gas_consumed: result.gas_consumed,
+		storage_deposit: result.storage_deposit.into(),
+		result: result.result.into(),
+		events,
+	}
+}
+
+/// Applies `extrinsics`, summarizing every contract call or instantiation first. Returns the
+/// summaries by extrinsic index.
+pub fn summarize_block(extrinsics: Vec<UncheckedExtrinsic>) -> Vec<(u32, Summary)> {
+	extrinsics
+		.into_iter()
+		.enumerate()
+		.filter_map(|(index, xt)| {
+			let summary =
+				ContractCall::from_extrinsic(&xt).map(|call| rolled_back(|| summarize(call)));
+			let _ = Executive::apply_extrinsic(xt);
+			summary.map(|summary| (index as u32, summary))
+		})
+		.collect()
+}
@@ -0,0 +1,246 @@
+//! Debug output of contract calls and instantiations executed by extrinsics.
+//!
+//! The `contracts` pallet only collects debug messages of dry-runs. Extrinsics are therefore
+//! replayed as dry-runs with debug output enabled, on the state they are applied to.
+use super::{
+	AccountId, Balance, Contracts, EventRecord, Executive, Hash, Runtime, RuntimeCall,
+	UncheckedExtrinsic, Weight, CONTRACTS_DEBUG_OUTPUT, CONTRACTS_EVENTS,
+};
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use pallet_contracts_primitives::{
+	Code, ContractExecResult, ContractInstantiateResult, ContractResult, ExecReturnValue,
+};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash as _, StaticLookup},
+	DispatchError,
+};
+use sp_std::prelude::*;
+
+type Lookup = <Runtime as frame_system::Config>::Lookup;
+
+/// Result of a dry-run, instantiations reduced to the constructor's return value.
+pub type DryRunResult =
+	ContractResult<Result<ExecReturnValue, DispatchError>, Balance, EventRecord>;
+
+/// Contract called or code instantiated by a [`ContractCall`].
+#[derive(Clone)]
+pub enum Target {
+	Call(AccountId),
+	Instantiate { code: Code<Hash>, salt: Vec<u8> },
+}
+
+/// Contract call or instantiation, as made by an extrinsic.
+#[derive(Clone)]
+pub struct ContractCall {
+	pub origin: AccountId,
+	pub target: Target,
+	pub value: Balance,
+	pub gas_limit: Weight,
+	pub storage_deposit_limit: Option<Balance>,
+	pub data: Vec<u8>,
+}
+
+impl ContractCall {
+	/// The contract call or instantiation made by `xt`, if it makes one.
+	pub fn from_extrinsic(xt: &UncheckedExtrinsic) -> Option<Self> {
+		let (address, _, _) = xt.signature.as_ref()?;
+		let origin = Lookup::lookup(address.clone()).ok()?;
+		let RuntimeCall::Contracts(call) = xt.function.clone() else { return None };
+
+		let (target, value, gas_limit, storage_deposit_limit, data) = match call {
+			pallet_contracts::Call::call {
+				dest,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				data,
+			} => (
+				Target::Call(Lookup::lookup(dest).ok()?),
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				data,
+			),
+			pallet_contracts::Call::instantiate_with_code {
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				code,
+				data,
+				salt,
+			} => (
+				Target::Instantiate { code: Code::Upload(code), salt },
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				data,
+			),
+			pallet_contracts::Call::instantiate {
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				code_hash,
+				data,
+				salt,
+			} => (
+				Target::Instantiate { code: Code::Existing(code_hash), salt },
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				data,
+			),
+			_ => return None,
+		};
+		Some(ContractCall {
+			origin,
+			target,
+			value,
+			gas_limit,
+			storage_deposit_limit: storage_deposit_limit.map(Into::into),
+			data,
+		})
+	}
+
+	/// The contract called, or the address of the contract instantiated.
+	pub fn callee(&self) -> AccountId {
+		match &self.target {
+			Target::Call(dest) => dest.clone(),
+			Target::Instantiate { code, salt } =>
+				Contracts::contract_address(&self.origin, &code_hash(code), &self.data, salt),
+		}
+	}
+
+	/// Hash of the code of the contract called, or of the code instantiated.
+	pub fn code_hash(&self) -> Option<Hash> {
+		match &self.target {
+			Target::Call(dest) => Contracts::code_hash(dest),
+			Target::Instantiate { code, .. } => Some(code_hash(code)),
+		}
+	}
+
+	/// Dry-runs the call with debug output enabled.
+	pub fn dry_run(self) -> DryRunResult {
+		match self.target {
+			Target::Call(dest) => dry_run_call(
+				self.origin,
+				dest,
+				self.value,
+				self.gas_limit,
+				self.storage_deposit_limit,
+				self.data,
+			),
+			Target::Instantiate { code, salt } => {
+				let result = dry_run_instantiate(
+					self.origin,
+					self.value,
+					self.gas_limit,
+					self.storage_deposit_limit,
+					code,
+					self.data,
+					salt,
+				);
+				ContractResult {
+					gas_consumed: result.gas_consumed,
+					gas_required: result.gas_required,
+					storage_deposit: result.storage_deposit,
+					debug_message: result.debug_message,
+					result: result.result.map(|instantiated| instantiated.result),
+					events: result.events,
+				}
+			},
+		}
+	}
+}
+
+fn code_hash(code: &Code<Hash>) -> Hash {
+	match code {
+		Code::Upload(code) => BlakeTwo256::hash(code),
+		Code::Existing(code_hash) => *code_hash,
+	}
+}
+
+/// Applies `extrinsics`, returning the debug message of each contract call or instantiation by
+/// extrinsic index.
+///
+/// Only extrinsics calling `Contracts` directly are dry-run, and before their fee is withdrawn.
+pub fn debug_messages(extrinsics: Vec<UncheckedExtrinsic>) -> Vec<(u32, Vec<u8>)> {
+	extrinsics
+		.into_iter()
+		.enumerate()
+		.filter_map(|(index, xt)| {
+			let message = ContractCall::from_extrinsic(&xt)
+				.map(|call| rolled_back(|| call.dry_run().debug_message));
+			let _ = Executive::apply_extrinsic(xt);
+			message.map(|message| (index as u32, message))
+		})
+		.collect()
+}
+
+/// Runs `f`, discarding its changes to storage.
+pub fn rolled_back<R>(f: impl FnOnce() -> R) -> R {
+	with_transaction(|| TransactionOutcome::Rollback(Ok::<_, DispatchError>(f())))
+		.expect("the closure doesn't fail; qed")
+}
+
+/// Dry-runs a contract call with debug output enabled, appending chain extension traces to the
+/// debug message.
+pub fn dry_run_call(
+	origin: AccountId,
+	dest: AccountId,
+	value: Balance,
+	gas_limit: Weight,
+	storage_deposit_limit: Option<Balance>,
+	input_data: Vec<u8>,
+) -> ContractExecResult<Balance, EventRecord> {
+	let (mut result, traces) = pallet_chain_extension_tracing::collect(|| {
+		Contracts::bare_call(
+			origin,
+			dest,
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			input_data,
+			CONTRACTS_DEBUG_OUTPUT,
+			CONTRACTS_EVENTS,
+			pallet_contracts::Determinism::Enforced,
+		)
+	});
+	pallet_chain_extension_tracing::append_to_debug_message(
+		CONTRACTS_DEBUG_OUTPUT,
+		&mut result.debug_message,
+		&traces,
+	);
+	result
+}
+
+/// Dry-runs a contract instantiation with debug output enabled, appending chain extension traces
+/// to the debug message.
+pub fn dry_run_instantiate(
+	origin: AccountId,
+	value: Balance,
+	gas_limit: Weight,
+	storage_deposit_limit: Option<Balance>,
+	code: Code<Hash>,
+	data: Vec<u8>,
+	salt: Vec<u8>,
+) -> ContractInstantiateResult<AccountId, Balance, EventRecord> {
+	let (mut result, traces) = pallet_chain_extension_tracing::collect(|| {
+		Contracts::bare_instantiate(
+			origin,
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			code,
+			data,
+			salt,
+			CONTRACTS_DEBUG_OUTPUT,
+			CONTRACTS_EVENTS,
+		)
+	});
+	pallet_chain_extension_tracing::append_to_debug_message(
+		CONTRACTS_DEBUG_OUTPUT,
+		&mut result.debug_message,
+		&traces,
+	);
+	result
+}
@@ -0,0 +1,153 @@
+//! Smallest gas and storage deposit limits with which contract calls and instantiations succeed.
+//!
+//! The limits are binary searched by dry-running the call, each dry-run rolled back. The gas
+//! required reported by a dry-run with the block's weight as limit bounds the search of
+//! `ref_time`, then of `proof_size`, and the storage deposit it charges the search of the storage
+//! deposit limit. A call succeeds when it neither traps nor reverts. Should it fail with the gas
+//! required or storage deposit charged, the estimate holds the failing dry-run.
+use super::{
+	contracts_debug::{rolled_back, ContractCall, DryRunResult, Target},
+	AccountId, Balance, Contracts, Hash, RuntimeBlockWeights, Weight,
+};
+use pallet_contracts_dev_rpc_runtime_api::{CallResult, StorageDeposit};
+use pallet_contracts_primitives::StorageDeposit as ContractsStorageDeposit;
+use sp_std::prelude::*;
+
+pub type EstimateRequest =
+	pallet_contracts_dev_rpc_runtime_api::EstimateRequest<AccountId, Balance, Hash>;
+pub type Estimate = pallet_contracts_dev_rpc_runtime_api::Estimate<AccountId, Balance, Hash>;
+
+/// Searches the smallest limits with which the call, instantiation or upload of `request`
+/// succeeds.
+pub fn estimate(request: EstimateRequest) -> Estimate {
+	let max_gas = RuntimeBlockWeights::get().max_block;
+	let call = match request {
+		EstimateRequest::Call { origin, dest, value, input_data } => ContractCall {
+			origin,
+			target: Target::Call(dest),
+			value,
+			gas_limit: max_gas,
+			storage_deposit_limit: None,
+			data: input_data,
+		},
+		EstimateRequest::Instantiate { origin, value, code, data, salt } => ContractCall {
+			origin,
+			target: Target::Instantiate { code, salt },
+			value,
+			gas_limit: max_gas,
+			storage_deposit_limit: None,
+			data,
+		},
+		EstimateRequest::Upload { origin, code } => return estimate_upload(origin, code),
+	};
+
+	let unlimited = dry_run(&call, max_gas, None);
+	let charged = match unlimited.storage_deposit {
+		ContractsStorageDeposit::Charge(amount) => amount,
+		ContractsStorageDeposit::Refund(_) => 0,
+	};
+	if !succeeded(&unlimited) {
+		return estimate_of(&call, unlimited.gas_required, charged, unlimited)
+	}
+
+	let required = unlimited.gas_required;
+	// The gas required reported by a dry-run isn't guaranteed to be enough, in which case the
+	// dry-run failing with it is returned.
+	let failed = |gas_limit, storage_deposit_limit: Option<Balance>| {
+		let result = dry_run(&call, gas_limit, storage_deposit_limit);
+		estimate_of(&call, gas_limit, storage_deposit_limit.unwrap_or(charged), result)
+	};
+	let Some(ref_time) = search(0, required.ref_time().into(), |ref_time| {
+		succeeded(&dry_run(&call, Weight::from_parts(ref_time as u64, required.proof_size()), None))
+	}) else {
+		return failed(required, None)
+	};
+	let ref_time = ref_time as u64;
+	let Some(proof_size) = search(0, required.proof_size().into(), |proof_size| {
+		succeeded(&dry_run(&call, Weight::from_parts(ref_time, proof_size as u64), None))
+	}) else {
+		return failed(Weight::from_parts(ref_time, required.proof_size()), None)
+	};
+	let gas_limit = Weight::from_parts(ref_time, proof_size as u64);
+	let Some(storage_deposit_limit) =
+		search(0, charged, |limit| succeeded(&dry_run(&call, gas_limit, Some(limit))))
+	else {
+		return failed(gas_limit, Some(charged))
+	};
+
+	let result = dry_run(&call, gas_limit, Some(storage_deposit_limit));
+	estimate_of(&call, gas_limit, storage_deposit_limit, result)
+}
+
+/// Uploads take no gas limit, and are charged a storage deposit only depending on the code.
+fn estimate_upload(origin: AccountId, code: Vec<u8>) -> Estimate {
+	let result = rolled_back(|| {
+		Contracts::bare_upload_code(origin, code, None, pallet_contracts::Determinism::Enforced)
+	});
+	let (deposit, code_hash, result) = match result {
+		Ok(uploaded) => (uploaded.deposit, Some(uploaded.code_hash), CallResult::Completed),
+		Err(error) => (0, None, CallResult::Failed(error)),
+	};
+	Estimate {
+		gas_limit: Weight::zero(),
+		storage_deposit_limit: deposit,
+		gas_consumed: Weight::zero(),
+		storage_deposit: StorageDeposit::Charge(deposit),
+		result,
+		account_id: None,
+		code_hash,
+		debug_message: Vec::new(),
+	}
+}
+
+fn dry_run(
+	call: &ContractCall,
+	gas_limit: Weight,
+	storage_deposit_limit: Option<Balance>,
+) -> DryRunResult {
+	let call = ContractCall { gas_limit, storage_deposit_limit, ..call.clone() };
+	rolled_back(|| call.dry_run())
+}
+
+fn succeeded(result: &DryRunResult) -> bool {
+	matches!(&result.result, Ok(output) if !output.did_revert())
+}
+
+/// Smallest value in `low..=high` for which `succeeds` holds, given that it holds for every value
+/// above one it holds for. `None` if it doesn't hold for `high`.
+fn search(mut low: u128, mut high: u128, mut succeeds: impl FnMut(u128) -> bool) -> Option<u128> {
+	if !succeeds(high) {
+		return None
+	}
+	while low < high {
+		let middle = low + (high - low) / 2;
+		if succeeds(middle) {
+			high = middle;
+		} else {
+			low = middle + 1;
+		}
+	}
+	Some(high)
+}
+
+fn estimate_of(
+	call: &ContractCall,
+	gas_limit: Weight,
+	storage_deposit_limit: Balance,
+	result: DryRunResult,
+) -> Estimate {
+	let instantiated = matches!(call.target, Target::Instantiate { .. }) && succeeded(&result);
+	Estimate {
+		gas_limit,
+		storage_deposit_limit,
+		gas_consumed: result.gas_consumed,
+		storage_deposit: result.storage_deposit.into(),
+		result: result.result.into(),
+		account_id: instantiated.then(|| call.callee()),
+		code_hash: match call.target {
+			Target::Instantiate { .. } => call.code_hash(),
+			Target::Call(_) => None,
+		},
+		debug_message: result.debug_message,
+	}
+}
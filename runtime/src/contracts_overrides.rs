@@ -0,0 +1,78 @@
+//! Dry-runs of contract calls on top of temporary overrides of the state and block context.
+//!
+//! The overrides are applied in the same storage transaction as the dry-run, and rolled back with
+//! it. Storage deposits aren't adjusted to overridden contract storage.
+use super::{
+	contracts_debug::{rolled_back, ContractCall},
+	AccountId, Balance, Balances, Contracts, Hash, Runtime, RuntimeOrigin, System,
+};
+use frame_support::{
+	storage::{child, child::ChildInfo},
+	traits::Currency,
+	Blake2_128Concat, StorageHasher,
+};
+use pallet_contracts_dev::storage::ContractInfoOf;
+use pallet_contracts_dev_rpc_runtime_api::StorageDeposit;
+use sp_runtime::{DispatchError, DispatchResult};
+
+pub type Overrides = pallet_contracts_dev_rpc_runtime_api::Overrides<AccountId, Balance, Hash>;
+pub type CallOutcome = pallet_contracts_dev_rpc_runtime_api::CallOutcome<Balance>;
+
+/// Dry-runs `call` on top of `overrides`.
+pub fn call_with_overrides(
+	call: ContractCall,
+	overrides: Overrides,
+) -> Result<CallOutcome, DispatchError> {
+	rolled_back(|| {
+		apply(overrides)?;
+		let result = call.dry_run();
+		Ok(CallOutcome {
+			gas_consumed: result.gas_consumed,
+			gas_required: result.gas_required,
+			storage_deposit: StorageDeposit::from(result.storage_deposit),
+			result: result.result.into(),
+			debug_message: result.debug_message,
+		})
+	})
+}
+
+fn apply(overrides: Overrides) -> DispatchResult {
+	if let Some(number) = overrides.block_number {
+		System::set_block_number(number);
+	}
+	if let Some(timestamp) = overrides.timestamp {
+		pallet_timestamp::Now::<Runtime>::put(timestamp);
+		// Contracts see the overridden timestamp as is, not shifted by the cheatcodes.
+		#[cfg(feature = "dev-cheatcodes")]
+		pallet_cheatcodes::TimestampOffset::<Runtime>::kill();
+	}
+
+	for account in overrides.accounts {
+		if let Some(free) = account.free_balance {
+			let _ = Balances::make_free_balance_be(&account.address, free);
+		}
+		if let Some(code_hash) = account.code_hash {
+			Contracts::set_code(RuntimeOrigin::root(), account.address.clone().into(), code_hash)?;
+		}
+		if account.storage.is_empty() {
+			continue
+		}
+		let trie = child_trie(&account.address)
+			.ok_or(pallet_contracts::Error::<Runtime>::ContractNotFound)?;
+		for (key, value) in account.storage {
+			// Keys are hashed like the variable sized keys of `seal_set_storage`.
+			let key = Blake2_128Concat::hash(&key);
+			match value {
+				Some(value) => child::put_raw(&trie, &key, &value),
+				None => child::kill(&trie, &key),
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Child trie holding the storage of `contract`.
+fn child_trie(contract: &AccountId) -> Option<ChildInfo> {
+	let info = ContractInfoOf::<Runtime>::get(contract)?;
+	Some(ChildInfo::new_default(&info.trie_id))
+}
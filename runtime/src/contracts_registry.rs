@@ -0,0 +1,56 @@
+//! Contracts deployed and code uploaded on the chain, read from the storage of the `contracts`
+//! pallet.
+//!
+//! The storage items of the pallet are aliased by `pallet_contracts_dev::storage`.
+use super::{AccountId, Balance, Balances, Hash, Runtime};
+use frame_support::traits::Currency;
+use pallet_contracts::Determinism;
+use pallet_contracts_dev::storage::{
+	CodeStorage, ContractInfo, ContractInfoOf, OwnerInfoOf, PristineCode,
+};
+use sp_std::prelude::*;
+
+pub type ContractDetails =
+	pallet_contracts_dev_rpc_runtime_api::ContractDetails<AccountId, Balance, Hash>;
+pub type CodeDetails = pallet_contracts_dev_rpc_runtime_api::CodeDetails<AccountId, Balance, Hash>;
+
+fn contract_details(address: AccountId, info: ContractInfo<Runtime>) -> ContractDetails {
+	ContractDetails {
+		balance: Balances::free_balance(&address),
+		address,
+		code_hash: info.code_hash,
+		storage_deposit: info
+			.storage_byte_deposit
+			.saturating_add(info.storage_item_deposit)
+			.saturating_add(info.storage_base_deposit),
+		storage_items: info.storage_items,
+		storage_bytes: info.storage_bytes,
+	}
+}
+
+/// Contracts deployed on the chain.
+pub fn contracts() -> Vec<ContractDetails> {
+	ContractInfoOf::<Runtime>::iter()
+		.map(|(address, info)| contract_details(address, info))
+		.collect()
+}
+
+/// Contract deployed at `address`.
+pub fn contract_info(address: AccountId) -> Option<ContractDetails> {
+	ContractInfoOf::<Runtime>::get(&address).map(|info| contract_details(address, info))
+}
+
+/// Code uploaded to the chain.
+pub fn codes() -> Vec<CodeDetails> {
+	OwnerInfoOf::<Runtime>::iter()
+		.map(|(code_hash, owner_info)| CodeDetails {
+			code_hash,
+			size: PristineCode::<Runtime>::decode_len(code_hash).unwrap_or_default() as u32,
+			deterministic: CodeStorage::<Runtime>::get(code_hash)
+				.map_or(true, |module| module.determinism == Determinism::Enforced),
+			refcount: owner_info.refcount,
+			owner: owner_info.owner,
+			deposit: owner_info.deposit,
+		})
+		.collect()
+}
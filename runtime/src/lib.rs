@@ -29,8 +29,8 @@ pub use frame_support::{
 	pallet_prelude::MaxEncodedLen,
 	parameter_types,
 	traits::{
-		AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU8, KeyOwnerProofSystem, Randomness,
-		StorageInfo,
+		AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU8, Contains, KeyOwnerProofSystem,
+		Randomness, StorageInfo,
 	},
 	weights::{
 		constants::{
@@ -46,6 +46,8 @@ use frame_system::{
 	EnsureSigned,
 };
 pub use pallet_balances::Call as BalancesCall;
+pub use pallet_dev_parameters::{AllowedCall, ContractsParameters, FeeParameters};
+pub use pallet_mock_chain_extension::MockResponse;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::CurrencyAdapter;
 
@@ -55,9 +57,18 @@ pub use sp_runtime::{Perbill, Permill};
 
 // Chain extensions
 use pallet_chain_extension_assets::AssetsExtension;
-// use pallet_chain_extension_dapps_staking::DappsStakingExtension;
+use pallet_chain_extension_dapps_staking::DappsStakingExtension;
+use pallet_chain_extension_uniques::UniquesExtension;
+#[cfg(feature = "dev-cheatcodes")]
+use pallet_cheatcodes::chain_extension::CheatcodesExtension;
 
 mod chain_extensions;
+pub mod contracts_bundle;
+pub mod contracts_debug;
+pub mod contracts_estimate;
+pub mod contracts_overrides;
+pub mod contracts_registry;
+pub mod contracts_calls;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -111,7 +122,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 4,
+	spec_version: 5,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -156,6 +167,10 @@ pub const EXISTENTIAL_DEPOSIT: Balance = MILLIUNIT;
 
 pub const STORAGE_BYTE_FEE: Balance = 100 * MICROUNIT;
 
+/// Upper bound of contract code size. Bounds the code storage of `pallet_contracts`, the actual
+/// limit is set through `DevParameters` and checked on upload by [`MaxCodeLenFilter`].
+pub const MAX_CODE_LEN: u32 = 123 * 1024;
+
 /// Charge fee for stored bytes and items.
 pub const fn deposit(items: u32, bytes: u32) -> Balance {
 	items as Balance * 1 * UNIT + (bytes as Balance) * STORAGE_BYTE_FEE
@@ -192,7 +207,7 @@ parameter_types! {
 
 impl frame_system::Config for Runtime {
 	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = MaxCodeLenFilter;
 	/// Block & extrinsics weights: base values and limits.
 	type BlockWeights = RuntimeBlockWeights;
 	/// The maximum length of a block (in bytes).
@@ -258,6 +273,9 @@ parameter_types! {
 impl pallet_timestamp::Config for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
 	type Moment = u64;
+	#[cfg(feature = "dev-cheatcodes")]
+	type OnTimestampSet = Cheatcodes;
+	#[cfg(not(feature = "dev-cheatcodes"))]
 	type OnTimestampSet = ();
 	type MinimumPeriod = MinimumPeriod;
 	type WeightInfo = ();
@@ -265,6 +283,7 @@ impl pallet_timestamp::Config for Runtime {
 
 parameter_types! {
 	pub const MaxLocks: u32 = 50;
+	pub ExistentialDeposit: Balance = DevParameters::existential_deposit();
 }
 
 impl pallet_balances::Config for Runtime {
@@ -276,7 +295,7 @@ impl pallet_balances::Config for Runtime {
 	/// The ubiquitous event type.
 	type RuntimeEvent = RuntimeEvent;
 	type DustRemoval = ();
-	type ExistentialDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 	type FreezeIdentifier = ();
@@ -322,8 +341,8 @@ impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
 	type OperationalFeeMultiplier = ConstU8<5>;
-	type WeightToFee = IdentityFee<Balance>;
-	type LengthToFee = IdentityFee<Balance>;
+	type WeightToFee = pallet_dev_parameters::WeightToFee<Runtime>;
+	type LengthToFee = pallet_dev_parameters::LengthToFee<Runtime>;
 	type FeeMultiplierUpdate = ();
 }
 
@@ -333,44 +352,153 @@ impl pallet_sudo::Config for Runtime {
 	type WeightInfo = pallet_sudo::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	/// Swanky's permissive contracts configuration, used unless the chain spec or root says
+	/// otherwise.
+	pub DefaultContractsParameters: ContractsParameters<Balance> = {
+		let limits = pallet_contracts::Schedule::<Runtime>::default().limits;
+		ContractsParameters {
+			max_code_len: MAX_CODE_LEN,
+			deposit_per_item: deposit(1, 0),
+			deposit_per_byte: deposit(0, 1),
+			default_deposit_limit: deposit(1024, 1024 * 1024),
+			unsafe_unstable_interface: true,
+			max_memory_pages: limits.memory_pages,
+			max_event_topics: limits.event_topics,
+			max_subject_len: limits.subject_len,
+			max_payload_len: limits.payload_len,
+		}
+	};
+	/// Identity weight-to-fee and length-to-fee.
+	pub DefaultFeeParameters: FeeParameters<Balance> = FeeParameters {
+		weight_fee_numerator: 1,
+		weight_fee_denominator: 1,
+		length_fee_per_byte: 1,
+	};
+}
+
+impl pallet_dev_parameters::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type DefaultContractsParameters = DefaultContractsParameters;
+	type DefaultFeeParameters = DefaultFeeParameters;
+	type DefaultExistentialDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type MaxCodeLenLimit = ConstU32<MAX_CODE_LEN>;
+	type MaxAllowedContractsCalls = ConstU32<128>;
+}
+
+#[cfg(feature = "dev-cheatcodes")]
+impl pallet_cheatcodes::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type MaxMarkerLen = ConstU32<1024>;
+}
+
+impl pallet_mock_chain_extension::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+}
+
+impl pallet_contracts_dev::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+}
+
 // contracts stuffs.
 parameter_types! {
-	pub const DepositPerItem: Balance = deposit(1, 0);
-	pub const DepositPerByte: Balance = deposit(0, 1);
-	pub const DefaultDepositLimit: Balance = deposit(1024, 1024 * 1024);
+	pub DepositPerItem: Balance = DevParameters::contracts_parameters().deposit_per_item;
+	pub DepositPerByte: Balance = DevParameters::contracts_parameters().deposit_per_byte;
+	pub DefaultDepositLimit: Balance = DevParameters::contracts_parameters().default_deposit_limit;
+	pub UnsafeUnstableInterface: bool =
+		DevParameters::contracts_parameters().unsafe_unstable_interface;
 	pub const MaxValueSize: u32 = 16 * 1024;
 	// The lazy deletion runs inside on_initialize.
 	pub DeletionWeightLimit: Weight = AVERAGE_ON_INITIALIZE_RATIO *
 		RuntimeBlockWeights::get().max_block;
-	pub Schedule: pallet_contracts::Schedule<Runtime> = Default::default();
+	pub Schedule: pallet_contracts::Schedule<Runtime> = {
+		let parameters = DevParameters::contracts_parameters();
+		let mut schedule = pallet_contracts::Schedule::<Runtime>::default();
+		schedule.limits.memory_pages = parameters.max_memory_pages;
+		schedule.limits.event_topics = parameters.max_event_topics;
+		schedule.limits.subject_len = parameters.max_subject_len;
+		schedule.limits.payload_len = parameters.max_payload_len;
+		schedule
+	};
+}
+
+/// Whether `code` is within the `max_code_len` of `DevParameters`.
+fn code_len_allowed(code: &[u8]) -> bool {
+	code.len() <= DevParameters::contracts_parameters().max_code_len as usize
 }
 
+/// Rejects uploads of contract code above the `max_code_len` of `DevParameters`.
+///
+/// The code storage of `pallet_contracts` stays bounded by [`MAX_CODE_LEN`] rather than by the
+/// parameter, so that lowering it doesn't make code already uploaded undecodable.
+pub struct MaxCodeLenFilter;
+
+impl Contains<RuntimeCall> for MaxCodeLenFilter {
+	#[allow(deprecated)]
+	fn contains(call: &RuntimeCall) -> bool {
+		match call {
+			RuntimeCall::Contracts(
+				pallet_contracts::Call::upload_code { code, .. } |
+				pallet_contracts::Call::instantiate_with_code { code, .. } |
+				pallet_contracts::Call::instantiate_with_code_old_weight { code, .. },
+			) => code_len_allowed(code),
+			_ => true,
+		}
+	}
+}
+
+/// Chain extensions available to contracts.
+#[cfg(not(feature = "dev-cheatcodes"))]
+type ChainExtensions =
+	(DappsStakingExtension<Runtime>, AssetsExtension<Runtime>, UniquesExtension<Runtime>);
+#[cfg(feature = "dev-cheatcodes")]
+type ChainExtensions = (
+	DappsStakingExtension<Runtime>,
+	AssetsExtension<Runtime>,
+	UniquesExtension<Runtime>,
+	CheatcodesExtension<Runtime>,
+);
+
 impl pallet_contracts::Config for Runtime {
+	/// Block timestamp, shifted by the `Cheatcodes` timestamp override.
+	#[cfg(feature = "dev-cheatcodes")]
+	type Time = pallet_cheatcodes::ContractsTime<Runtime>;
+	#[cfg(not(feature = "dev-cheatcodes"))]
 	type Time = Timestamp;
 	type Randomness = RandomnessCollectiveFlip;
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
-	/// The safest default is to allow no calls at all.
+	/// No calls are allowed unless whitelisted through `DevParameters`, at genesis or by root.
 	///
 	/// Runtimes should whitelist dispatchables that are allowed to be called from contracts
 	/// and make sure they are stable. Dispatchables exposed to contracts are not allowed to
 	/// change because that would break already deployed contracts. The `Call` structure itself
 	/// is not allowed to change the indices of existing pallets, too.
-	type CallFilter = frame_support::traits::Nothing;
+	type CallFilter = pallet_dev_parameters::ContractsCallFilter<Runtime>;
 	type DepositPerItem = DepositPerItem;
 	type DepositPerByte = DepositPerByte;
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
-	type ChainExtension = AssetsExtension<Self>;
+	/// Calls of functions mocked through `dev_mockChainExtension` are answered by
+	/// `MockChainExtension`, everything else reaches the registered extensions. All calls are
+	/// traced to `runtime::chain_extension`.
+	type ChainExtension = pallet_chain_extension_tracing::TracingChainExtension<
+		Self,
+		pallet_mock_chain_extension::MockChainExtension<Self, ChainExtensions>,
+	>;
 	type Schedule = Schedule;
+	/// The call stack depth is part of the type, so unlike the other limits it is not a dev
+	/// parameter. 5 frames is what Astar and Shiden use.
 	type CallStack = [pallet_contracts::Frame<Self>; 5];
 	type AddressGenerator = pallet_contracts::DefaultAddressGenerator;
-	type MaxCodeLen = ConstU32<{ 123 * 1024 }>;
+	type MaxCodeLen = ConstU32<MAX_CODE_LEN>;
 	type DefaultDepositLimit = DefaultDepositLimit;
 
 	type MaxStorageKeyLen = ConstU32<128>;
-	type UnsafeUnstableInterface = ConstBool<true>;
+	type UnsafeUnstableInterface = UnsafeUnstableInterface;
 	type MaxDebugBufferLen = ConstU32<{ 2 * 1024 * 1024 }>;
 }
 
@@ -420,6 +548,7 @@ parameter_types! {
 
 parameter_types! {
 	pub const CollectionDeposit: Balance = 10 * MILLIUNIT;
+	pub const UniquesDepositPerByte: Balance = deposit(0, 1);
 	pub const ItemDeposit: Balance = 1 * UNIT;
 	pub const KeyLimit: u32 = 32;
 	pub const ValueLimit: u32 = 256;
@@ -446,7 +575,7 @@ impl pallet_uniques::Config for Runtime {
 	type ItemDeposit = ItemDeposit;
 	type MetadataDepositBase = UniquesMetadataDepositBase;
 	type AttributeDepositBase = AttributeDepositBase;
-	type DepositPerByte = DepositPerByte;
+	type DepositPerByte = UniquesDepositPerByte;
 	type StringLimit = StringLimit;
 	type KeyLimit = KeyLimit;
 	type ValueLimit = ValueLimit;
@@ -469,18 +598,13 @@ impl<AccountId> Default for SmartContract<AccountId> {
 	}
 }
 
+// Required by the dApps staking chain extension, which only deals with Wasm contracts.
 impl<AccountId: From<[u8; 32]>> From<[u8; 32]> for SmartContract<AccountId> {
 	fn from(input: [u8; 32]) -> Self {
 		SmartContract::Wasm(input.into())
 	}
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen)]
-pub struct BondStakeInput<AccountId, Balance> {
-	account_id: AccountId,
-	value: Balance,
-}
-
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub struct Runtime
@@ -500,6 +624,11 @@ construct_runtime!(
 		DappsStaking: pallet_dapps_staking,
 		Uniques: pallet_uniques,
 		Utility: pallet_utility,
+		DevParameters: pallet_dev_parameters,
+		#[cfg(feature = "dev-cheatcodes")]
+		Cheatcodes: pallet_cheatcodes,
+		MockChainExtension: pallet_mock_chain_extension,
+		ContractsDev: pallet_contracts_dev,
 	}
 );
 
@@ -549,7 +678,7 @@ mod benches {
 	);
 }
 
-type EventRecord = frame_system::EventRecord<
+pub type EventRecord = frame_system::EventRecord<
 	<Runtime as frame_system::Config>::RuntimeEvent,
 	<Runtime as frame_system::Config>::Hash,
 >;
@@ -672,6 +801,139 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_dev_parameters_rpc_runtime_api::DevParametersApi<Block> for Runtime {
+		fn contracts_call_filter() -> Vec<AllowedCall> {
+			DevParameters::allowed_contracts_calls().into_inner()
+		}
+	}
+
+	impl pallet_mock_chain_extension_rpc_runtime_api::MockChainExtensionApi<Block> for Runtime {
+		fn mock_chain_extension_extrinsic(ext_id: u16, func_id: u16, mock: MockResponse) -> <Block as BlockT>::Extrinsic {
+			UncheckedExtrinsic::new_unsigned(
+				pallet_mock_chain_extension::Call::<Runtime>::mock_chain_extension { ext_id, func_id, mock, magic_number: Timestamp::now() }.into()
+			)
+		}
+
+		fn clear_mocks_extrinsic() -> <Block as BlockT>::Extrinsic {
+			UncheckedExtrinsic::new_unsigned(
+				pallet_mock_chain_extension::Call::<Runtime>::clear_mocks { magic_number: Timestamp::now() }.into()
+			)
+		}
+
+		fn mocks() -> Vec<(u16, u16, MockResponse)> {
+			pallet_mock_chain_extension::Mocks::<Runtime>::iter().collect()
+		}
+	}
+
+	impl pallet_contracts_dev_rpc_runtime_api::ContractsDevApi<Block, AccountId, Balance>
+		for Runtime
+	{
+		fn debug_messages(extrinsics: Vec<<Block as BlockT>::Extrinsic>) -> Vec<(u32, Vec<u8>)> {
+			contracts_debug::debug_messages(extrinsics)
+		}
+
+		fn block_calls(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+		) -> Vec<(u32, contracts_calls::Summary)> {
+			contracts_calls::summarize_block(extrinsics)
+		}
+
+		fn code_hash(address: AccountId) -> Option<Hash> {
+			Contracts::code_hash(&address)
+		}
+
+		fn estimate(request: contracts_estimate::EstimateRequest) -> contracts_estimate::Estimate {
+			contracts_estimate::estimate(request)
+		}
+
+		fn call_with_overrides(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			input_data: Vec<u8>,
+			overrides: contracts_overrides::Overrides,
+		) -> Result<contracts_overrides::CallOutcome, sp_runtime::DispatchError> {
+			let call = contracts_debug::ContractCall {
+				origin,
+				target: contracts_debug::Target::Call(dest),
+				value,
+				gas_limit: gas_limit.unwrap_or(RuntimeBlockWeights::get().max_block),
+				storage_deposit_limit,
+				data: input_data,
+			};
+			contracts_overrides::call_with_overrides(call, overrides)
+		}
+
+		fn simulate_bundle(
+			steps: Vec<contracts_bundle::CallSpec>,
+		) -> Result<Vec<contracts_bundle::StepOutcome>, u32> {
+			contracts_bundle::simulate(steps)
+		}
+
+		fn contracts() -> Vec<contracts_registry::ContractDetails> {
+			contracts_registry::contracts()
+		}
+
+		fn contract_info(address: AccountId) -> Option<contracts_registry::ContractDetails> {
+			contracts_registry::contract_info(address)
+		}
+
+		fn codes() -> Vec<contracts_registry::CodeDetails> {
+			contracts_registry::codes()
+		}
+
+		fn predict_address(
+			deployer: AccountId,
+			code_hash: Hash,
+			input_data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> AccountId {
+			Contracts::contract_address(&deployer, &code_hash, &input_data, &salt)
+		}
+
+		fn upgrade_code_extrinsic(
+			owner: AccountId,
+			code: Vec<u8>,
+			contracts: Vec<AccountId>,
+		) -> <Block as BlockT>::Extrinsic {
+			UncheckedExtrinsic::new_unsigned(
+				pallet_contracts_dev::Call::<Runtime>::upgrade_code {
+					owner,
+					code,
+					contracts,
+					magic_number: Timestamp::now(),
+				}
+				.into(),
+			)
+		}
+
+		fn kill_contract_extrinsic(
+			contract: AccountId,
+			beneficiary: AccountId,
+		) -> <Block as BlockT>::Extrinsic {
+			UncheckedExtrinsic::new_unsigned(
+				pallet_contracts_dev::Call::<Runtime>::kill_contract {
+					contract,
+					beneficiary,
+					magic_number: Timestamp::now(),
+				}
+				.into(),
+			)
+		}
+
+		fn reset_contract_storage_extrinsic(contract: AccountId) -> <Block as BlockT>::Extrinsic {
+			UncheckedExtrinsic::new_unsigned(
+				pallet_contracts_dev::Call::<Runtime>::reset_contract_storage {
+					contract,
+					magic_number: Timestamp::now(),
+				}
+				.into(),
+			)
+		}
+	}
+
 	impl pallet_contracts::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash, EventRecord>
 		for Runtime
 	{
@@ -684,16 +946,13 @@ impl_runtime_apis! {
 			input_data: Vec<u8>,
 		) -> pallet_contracts_primitives::ContractExecResult<Balance, EventRecord> {
 			let gas_limit = gas_limit.unwrap_or(RuntimeBlockWeights::get().max_block);
-			Contracts::bare_call(
+			contracts_debug::dry_run_call(
 				origin,
 				dest,
 				value,
 				gas_limit,
 				storage_deposit_limit,
 				input_data,
-				CONTRACTS_DEBUG_OUTPUT,
-				CONTRACTS_EVENTS,
-				pallet_contracts::Determinism::Enforced,
 			)
 		}
 
@@ -707,8 +966,20 @@ impl_runtime_apis! {
 			salt: Vec<u8>,
 		) -> pallet_contracts_primitives::ContractInstantiateResult<AccountId, Balance, EventRecord>
 		{
+			if let pallet_contracts_primitives::Code::Upload(code) = &code {
+				if !code_len_allowed(code) {
+					return pallet_contracts_primitives::ContractResult {
+						gas_consumed: Weight::zero(),
+						gas_required: Weight::zero(),
+						storage_deposit: Default::default(),
+						debug_message: Vec::new(),
+						result: Err(pallet_contracts::Error::<Runtime>::CodeTooLarge.into()),
+						events: None,
+					}
+				}
+			}
 			let gas_limit = gas_limit.unwrap_or(RuntimeBlockWeights::get().max_block);
-			Contracts::bare_instantiate(
+			contracts_debug::dry_run_instantiate(
 				origin,
 				value,
 				gas_limit,
@@ -716,8 +987,6 @@ impl_runtime_apis! {
 				code,
 				data,
 				salt,
-				CONTRACTS_DEBUG_OUTPUT,
-				CONTRACTS_EVENTS,
 			)
 		}
 
@@ -728,6 +997,9 @@ impl_runtime_apis! {
 			determinism: pallet_contracts::Determinism,
 		) -> pallet_contracts_primitives::CodeUploadResult<Hash, Balance>
 		{
+			if !code_len_allowed(&code) {
+				return Err(pallet_contracts::Error::<Runtime>::CodeTooLarge.into())
+			}
 			Contracts::bare_upload_code(origin, code, storage_deposit_limit, determinism)
 		}
 
@@ -0,0 +1,141 @@
+//! Traces chain extension calls of an ink! contract.
+mod common;
+
+use codec::Encode;
+use common::*;
+use frame_support::{assert_ok, traits::Get, weights::Weight};
+use pallet_chain_extension_tracing::{collect, ChainExtensionTrace, Details, Outcome};
+use pallet_contracts::{CollectEvents, DebugInfo, Determinism};
+use pallet_uniques::WeightInfo;
+use swanky_runtime::{
+	AccountId, Contracts, MockChainExtension, MockResponse, Runtime, RuntimeOrigin, UNIT,
+};
+
+const CONTRACT: &str = "uniques_extension_test";
+const UNIQUES_EXT_ID: u16 = 100;
+const COLLECTION: u32 = 1;
+
+// Status code of `pallet_chain_extension_uniques::UniquesError::UnknownCollection`.
+const UNKNOWN_COLLECTION: u32 = 2;
+
+fn deploy() -> AccountId {
+	instantiate(ALICE, load_contract(CONTRACT), 1_000 * UNIT)
+}
+
+fn input(id: u32, args: impl Encode) -> Vec<u8> {
+	let mut input = selector(id);
+	args.encode_to(&mut input);
+	input
+}
+
+fn trace(func_id: u16, details: Details, outcome: Outcome) -> ChainExtensionTrace {
+	ChainExtensionTrace { ext_id: UNIQUES_EXT_ID, func_id, details, outcome }
+}
+
+fn details(input_len: u32, weight_charged: Weight, output: Option<Vec<u8>>) -> Details {
+	Details { input_len: Some(input_len), weight_charged: Some(weight_charged), output }
+}
+
+fn read_weight() -> Weight {
+	<Runtime as frame_system::Config>::DbWeight::get().reads(1)
+}
+
+#[test]
+fn calls_are_traced() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+
+		let (owner, traces) = collect(|| {
+			call_message::<Option<AccountId>>(ALICE, contract.clone(), input(10, COLLECTION))
+		});
+		assert_eq!(owner, None);
+		let output = None::<AccountId>.encode();
+		assert_eq!(
+			traces,
+			vec![trace(9, details(4, read_weight(), Some(output)), Outcome::Converging(0))]
+		);
+
+		let (result, traces) = collect(|| {
+			call_message::<Result<(), u8>>(ALICE, contract, input(2, (COLLECTION, 7u32, BOB)))
+		});
+		assert_eq!(result, Err(UNKNOWN_COLLECTION as u8));
+		let weight = <Runtime as pallet_uniques::Config>::WeightInfo::mint();
+		assert_eq!(
+			traces,
+			vec![trace(1, details(40, weight, None), Outcome::Converging(UNKNOWN_COLLECTION))]
+		);
+	});
+}
+
+#[test]
+fn failing_calls_are_traced() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		// Mocks `collection_owner` with an output longer than the contract's buffer.
+		let output = vec![0; pallet_mock_chain_extension::MAX_MOCK_DATA_LEN as usize];
+		let mock = MockResponse { ret_code: 0, output: output.try_into().unwrap() };
+		assert_ok!(MockChainExtension::mock_chain_extension(
+			RuntimeOrigin::none(),
+			UNIQUES_EXT_ID,
+			9,
+			mock,
+			0
+		));
+
+		let (result, traces) = collect(|| {
+			Contracts::bare_call(
+				ALICE,
+				contract,
+				0,
+				GAS_LIMIT,
+				None,
+				input(10, COLLECTION),
+				DebugInfo::Skip,
+				CollectEvents::Skip,
+				Determinism::Enforced,
+			)
+			.result
+		});
+		let error = pallet_contracts::Error::<Runtime>::OutputBufferTooSmall.into();
+		assert_eq!(result.map(|_| ()), Err(error));
+		// The output never made it to the contract.
+		assert_eq!(traces, vec![trace(9, details(4, read_weight(), None), Outcome::Error(error))]);
+	});
+}
+
+#[test]
+fn traces_are_appended_to_debug_message() {
+	let mut debug_message = b"contract output\n".to_vec();
+	let charged = Weight::from_parts(1_000, 64);
+	let traces = [
+		trace(8, details(36, charged, Some(vec![1, 2])), Outcome::Converging(0)),
+		trace(0, Details::default(), Outcome::Converging(1)),
+	];
+
+	pallet_chain_extension_tracing::append_to_debug_message(
+		DebugInfo::UnsafeDebug,
+		&mut debug_message,
+		&traces,
+	);
+
+	assert_eq!(
+		String::from_utf8(debug_message).unwrap(),
+		"contract output\n\
+		 chain extension 100 function 8: input of 36 bytes, charged 1000/64, output 0x0102, \
+		 returned 0\n\
+		 chain extension 100 function 0: returned 1\n"
+	);
+}
+
+#[test]
+fn traces_are_only_appended_with_debug_output() {
+	let mut debug_message = Vec::new();
+
+	pallet_chain_extension_tracing::append_to_debug_message(
+		DebugInfo::Skip,
+		&mut debug_message,
+		&[trace(8, Details::default(), Outcome::Converging(0))],
+	);
+
+	assert!(debug_message.is_empty());
+}
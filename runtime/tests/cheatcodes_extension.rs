@@ -0,0 +1,107 @@
+//! Calls the cheatcodes chain extension from an ink! contract.
+#![cfg(feature = "dev-cheatcodes")]
+
+mod common;
+
+use codec::Encode;
+use common::*;
+use frame_support::{assert_ok, traits::Hooks};
+use swanky_runtime::{AccountId, RuntimeEvent, RuntimeOrigin, System, Timestamp, UNIT};
+
+const CONTRACT: &str = "cheatcodes_extension_test";
+
+// Status codes of `pallet_cheatcodes::chain_extension::CheatcodesError`.
+const NOT_DEV_CHAIN: u8 = 1;
+const TIMESTAMP_IN_PAST: u8 = 2;
+
+fn deploy() -> AccountId {
+	instantiate(ALICE, load_contract(CONTRACT), 1_000 * UNIT)
+}
+
+fn message<R: codec::Decode>(contract: &AccountId, id: u32, args: impl Encode) -> R {
+	let mut input = selector(id);
+	args.encode_to(&mut input);
+	call_message(ALICE, contract.clone(), input)
+}
+
+/// Finishes the current block and starts the next one with the given block timestamp.
+fn next_block(now: u64) {
+	Timestamp::on_finalize(System::block_number());
+	run_to_block(System::block_number() + 1);
+	assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+}
+
+#[test]
+fn is_dev_chain_works() {
+	ExtBuilder::default().dev_chain(true).build().execute_with(|| {
+		let contract = deploy();
+		assert!(message::<bool>(&contract, 5, ()));
+	});
+
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		assert!(!message::<bool>(&contract, 5, ()));
+	});
+}
+
+#[test]
+fn cheatcodes_are_refused_outside_dev_chain() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+
+		let result: Result<(), u8> = message(&contract, 1, (BOB, 42 * UNIT));
+		assert_eq!(result, Err(NOT_DEV_CHAIN));
+		assert_eq!(balance_of(&BOB), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn set_free_balance_works() {
+	ExtBuilder::default().dev_chain(true).build().execute_with(|| {
+		let contract = deploy();
+
+		let result: Result<(), u8> = message(&contract, 1, (BOB, 42 * UNIT));
+		assert_eq!(result, Ok(()));
+		assert_eq!(balance_of(&BOB), 42 * UNIT);
+	});
+}
+
+#[test]
+fn next_timestamp_override_works() {
+	ExtBuilder::default().dev_chain(true).build().execute_with(|| {
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 1_000));
+		let contract = deploy();
+
+		assert_eq!(message::<Option<u64>>(&contract, 2, ()), None);
+		let result: Result<(), u8> = message(&contract, 3, 1_000_000u64);
+		assert_eq!(result, Ok(()));
+		assert_eq!(message::<Option<u64>>(&contract, 2, ()), Some(1_000_000));
+		assert_eq!(message::<u64>(&contract, 6, ()), 1_000);
+
+		next_block(7_000);
+		assert_eq!(message::<Option<u64>>(&contract, 2, ()), None);
+		assert_eq!(message::<u64>(&contract, 6, ()), 1_000_000);
+
+		// Time keeps advancing from the override.
+		next_block(13_000);
+		assert_eq!(message::<u64>(&contract, 6, ()), 1_006_000);
+
+		let result: Result<(), u8> = message(&contract, 3, 1_000_000u64);
+		assert_eq!(result, Err(TIMESTAMP_IN_PAST));
+	});
+}
+
+#[test]
+fn emit_marker_works() {
+	ExtBuilder::default().dev_chain(true).build().execute_with(|| {
+		let contract = deploy();
+
+		let result: Result<(), u8> = message(&contract, 4, b"checkpoint".to_vec());
+		assert_eq!(result, Ok(()));
+
+		System::assert_has_event(RuntimeEvent::Cheatcodes(pallet_cheatcodes::Event::Marker {
+			contract,
+			data: b"checkpoint".to_vec(),
+		}));
+	});
+}
@@ -0,0 +1,151 @@
+//! Shared helpers for runtime integration tests.
+#![allow(dead_code)]
+
+use codec::Decode;
+use frame_support::{traits::Hooks, weights::Weight};
+use pallet_contracts::{CollectEvents, DebugInfo, Determinism};
+use pallet_contracts_dev::GenesisContract;
+use pallet_contracts_primitives::{Code, ExecReturnValue};
+#[cfg(feature = "dev-cheatcodes")]
+use swanky_runtime::CheatcodesConfig;
+use swanky_runtime::{
+	AccountId, Balance, BalancesConfig, BuildStorage, Contracts, ContractsDevConfig, DappsStaking,
+	GenesisConfig, Runtime, System, UNIT,
+};
+
+pub const ALICE: AccountId = AccountId::new([1u8; 32]);
+pub const BOB: AccountId = AccountId::new([2u8; 32]);
+
+pub const INITIAL_BALANCE: Balance = 1_000_000 * UNIT;
+pub const GAS_LIMIT: Weight = Weight::from_parts(100_000_000_000, 3 * 1024 * 1024);
+
+#[derive(Default)]
+pub struct ExtBuilder {
+	balances: Vec<(AccountId, Balance)>,
+	dev_chain: bool,
+	contracts: Vec<GenesisContract<AccountId, Balance>>,
+}
+
+impl ExtBuilder {
+	pub fn balances(mut self, balances: Vec<(AccountId, Balance)>) -> Self {
+		self.balances = balances;
+		self
+	}
+
+	/// Enables cheatcodes.
+	pub fn dev_chain(mut self, dev_chain: bool) -> Self {
+		self.dev_chain = dev_chain;
+		self
+	}
+
+	pub fn contracts(mut self, contracts: Vec<GenesisContract<AccountId, Balance>>) -> Self {
+		self.contracts = contracts;
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let balances = if self.balances.is_empty() {
+			vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)]
+		} else {
+			self.balances
+		};
+		let storage = GenesisConfig {
+			balances: BalancesConfig { balances },
+			#[cfg(feature = "dev-cheatcodes")]
+			cheatcodes: CheatcodesConfig { dev_chain: self.dev_chain },
+			contracts_dev: ContractsDevConfig { contracts: self.contracts },
+			..Default::default()
+		}
+		.build_storage()
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| run_to_block(1));
+		ext
+	}
+}
+
+/// Advances the chain, running the hooks of the pallets the tests rely on.
+pub fn run_to_block(n: u32) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		DappsStaking::on_initialize(next);
+	}
+}
+
+/// Reads `contracts/<name>/target/ink/<name>.wasm`, built by `scripts/build_test_contracts.sh`.
+pub fn load_contract(name: &str) -> Vec<u8> {
+	let crate_name = name.replace('_', "-");
+	let path = format!(
+		"{}/../contracts/{}/target/ink/{}.wasm",
+		env!("CARGO_MANIFEST_DIR"),
+		crate_name,
+		name
+	);
+	std::fs::read(&path).unwrap_or_else(|e| {
+		panic!("could not read {path}: {e}. Run `scripts/build_test_contracts.sh` first.")
+	})
+}
+
+/// Instantiates `code` as `origin` with the `0x00000000` constructor selector.
+pub fn instantiate(origin: AccountId, code: Vec<u8>, value: Balance) -> AccountId {
+	instantiate_with_salt(origin, code, value, vec![])
+}
+
+/// Like [`instantiate`], `salt` telling apart instances of the same code.
+pub fn instantiate_with_salt(
+	origin: AccountId,
+	code: Vec<u8>,
+	value: Balance,
+	salt: Vec<u8>,
+) -> AccountId {
+	Contracts::bare_instantiate(
+		origin,
+		value,
+		GAS_LIMIT,
+		None,
+		Code::Upload(code),
+		selector(0),
+		salt,
+		DebugInfo::UnsafeDebug,
+		CollectEvents::Skip,
+	)
+	.result
+	.expect("instantiation failed")
+	.account_id
+}
+
+pub fn call(origin: AccountId, contract: AccountId, input: Vec<u8>) -> ExecReturnValue {
+	Contracts::bare_call(
+		origin,
+		contract,
+		0,
+		GAS_LIMIT,
+		None,
+		input,
+		DebugInfo::UnsafeDebug,
+		CollectEvents::Skip,
+		Determinism::Enforced,
+	)
+	.result
+	.expect("call failed")
+}
+
+/// Calls an ink! message and decodes its return value, failing on `LangError`.
+pub fn call_message<R: Decode>(origin: AccountId, contract: AccountId, input: Vec<u8>) -> R {
+	let output = call(origin, contract, input);
+	assert!(!output.did_revert(), "contract reverted: {:?}", output.data);
+	<Result<R, u8>>::decode(&mut &output.data[..])
+		.expect("unexpected output")
+		.expect("ink! LangError")
+}
+
+/// Encodes the fixed selector used by the test contracts.
+pub fn selector(id: u32) -> Vec<u8> {
+	id.to_be_bytes().to_vec()
+}
+
+pub fn balance_of(who: &AccountId) -> Balance {
+	pallet_balances::Pallet::<Runtime>::free_balance(who)
+}
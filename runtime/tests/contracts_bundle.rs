@@ -0,0 +1,118 @@
+//! Simulates bundles of contract calls, instantiations and runtime calls.
+mod common;
+
+use codec::Encode;
+use common::*;
+use pallet_contracts_dev_rpc_runtime_api::{CallResult, StorageDeposit};
+use pallet_contracts_primitives::Code;
+use sp_runtime::traits::{BlakeTwo256, Hash as _};
+use swanky_runtime::{
+	contracts_bundle::{self, CallSpec},
+	AccountId, Balance, Contracts, RuntimeCall, UNIT,
+};
+
+const CONTRACT: &str = "call_trace_test";
+
+fn instantiate_spec(salt: u8) -> CallSpec {
+	CallSpec::Instantiate {
+		origin: ALICE,
+		value: UNIT,
+		gas_limit: None,
+		storage_deposit_limit: None,
+		code: Code::Upload(load_contract(CONTRACT)),
+		data: selector(0),
+		salt: vec![salt],
+	}
+}
+
+fn call_spec(dest: AccountId, input_data: Vec<u8>) -> CallSpec {
+	CallSpec::Call {
+		origin: ALICE,
+		dest,
+		value: 0,
+		gas_limit: None,
+		storage_deposit_limit: None,
+		input_data,
+	}
+}
+
+fn transfer_spec(value: Balance) -> CallSpec {
+	let call = RuntimeCall::Balances(pallet_balances::Call::transfer { dest: BOB.into(), value });
+	CallSpec::Dispatch { origin: ALICE, call: call.encode() }
+}
+
+/// Address of `CONTRACT` instantiated by `ALICE` with `salt`.
+fn address(salt: u8) -> AccountId {
+	let code_hash = BlakeTwo256::hash(&load_contract(CONTRACT));
+	Contracts::contract_address(&ALICE, &code_hash, &selector(0), &[salt])
+}
+
+/// Input of `relay(callees)`.
+fn relay(callees: Vec<AccountId>) -> Vec<u8> {
+	let mut input = selector(1);
+	callees.encode_to(&mut input);
+	input
+}
+
+#[test]
+fn steps_run_on_the_same_state() {
+	ExtBuilder::default().build().execute_with(|| {
+		let balance = balance_of(&BOB);
+
+		let outcomes = contracts_bundle::simulate(vec![
+			instantiate_spec(1),
+			call_spec(address(1), relay(vec![])),
+			transfer_spec(UNIT),
+		])
+		.unwrap();
+
+		assert_eq!(outcomes.len(), 3);
+		assert_eq!(outcomes[0].account_id, Some(address(1)));
+		assert!(matches!(outcomes[0].storage_deposit, Some(StorageDeposit::Charge(_))));
+
+		assert_eq!(outcomes[1].result, CallResult::Returned(Ok::<(), u8>(()).encode()));
+		let emitted = &outcomes[1].contract_events;
+		assert_eq!(emitted.len(), 1);
+		assert_eq!(emitted[0].contract, address(1));
+		assert_eq!(emitted[0].data, (0u8, 0u32).encode());
+		assert!(emitted[0].code_hash.is_some());
+
+		assert_eq!(outcomes[2].result, CallResult::Completed);
+		assert_eq!(outcomes[2].storage_deposit, None);
+		assert!(!outcomes[2].events.is_empty());
+
+		// The state is discarded afterwards.
+		assert_eq!(Contracts::code_hash(&address(1)), None);
+		assert_eq!(balance_of(&BOB), balance);
+	});
+}
+
+#[test]
+fn failed_step_does_not_stop_the_bundle() {
+	ExtBuilder::default().build().execute_with(|| {
+		let outcomes = contracts_bundle::simulate(vec![
+			transfer_spec(10 * INITIAL_BALANCE),
+			instantiate_spec(1),
+			call_spec(address(1), selector(2)),
+		])
+		.unwrap();
+
+		assert!(matches!(outcomes[0].result, CallResult::Failed(_)));
+		assert_eq!(outcomes[1].account_id, Some(address(1)));
+		assert_eq!(
+			outcomes[2].result,
+			CallResult::Reverted(Ok::<Result<(), u8>, u8>(Err(1)).encode())
+		);
+	});
+}
+
+#[test]
+fn undecodable_call_is_refused() {
+	ExtBuilder::default().build().execute_with(|| {
+		let steps =
+			vec![instantiate_spec(1), CallSpec::Dispatch { origin: ALICE, call: vec![255] }];
+
+		assert_eq!(contracts_bundle::simulate(steps), Err(1));
+		assert_eq!(Contracts::code_hash(&address(1)), None);
+	});
+}
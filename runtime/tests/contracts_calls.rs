@@ -0,0 +1,77 @@
+//! Summaries of contract calls.
+mod common;
+
+use codec::Encode;
+use common::*;
+use pallet_contracts_dev_rpc_runtime_api::{CallKind, CallResult, ContractEvent};
+use swanky_runtime::{
+	contracts_calls::{self, Summary},
+	contracts_debug::{ContractCall, Target},
+	AccountId, Contracts, Hash, UNIT,
+};
+
+const CONTRACT: &str = "call_trace_test";
+
+fn deploy(salt: u8) -> AccountId {
+	instantiate_with_salt(ALICE, load_contract(CONTRACT), 1_000 * UNIT, vec![salt])
+}
+
+fn summarize(contract: AccountId, input: Vec<u8>) -> Summary {
+	contracts_calls::summarize(ContractCall {
+		origin: ALICE,
+		target: Target::Call(contract),
+		value: 0,
+		gas_limit: GAS_LIMIT,
+		storage_deposit_limit: None,
+		data: input,
+	})
+}
+
+/// Input of `relay(callees)`.
+fn relay(callees: Vec<AccountId>) -> Vec<u8> {
+	let mut input = selector(1);
+	callees.encode_to(&mut input);
+	input
+}
+
+/// `Relayed` event emitted by `contract`.
+fn relayed(contract: &AccountId, remaining: u32) -> ContractEvent<AccountId, Hash> {
+	ContractEvent {
+		contract: contract.clone(),
+		code_hash: Contracts::code_hash(contract),
+		data: (0u8, remaining).encode(),
+	}
+}
+
+#[test]
+fn outermost_call_is_summarized_with_events_of_nested_calls() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (first, second) = (deploy(1), deploy(2));
+
+		let call = summarize(first.clone(), relay(vec![second.clone()]));
+
+		assert_eq!(call.kind, CallKind::Call);
+		assert_eq!((&call.caller, &call.callee), (&ALICE, &first));
+		assert_eq!(call.code_hash, Contracts::code_hash(&first));
+		assert!(call.code_hash.is_some());
+		assert_eq!(call.selector, Some([0, 0, 0, 1]));
+		assert_eq!(call.input, relay(vec![second.clone()]));
+		assert_eq!(call.value, 0);
+		assert!(call.gas_consumed.ref_time() > 0);
+		assert_eq!(call.result, CallResult::Returned(Ok::<(), u8>(()).encode()));
+		assert_eq!(call.events, vec![relayed(&first, 1), relayed(&second, 0)]);
+	});
+}
+
+#[test]
+fn reverted_call_has_no_events() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy(1);
+
+		let call = summarize(contract.clone(), selector(2));
+
+		assert_eq!((&call.caller, &call.callee), (&ALICE, &contract));
+		assert_eq!(call.result, CallResult::Reverted(Ok::<Result<(), u8>, u8>(Err(1)).encode()));
+		assert!(call.events.is_empty());
+	});
+}
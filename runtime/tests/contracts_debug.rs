@@ -0,0 +1,93 @@
+//! Replays contract calls of extrinsics with debug output enabled.
+mod common;
+
+use codec::Encode;
+use common::*;
+use frame_support::{traits::Get, weights::Weight};
+use pallet_uniques::WeightInfo;
+use sp_runtime::{generic::Era, MultiAddress, MultiSignature};
+use swanky_runtime::{
+	contracts_debug, AccountId, Runtime, RuntimeCall, SignedExtra, SystemCall, UncheckedExtrinsic,
+	UNIT,
+};
+
+const CONTRACT: &str = "uniques_extension_test";
+const COLLECTION: u32 = 1;
+
+fn deploy() -> AccountId {
+	instantiate(ALICE, load_contract(CONTRACT), 1_000 * UNIT)
+}
+
+/// Debug message of a call to the uniques extension returning `Success`.
+fn traced(func_id: u16, input_len: u32, charged: Weight, output: Option<&str>) -> Vec<u8> {
+	let output = output.map(|output| format!("output 0x{}, ", output)).unwrap_or_default();
+	format!(
+		"chain extension 100 function {}: input of {} bytes, charged {}/{}, {}returned 0\n",
+		func_id,
+		input_len,
+		charged.ref_time(),
+		charged.proof_size(),
+		output
+	)
+	.into_bytes()
+}
+
+/// Signed extrinsic whose signature isn't checked by the replay.
+fn extrinsic(who: AccountId, call: RuntimeCall) -> UncheckedExtrinsic {
+	let extra: SignedExtra = (
+		frame_system::CheckNonZeroSender::new(),
+		frame_system::CheckSpecVersion::new(),
+		frame_system::CheckTxVersion::new(),
+		frame_system::CheckGenesis::new(),
+		frame_system::CheckEra::from(Era::Immortal),
+		frame_system::CheckNonce::from(0),
+		frame_system::CheckWeight::new(),
+		pallet_transaction_payment::ChargeTransactionPayment::from(0),
+	);
+	let signature = MultiSignature::Sr25519(sp_core::sr25519::Signature([0u8; 64]));
+	UncheckedExtrinsic::new_signed(call, MultiAddress::Id(who), signature, extra)
+}
+
+fn call_contract(contract: AccountId, input: Vec<u8>) -> RuntimeCall {
+	RuntimeCall::Contracts(pallet_contracts::Call::call {
+		dest: MultiAddress::Id(contract),
+		value: 0,
+		gas_limit: GAS_LIMIT,
+		storage_deposit_limit: None,
+		data: input,
+	})
+}
+
+#[test]
+fn debug_messages_of_contract_calls_are_returned() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		let mut input = selector(10);
+		COLLECTION.encode_to(&mut input);
+
+		let messages = contracts_debug::debug_messages(vec![
+			extrinsic(ALICE, RuntimeCall::System(SystemCall::remark { remark: vec![] })),
+			extrinsic(ALICE, call_contract(contract, input)),
+		]);
+
+		let charged = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+		assert_eq!(messages, vec![(1, traced(9, 4, charged, Some("00")))]);
+	});
+}
+
+#[test]
+fn replay_leaves_no_changes() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		let mut input = selector(1);
+		COLLECTION.encode_to(&mut input);
+
+		// The signature is invalid, so only the dry-run would create the collection.
+		let messages =
+			contracts_debug::debug_messages(vec![extrinsic(ALICE, call_contract(contract, input))]);
+
+		let charged = <Runtime as pallet_uniques::Config>::WeightInfo::create();
+		assert_eq!(messages, vec![(0, traced(0, 36, charged, None))]);
+		assert!(!pallet_uniques::Collection::<Runtime>::contains_key(COLLECTION));
+	});
+}
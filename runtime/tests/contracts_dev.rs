@@ -0,0 +1,175 @@
+//! Changes deployed contracts with the unsigned calls the node submits.
+mod common;
+
+use common::*;
+use frame_support::{assert_noop, assert_ok, unsigned::ValidateUnsigned};
+use pallet_contracts_dev::GenesisContract;
+use sp_runtime::{
+	traits::{BlakeTwo256, Dispatchable, Hash as _},
+	transaction_validity::TransactionSource,
+	DispatchError, DispatchResult,
+};
+use swanky_runtime::{
+	contracts_registry, AccountId, Balance, Contracts, ContractsDev, Runtime, RuntimeCall,
+	RuntimeEvent, RuntimeOrigin, System, UNIT,
+};
+
+fn upgrade_code(contract: &AccountId, name: &str) -> pallet_contracts_dev::Call<Runtime> {
+	pallet_contracts_dev::Call::upgrade_code {
+		owner: BOB,
+		code: load_contract(name),
+		contracts: vec![contract.clone()],
+		magic_number: 0,
+	}
+}
+
+fn dispatch(call: pallet_contracts_dev::Call<Runtime>) -> DispatchResult {
+	let call: RuntimeCall = call.into();
+	call.dispatch(RuntimeOrigin::none()).map(|_| ()).map_err(|e| e.error)
+}
+
+#[test]
+fn code_is_swapped_keeping_storage() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate(ALICE, load_contract("overrides_test"), 0);
+		let old_code_hash = BlakeTwo256::hash(&load_contract("overrides_test"));
+		let new_code_hash = BlakeTwo256::hash(&load_contract("call_trace_test"));
+
+		assert_ok!(dispatch(upgrade_code(&contract, "call_trace_test")));
+
+		assert_eq!(Contracts::code_hash(&contract), Some(new_code_hash));
+		assert!(System::events().iter().any(|record| record.event ==
+			RuntimeEvent::ContractsDev(pallet_contracts_dev::Event::CodeUpgraded {
+				contract: contract.clone(),
+				old_code_hash,
+				new_code_hash,
+			})));
+		let code = contracts_registry::codes()
+			.into_iter()
+			.find(|code| code.code_hash == new_code_hash)
+			.unwrap();
+		assert_eq!((code.owner, code.refcount), (BOB, 1));
+
+		// Switching back to code which is already uploaded reads the storage written before.
+		assert_ok!(dispatch(upgrade_code(&contract, "overrides_test")));
+		assert_eq!(call_message::<u32>(ALICE, contract, selector(1)), 1);
+	});
+}
+
+#[test]
+fn only_the_local_node_may_upgrade_code() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate(ALICE, load_contract("overrides_test"), 0);
+		let call = upgrade_code(&contract, "call_trace_test");
+
+		assert!(ContractsDev::validate_unsigned(TransactionSource::External, &call).is_err());
+		assert!(ContractsDev::validate_unsigned(TransactionSource::Local, &call).is_ok());
+		let call: RuntimeCall = call.into();
+		assert_noop!(
+			call.dispatch(RuntimeOrigin::signed(ALICE)).map_err(|e| e.error),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn unknown_contract_is_refused() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			dispatch(upgrade_code(&ALICE, "call_trace_test")),
+			pallet_contracts_dev::Error::<Runtime>::ContractNotFound
+		);
+	});
+}
+
+fn genesis_contract(selector_id: u32, salt: u8) -> GenesisContract<AccountId, Balance> {
+	GenesisContract {
+		deployer: ALICE,
+		code: load_contract("overrides_test"),
+		selector: selector(selector_id),
+		args: vec![],
+		endowment: UNIT,
+		salt: vec![salt],
+	}
+}
+
+#[test]
+fn genesis_contracts_are_instantiated() {
+	ExtBuilder::default()
+		.contracts(vec![genesis_contract(0, 1), genesis_contract(0, 2)])
+		.build()
+		.execute_with(|| {
+			let code_hash = BlakeTwo256::hash(&load_contract("overrides_test"));
+			for salt in [1, 2] {
+				let contract =
+					Contracts::contract_address(&ALICE, &code_hash, &selector(0), &[salt]);
+				assert_eq!(Contracts::code_hash(&contract), Some(code_hash));
+				assert!(balance_of(&contract) >= UNIT);
+				assert_eq!(call_message::<u32>(ALICE, contract, selector(1)), 1);
+			}
+		});
+}
+
+#[test]
+#[should_panic(expected = "genesis contract 1")]
+fn failing_genesis_contract_fails_the_genesis() {
+	ExtBuilder::default()
+		.contracts(vec![genesis_contract(0, 1), genesis_contract(9, 2)])
+		.build();
+}
+
+#[test]
+fn killed_contract_is_removed_and_refunded() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate(ALICE, load_contract("overrides_test"), UNIT);
+		let code_hash = Contracts::code_hash(&contract).unwrap();
+		let details = contracts_registry::contract_info(contract.clone()).unwrap();
+		let bob_balance = balance_of(&BOB);
+
+		assert_ok!(dispatch(pallet_contracts_dev::Call::kill_contract {
+			contract: contract.clone(),
+			beneficiary: BOB,
+			magic_number: 0,
+		}));
+
+		assert_eq!(Contracts::code_hash(&contract), None);
+		assert_eq!(balance_of(&contract), 0);
+		assert!(balance_of(&BOB) >= bob_balance + details.balance + details.storage_deposit);
+		let code = contracts_registry::codes()
+			.into_iter()
+			.find(|code| code.code_hash == code_hash)
+			.unwrap();
+		assert_eq!(code.refcount, 0);
+		assert!(System::events().iter().any(|record| record.event ==
+			RuntimeEvent::ContractsDev(pallet_contracts_dev::Event::ContractKilled {
+				contract: contract.clone(),
+				beneficiary: BOB,
+			})));
+	});
+}
+
+#[test]
+fn reset_contract_keeps_its_code() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = instantiate(ALICE, load_contract("overrides_test"), 0);
+		let code_hash = Contracts::code_hash(&contract);
+
+		assert_ok!(dispatch(pallet_contracts_dev::Call::reset_contract_storage {
+			contract: contract.clone(),
+			magic_number: 0,
+		}));
+
+		assert_eq!(Contracts::code_hash(&contract), code_hash);
+		let details = contracts_registry::contract_info(contract.clone()).unwrap();
+		assert_eq!((details.storage_items, details.storage_bytes), (0, 0));
+		// The storage root written by the constructor is gone.
+		assert!(Contracts::get_storage(contract.clone(), vec![0; 4]).unwrap().is_none());
+		assert_noop!(
+			dispatch(pallet_contracts_dev::Call::reset_contract_storage {
+				contract: ALICE,
+				magic_number: 0,
+			}),
+			pallet_contracts_dev::Error::<Runtime>::ContractNotFound
+		);
+	});
+}
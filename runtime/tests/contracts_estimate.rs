@@ -0,0 +1,119 @@
+//! Searches the smallest limits with which contract calls, instantiations and uploads succeed.
+mod common;
+
+use codec::Encode;
+use common::*;
+use pallet_contracts_dev_rpc_runtime_api::{CallResult, EstimateRequest, StorageDeposit};
+use pallet_contracts_primitives::Code;
+use swanky_runtime::{
+	contracts_debug::{rolled_back, ContractCall, Target},
+	contracts_estimate::{self, Estimate},
+	AccountId, Contracts, Weight, UNIT,
+};
+
+const CONTRACT: &str = "call_trace_test";
+
+fn deploy(salt: u8) -> AccountId {
+	instantiate_with_salt(ALICE, load_contract(CONTRACT), 1_000 * UNIT, vec![salt])
+}
+
+/// Input of `relay(callees)`.
+fn relay(callees: Vec<AccountId>) -> Vec<u8> {
+	let mut input = selector(1);
+	callees.encode_to(&mut input);
+	input
+}
+
+fn estimate_call(contract: AccountId, input: Vec<u8>) -> Estimate {
+	contracts_estimate::estimate(EstimateRequest::Call {
+		origin: ALICE,
+		dest: contract,
+		value: 0,
+		input_data: input,
+	})
+}
+
+/// Whether calling `contract` with `input` succeeds with the given limits.
+fn succeeds(contract: &AccountId, input: &[u8], gas_limit: Weight, deposit_limit: u128) -> bool {
+	let call = ContractCall {
+		origin: ALICE,
+		target: Target::Call(contract.clone()),
+		value: 0,
+		gas_limit,
+		storage_deposit_limit: Some(deposit_limit),
+		data: input.to_vec(),
+	};
+	matches!(rolled_back(|| call.dry_run()).result, Ok(output) if !output.did_revert())
+}
+
+#[test]
+fn call_limits_are_the_smallest_that_succeed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (first, second) = (deploy(1), deploy(2));
+		let input = relay(vec![second]);
+
+		let estimate = estimate_call(first.clone(), input.clone());
+
+		assert_eq!(estimate.result, CallResult::Returned(Ok::<(), u8>(()).encode()));
+		assert_eq!(estimate.account_id, None);
+		let (gas, deposit) = (estimate.gas_limit, estimate.storage_deposit_limit);
+		assert!(succeeds(&first, &input, gas, deposit));
+		let less_ref_time = Weight::from_parts(gas.ref_time() - 1, gas.proof_size());
+		assert!(!succeeds(&first, &input, less_ref_time, deposit));
+		let less_proof_size = Weight::from_parts(gas.ref_time(), gas.proof_size() - 1);
+		assert!(!succeeds(&first, &input, less_proof_size, deposit));
+	});
+}
+
+#[test]
+fn reverted_call_is_returned_as_is() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy(1);
+
+		let estimate = estimate_call(contract, selector(2));
+
+		assert_eq!(
+			estimate.result,
+			CallResult::Reverted(Ok::<Result<(), u8>, u8>(Err(1)).encode())
+		);
+		assert_eq!(estimate.storage_deposit_limit, 0);
+	});
+}
+
+#[test]
+fn instantiation_returns_the_contract() {
+	ExtBuilder::default().build().execute_with(|| {
+		let estimate = contracts_estimate::estimate(EstimateRequest::Instantiate {
+			origin: ALICE,
+			value: UNIT,
+			code: Code::Upload(load_contract(CONTRACT)),
+			data: selector(0),
+			salt: vec![1],
+		});
+
+		assert!(matches!(estimate.result, CallResult::Returned(_)));
+		assert!(estimate.account_id.is_some());
+		assert!(estimate.code_hash.is_some());
+		assert!(matches!(estimate.storage_deposit, StorageDeposit::Charge(amount) if amount > 0));
+		assert_eq!(
+			estimate.storage_deposit,
+			StorageDeposit::Charge(estimate.storage_deposit_limit)
+		);
+	});
+}
+
+#[test]
+fn upload_returns_the_code_hash_and_deposit() {
+	ExtBuilder::default().build().execute_with(|| {
+		let estimate = contracts_estimate::estimate(EstimateRequest::Upload {
+			origin: ALICE,
+			code: load_contract(CONTRACT),
+		});
+
+		assert_eq!(estimate.result, CallResult::Completed);
+		assert!(estimate.code_hash.is_some());
+		assert!(estimate.storage_deposit_limit > 0);
+		// The estimate is rolled back, the code is uploaded again by the instantiation.
+		assert_eq!(Contracts::code_hash(&deploy(1)), estimate.code_hash);
+	});
+}
@@ -0,0 +1,137 @@
+//! Dry-runs contract calls on top of temporary overrides of the state and block context.
+mod common;
+
+use codec::{Decode, Encode};
+use common::*;
+use pallet_contracts_dev_rpc_runtime_api::{AccountOverride, CallResult};
+use swanky_runtime::{
+	contracts_debug::{ContractCall, Target},
+	contracts_overrides::{self, CallOutcome, Overrides},
+	AccountId, Balance, Contracts, System, Timestamp, UNIT,
+};
+
+const CONTRACT: &str = "overrides_test";
+
+fn deploy() -> AccountId {
+	instantiate(ALICE, load_contract(CONTRACT), 1_000 * UNIT)
+}
+
+fn call(contract: &AccountId, id: u32, overrides: Overrides) -> CallOutcome {
+	let call = ContractCall {
+		origin: ALICE,
+		target: Target::Call(contract.clone()),
+		value: 0,
+		gas_limit: GAS_LIMIT,
+		storage_deposit_limit: None,
+		data: selector(id),
+	};
+	contracts_overrides::call_with_overrides(call, overrides).expect("overrides are applied")
+}
+
+/// Decodes the return value of an ink! message.
+fn returned<R: Decode>(outcome: CallOutcome) -> R {
+	let CallResult::Returned(output) = outcome.result else {
+		panic!("call didn't return: {:?}", outcome.result)
+	};
+	<Result<R, u8>>::decode(&mut &output[..]).unwrap().unwrap()
+}
+
+fn account(address: &AccountId) -> AccountOverride<AccountId, Balance, swanky_runtime::Hash> {
+	AccountOverride {
+		address: address.clone(),
+		free_balance: None,
+		code_hash: None,
+		storage: vec![],
+	}
+}
+
+fn no_overrides() -> Overrides {
+	Overrides { accounts: vec![], block_number: None, timestamp: None }
+}
+
+#[test]
+fn block_context_is_overridden() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		let overrides =
+			Overrides { block_number: Some(1_000), timestamp: Some(42_000), ..no_overrides() };
+
+		assert_eq!(returned::<u32>(call(&contract, 2, overrides.clone())), 1_000);
+		assert_eq!(returned::<u64>(call(&contract, 3, overrides)), 42_000);
+
+		// Overrides are discarded after the call.
+		assert_eq!(System::block_number(), 1);
+		assert_eq!(returned::<u64>(call(&contract, 3, no_overrides())), Timestamp::now());
+	});
+}
+
+#[test]
+fn balance_and_storage_are_overridden() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		let balance = balance_of(&contract);
+		let overrides = Overrides {
+			accounts: vec![AccountOverride {
+				free_balance: Some(5 * UNIT),
+				storage: vec![(vec![0, 0, 0, 0], Some(7u32.encode()))],
+				..account(&contract)
+			}],
+			..no_overrides()
+		};
+
+		assert_eq!(returned::<u32>(call(&contract, 1, overrides.clone())), 7);
+		assert_eq!(returned::<Balance>(call(&contract, 4, overrides)), 5 * UNIT);
+
+		assert_eq!(returned::<u32>(call(&contract, 1, no_overrides())), 1);
+		assert_eq!(balance_of(&contract), balance);
+	});
+}
+
+#[test]
+fn code_hash_is_overridden() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		let code_hash = Contracts::code_hash(&contract);
+		let other = instantiate(ALICE, load_contract("call_trace_test"), UNIT);
+		let overrides = Overrides {
+			accounts: vec![AccountOverride {
+				code_hash: Contracts::code_hash(&other),
+				..account(&contract)
+			}],
+			..no_overrides()
+		};
+
+		// Selector 2 is `fail` in the other contract.
+		let outcome = call(&contract, 2, overrides);
+		assert_eq!(outcome.result, CallResult::Reverted(Ok::<Result<(), u8>, u8>(Err(1)).encode()));
+
+		assert_eq!(Contracts::code_hash(&contract), code_hash);
+	});
+}
+
+#[test]
+fn storage_of_non_contract_is_refused() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		let call = ContractCall {
+			origin: ALICE,
+			target: Target::Call(contract),
+			value: 0,
+			gas_limit: GAS_LIMIT,
+			storage_deposit_limit: None,
+			data: selector(1),
+		};
+		let overrides = Overrides {
+			accounts: vec![AccountOverride {
+				storage: vec![(vec![0, 0, 0, 0], None)],
+				..account(&BOB)
+			}],
+			..no_overrides()
+		};
+
+		assert_eq!(
+			contracts_overrides::call_with_overrides(call, overrides).map(|_| ()),
+			Err(pallet_contracts::Error::<swanky_runtime::Runtime>::ContractNotFound.into())
+		);
+	});
+}
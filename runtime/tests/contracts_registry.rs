@@ -0,0 +1,64 @@
+//! Lists the contracts deployed and code uploaded on the chain.
+mod common;
+
+use common::*;
+use sp_runtime::traits::{BlakeTwo256, Hash as _};
+use swanky_runtime::{contracts_registry, Contracts, UNIT};
+
+const CONTRACT: &str = "call_trace_test";
+
+#[test]
+fn deployed_contracts_are_listed() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(contracts_registry::contracts().is_empty());
+
+		let first = instantiate_with_salt(ALICE, load_contract(CONTRACT), UNIT, vec![1]);
+		let second = instantiate_with_salt(BOB, load_contract(CONTRACT), 0, vec![2]);
+
+		let contracts = contracts_registry::contracts();
+		assert_eq!(contracts.len(), 2);
+		let details = contracts_registry::contract_info(first.clone()).unwrap();
+		assert!(contracts.contains(&details));
+		assert!(contracts.iter().any(|contract| contract.address == second));
+
+		assert_eq!(details.code_hash, BlakeTwo256::hash(&load_contract(CONTRACT)));
+		assert!(details.storage_deposit > 0);
+		assert!(details.storage_bytes > 0);
+		assert_eq!(details.balance, balance_of(&first));
+	});
+}
+
+#[test]
+fn unknown_contract_has_no_info() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(contracts_registry::contract_info(ALICE), None);
+	});
+}
+
+#[test]
+fn uploaded_code_is_listed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let code = load_contract(CONTRACT);
+		instantiate_with_salt(ALICE, code.clone(), 0, vec![1]);
+		instantiate_with_salt(BOB, code.clone(), 0, vec![2]);
+
+		let codes = contracts_registry::codes();
+		assert_eq!(codes.len(), 1);
+		assert_eq!(codes[0].code_hash, BlakeTwo256::hash(&code));
+		assert_eq!(codes[0].size as usize, code.len());
+		assert!(codes[0].deterministic);
+		assert_eq!(codes[0].refcount, 2);
+		assert_eq!(codes[0].owner, ALICE);
+		assert!(codes[0].deposit > 0);
+	});
+}
+
+#[test]
+fn predicted_address_is_assigned() {
+	ExtBuilder::default().build().execute_with(|| {
+		let code_hash = BlakeTwo256::hash(&load_contract(CONTRACT));
+		let predicted = Contracts::contract_address(&BOB, &code_hash, &selector(0), &[7]);
+		assert_eq!(instantiate_with_salt(BOB, load_contract(CONTRACT), 0, vec![7]), predicted);
+		assert_ne!(Contracts::contract_address(&BOB, &code_hash, &selector(0), &[8]), predicted);
+	});
+}
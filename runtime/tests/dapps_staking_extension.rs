@@ -0,0 +1,86 @@
+//! Calls the dApps staking chain extension from an ink! contract.
+mod common;
+
+use codec::Encode;
+use common::*;
+use frame_support::assert_ok;
+use swanky_runtime::{AccountId, DappsStaking, RuntimeOrigin, SmartContract, UNIT};
+
+const CONTRACT: &str = "dapps_staking_extension_test";
+
+fn deploy_and_register() -> AccountId {
+	let contract = instantiate(ALICE, load_contract(CONTRACT), 1_000 * UNIT);
+	assert_ok!(DappsStaking::register(
+		RuntimeOrigin::root(),
+		ALICE,
+		SmartContract::Wasm(contract.clone()),
+	));
+	contract
+}
+
+#[test]
+fn read_current_era_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy_and_register();
+
+		let era: u32 = call_message(ALICE, contract, selector(1));
+		assert_eq!(era, DappsStaking::current_era());
+	});
+}
+
+#[test]
+fn bond_and_stake_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy_and_register();
+		let value = 500 * UNIT;
+
+		let mut input = selector(3);
+		value.encode_to(&mut input);
+		let result: Result<(), u32> = call_message(ALICE, contract.clone(), input);
+		assert_eq!(result, Ok(()));
+
+		assert_eq!(DappsStaking::ledger(&contract).locked, value);
+
+		let mut input = selector(2);
+		contract.encode_to(&mut input);
+		let staked: u128 = call_message(ALICE, contract, input);
+		assert_eq!(staked, value);
+	});
+}
+
+#[test]
+fn unbond_and_unstake_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy_and_register();
+		let value = 500 * UNIT;
+
+		let mut input = selector(3);
+		value.encode_to(&mut input);
+		let result: Result<(), u32> = call_message(ALICE, contract.clone(), input);
+		assert_eq!(result, Ok(()));
+
+		let mut input = selector(4);
+		(value / 2).encode_to(&mut input);
+		let result: Result<(), u32> = call_message(ALICE, contract.clone(), input);
+		assert_eq!(result, Ok(()));
+
+		let mut input = selector(2);
+		contract.encode_to(&mut input);
+		let staked: u128 = call_message(ALICE, contract, input);
+		assert_eq!(staked, value / 2);
+	});
+}
+
+#[test]
+fn errors_are_returned_as_status_codes() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Not registered, so staking on it must fail without reverting the contract call.
+		let contract = instantiate(ALICE, load_contract(CONTRACT), 1_000 * UNIT);
+
+		let mut input = selector(3);
+		(500 * UNIT).encode_to(&mut input);
+		let result: Result<(), u32> = call_message(ALICE, contract.clone(), input);
+		assert!(result.is_err());
+		assert_eq!(DappsStaking::ledger(&contract).locked, 0);
+	});
+}
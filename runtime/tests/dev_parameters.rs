@@ -0,0 +1,32 @@
+//! Production parameters set through `DevParameters`.
+mod common;
+
+use common::*;
+use frame_support::{assert_noop, assert_ok};
+use pallet_contracts::Determinism;
+use sp_runtime::traits::Dispatchable;
+use swanky_runtime::{DevParameters, Runtime, RuntimeCall, RuntimeOrigin};
+
+#[test]
+fn max_code_len_is_checked_on_upload_only() {
+	ExtBuilder::default().build().execute_with(|| {
+		let code = load_contract("overrides_test");
+		let contract = instantiate(ALICE, code.clone(), 0);
+
+		let mut parameters = DevParameters::contracts_parameters();
+		parameters.max_code_len = code.len() as u32 - 1;
+		assert_ok!(DevParameters::set_contracts_parameters(RuntimeOrigin::root(), parameters));
+
+		let upload = RuntimeCall::Contracts(pallet_contracts::Call::upload_code {
+			code,
+			storage_deposit_limit: None,
+			determinism: Determinism::Enforced,
+		});
+		assert_noop!(
+			upload.dispatch(RuntimeOrigin::signed(BOB)),
+			frame_system::Error::<Runtime>::CallFiltered
+		);
+		// Code uploaded before the limit was lowered still runs.
+		assert_eq!(call_message::<u32>(ALICE, contract, selector(1)), 1);
+	});
+}
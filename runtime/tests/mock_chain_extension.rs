@@ -0,0 +1,86 @@
+//! Answers chain extension calls of an ink! contract with mocked responses.
+mod common;
+
+use codec::Encode;
+use common::*;
+use frame_support::assert_ok;
+use swanky_runtime::{AccountId, MockChainExtension, MockResponse, RuntimeOrigin, UNIT};
+
+const CONTRACT: &str = "uniques_extension_test";
+const ASSETS_EXT_ID: u16 = 2;
+const UNIQUES_EXT_ID: u16 = 100;
+const COLLECTION: u32 = 1;
+const ITEM: u32 = 7;
+
+// Status code of `pallet_chain_extension_uniques::UniquesError::NoPermission`.
+const NO_PERMISSION: u8 = 1;
+
+fn deploy() -> AccountId {
+	instantiate(ALICE, load_contract(CONTRACT), 1_000 * UNIT)
+}
+
+fn input(id: u32, args: impl Encode) -> Vec<u8> {
+	let mut input = selector(id);
+	args.encode_to(&mut input);
+	input
+}
+
+fn mock(ext_id: u16, func_id: u16, ret_code: u32, output: Vec<u8>) {
+	let mock = MockResponse { ret_code, output: output.try_into().unwrap() };
+	assert_ok!(MockChainExtension::mock_chain_extension(
+		RuntimeOrigin::none(),
+		ext_id,
+		func_id,
+		mock,
+		0
+	));
+}
+
+#[test]
+fn mocked_query_returns_mocked_output() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		// `owner` of the uniques extension.
+		mock(UNIQUES_EXT_ID, 8, 0, Some(BOB).encode());
+
+		let owner: Option<AccountId> = call_message(ALICE, contract, input(9, (COLLECTION, ITEM)));
+		assert_eq!(owner, Some(BOB));
+	});
+}
+
+#[test]
+fn mocked_status_code_reaches_contract() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		// `create` of the uniques extension.
+		mock(UNIQUES_EXT_ID, 0, NO_PERMISSION as u32, vec![]);
+
+		let result: Result<(), u8> = call_message(ALICE, contract, input(1, COLLECTION));
+		assert_eq!(result, Err(NO_PERMISSION));
+	});
+}
+
+#[test]
+fn unmocked_functions_reach_extension() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		mock(UNIQUES_EXT_ID, 8, 0, Some(BOB).encode());
+
+		// `collection_owner` isn't mocked, the collection doesn't exist.
+		let owner: Option<AccountId> = call_message(ALICE, contract, input(10, COLLECTION));
+		assert_eq!(owner, None);
+	});
+}
+
+#[test]
+fn mocks_of_other_extensions_dont_answer() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		// Function 8 of the assets extension, not `owner` of the uniques extension.
+		mock(ASSETS_EXT_ID, 8, 0, Some(BOB).encode());
+
+		// The item doesn't exist.
+		let owner: Option<AccountId> = call_message(ALICE, contract, input(9, (COLLECTION, ITEM)));
+		assert_eq!(owner, None);
+	});
+}
@@ -0,0 +1,164 @@
+//! Calls the uniques chain extension from an ink! contract.
+mod common;
+
+use codec::Encode;
+use common::*;
+use frame_support::traits::tokens::nonfungibles::Inspect;
+use swanky_runtime::{AccountId, Uniques, UNIT};
+
+const CONTRACT: &str = "uniques_extension_test";
+const COLLECTION: u32 = 1;
+const ITEM: u32 = 7;
+
+// Status codes of `pallet_chain_extension_uniques::UniquesError`.
+const NO_PERMISSION: u8 = 1;
+const UNKNOWN_COLLECTION: u8 = 2;
+const TOO_LONG: u8 = 15;
+
+fn deploy() -> AccountId {
+	instantiate(ALICE, load_contract(CONTRACT), 1_000 * UNIT)
+}
+
+fn exec(contract: &AccountId, id: u32, args: impl Encode) -> Result<(), u8> {
+	let mut input = selector(id);
+	args.encode_to(&mut input);
+	call_message(ALICE, contract.clone(), input)
+}
+
+fn query<R: codec::Decode>(contract: &AccountId, id: u32, args: impl Encode) -> R {
+	let mut input = selector(id);
+	args.encode_to(&mut input);
+	call_message(ALICE, contract.clone(), input)
+}
+
+fn owner_of(item: u32) -> Option<AccountId> {
+	<Uniques as Inspect<AccountId>>::owner(&COLLECTION, &item)
+}
+
+fn create_and_mint(contract: &AccountId, owner: &AccountId) {
+	assert_eq!(exec(contract, 1, COLLECTION), Ok(()));
+	assert_eq!(exec(contract, 2, (COLLECTION, ITEM, owner)), Ok(()));
+}
+
+#[test]
+fn create_collection_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+
+		assert_eq!(exec(&contract, 1, COLLECTION), Ok(()));
+
+		let owner = <Uniques as Inspect<AccountId>>::collection_owner(&COLLECTION);
+		assert_eq!(owner, Some(contract.clone()));
+		let owner: Option<AccountId> = query(&contract, 10, COLLECTION);
+		assert_eq!(owner, Some(contract));
+	});
+}
+
+#[test]
+fn mint_and_owner_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		create_and_mint(&contract, &BOB);
+
+		assert_eq!(owner_of(ITEM), Some(BOB));
+		let owner: Option<AccountId> = query(&contract, 9, (COLLECTION, ITEM));
+		assert_eq!(owner, Some(BOB));
+		let owner: Option<AccountId> = query(&contract, 9, (COLLECTION, ITEM + 1));
+		assert_eq!(owner, None);
+	});
+}
+
+#[test]
+fn mint_into_unknown_collection_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+
+		assert_eq!(exec(&contract, 2, (COLLECTION, ITEM, BOB)), Err(UNKNOWN_COLLECTION));
+	});
+}
+
+#[test]
+fn transfer_and_burn_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		create_and_mint(&contract, &contract);
+
+		assert_eq!(exec(&contract, 4, (COLLECTION, ITEM, BOB)), Ok(()));
+		assert_eq!(owner_of(ITEM), Some(BOB));
+
+		// The contract is the collection admin, so it may burn items it doesn't own.
+		assert_eq!(exec(&contract, 3, (COLLECTION, ITEM)), Ok(()));
+		assert_eq!(owner_of(ITEM), None);
+	});
+}
+
+#[test]
+fn transfer_requires_permission() {
+	ExtBuilder::default().build().execute_with(|| {
+		let issuer = deploy();
+		let other = deploy();
+		create_and_mint(&issuer, &BOB);
+
+		assert_eq!(exec(&other, 4, (COLLECTION, ITEM, ALICE)), Err(NO_PERMISSION));
+		assert_eq!(owner_of(ITEM), Some(BOB));
+	});
+}
+
+#[test]
+fn approved_contract_can_transfer() {
+	ExtBuilder::default().build().execute_with(|| {
+		let issuer = deploy();
+		let marketplace = deploy();
+		create_and_mint(&issuer, &issuer);
+
+		assert_eq!(exec(&issuer, 5, (COLLECTION, ITEM, &marketplace)), Ok(()));
+		assert_eq!(exec(&marketplace, 4, (COLLECTION, ITEM, BOB)), Ok(()));
+		assert_eq!(owner_of(ITEM), Some(BOB));
+	});
+}
+
+#[test]
+fn cancel_approval_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let issuer = deploy();
+		let marketplace = deploy();
+		create_and_mint(&issuer, &issuer);
+
+		assert_eq!(exec(&issuer, 5, (COLLECTION, ITEM, &marketplace)), Ok(()));
+		assert_eq!(exec(&issuer, 6, (COLLECTION, ITEM)), Ok(()));
+		assert_eq!(exec(&marketplace, 4, (COLLECTION, ITEM, BOB)), Err(NO_PERMISSION));
+	});
+}
+
+#[test]
+fn attributes_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		create_and_mint(&contract, &BOB);
+		let key = b"color".to_vec();
+
+		assert_eq!(exec(&contract, 7, (COLLECTION, Some(ITEM), &key, b"red".to_vec())), Ok(()));
+		assert_eq!(exec(&contract, 7, (COLLECTION, None::<u32>, &key, b"blue".to_vec())), Ok(()));
+
+		let value: Option<Vec<u8>> = query(&contract, 11, (COLLECTION, ITEM, &key));
+		assert_eq!(value, Some(b"red".to_vec()));
+		let value: Option<Vec<u8>> = query(&contract, 12, (COLLECTION, &key));
+		assert_eq!(value, Some(b"blue".to_vec()));
+
+		assert_eq!(exec(&contract, 8, (COLLECTION, Some(ITEM), &key)), Ok(()));
+		let value: Option<Vec<u8>> = query(&contract, 11, (COLLECTION, ITEM, &key));
+		assert_eq!(value, None);
+	});
+}
+
+#[test]
+fn too_long_attribute_key_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = deploy();
+		create_and_mint(&contract, &BOB);
+
+		let key = vec![0u8; 33];
+		let result = exec(&contract, 7, (COLLECTION, Some(ITEM), key, b"red".to_vec()));
+		assert_eq!(result, Err(TOO_LONG));
+	});
+}
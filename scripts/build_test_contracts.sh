@@ -0,0 +1,10 @@
+#!/usr/bin/env bash
+# Builds the ink! contracts under `contracts/` used by the runtime tests.
+set -e
+
+ROOT=$(cd "$(dirname "$0")/.." && pwd)
+
+for manifest in "$ROOT"/contracts/*/Cargo.toml; do
+	echo "Building $(dirname "$manifest")"
+	cargo contract build --release --manifest-path "$manifest"
+done